@@ -0,0 +1,527 @@
+//! Implementation of the subcommands
+
+use std::{
+    path::{Path, PathBuf},
+    thread::sleep,
+    time::Duration,
+};
+
+use base64::Engine;
+use borsh::BorshDeserialize;
+use sns_registrar::{
+    instruction_auto,
+    processor::{delete, quote_price},
+    utils::PriceQuote,
+};
+use sns_registrar_client::{
+    derivation::{pyth_feed_key, referrer_index},
+    resolver::{decode_reverse_lookup, MAX_MULTIPLE_ACCOUNTS, SUBDOMAIN_PREFIX},
+    Deployment, ParentName, RegisterOptions, Resolver, ReverseOptions,
+};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    account::Account,
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+use crate::{
+    config::Config,
+    error::CliError,
+    import::{self, Defaults, ImportEntry, Journal, JournalRecord},
+};
+
+/// State shared by the subcommands
+pub struct Context {
+    pub config: Config,
+    /// Keys of the deployment of the configuration, used by all the instruction builders
+    registrar: Deployment,
+    resolver: Resolver<RpcClient>,
+    keypair: PathBuf,
+    dry_run: bool,
+}
+
+impl Context {
+    pub fn new(config: Config, keypair: PathBuf, dry_run: bool) -> Result<Self, CliError> {
+        let commitment = config
+            .commitment
+            .parse::<CommitmentConfig>()
+            .map_err(|_| CliError::Config(format!("Invalid commitment {}", config.commitment)))?;
+        let rpc = RpcClient::new_with_commitment(config.rpc_endpoint.clone(), commitment);
+        let registrar = config.registrar();
+        Ok(Self {
+            resolver: Resolver::with_deployment(rpc, &config.tld, registrar),
+            registrar,
+            config,
+            keypair,
+            dry_run,
+        })
+    }
+
+    fn rpc(&self) -> &RpcClient {
+        self.resolver.fetcher()
+    }
+
+    fn signer(&self) -> Result<Keypair, CliError> {
+        read_keypair_file(&self.keypair)
+            .map_err(|e| CliError::Keypair(self.keypair.display().to_string(), e.to_string()))
+    }
+
+    /// Sends a transaction signed by `signer`, or simulates it with `--dry-run`
+    fn execute(&self, instructions: &[Instruction], signer: &Keypair) -> Result<(), CliError> {
+        let blockhash = self.rpc().get_latest_blockhash()?;
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &[signer],
+            blockhash,
+        );
+        if !self.dry_run {
+            let signature = self.rpc().send_and_confirm_transaction_with_spinner(&tx)?;
+            println!("Signature: {}", signature);
+            return Ok(());
+        }
+        let simulation = self.rpc().simulate_transaction(&tx)?.value;
+        for log in simulation.logs.unwrap_or_default() {
+            println!("{}", log);
+        }
+        if let Some(units) = simulation.units_consumed {
+            println!("Compute units: {}", units);
+        }
+        match simulation.err {
+            Some(err) => Err(CliError::Simulation(err.to_string())),
+            None => {
+                println!("Simulation succeeded, the transaction was not sent");
+                Ok(())
+            }
+        }
+    }
+
+    /// Splits `sub.alice.sol` into `\0sub` and its parent `alice`, domains have no parent
+    fn split_name<'a>(&self, name: &'a str) -> (String, Option<&'a str>) {
+        match self.config.strip_tld(name).split_once('.') {
+            Some((sub, parent)) => (format!("{}{}", SUBDOMAIN_PREFIX, sub), Some(parent)),
+            None => (self.config.strip_tld(name).to_owned(), None),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn register_name(
+    ctx: &Context,
+    name: &str,
+    token: &str,
+    years: u8,
+    space: u32,
+    metadata_url: Option<String>,
+    referrer: Option<Pubkey>,
+    owner: Option<Pubkey>,
+) -> Result<(), CliError> {
+    let signer = ctx.signer()?;
+    let (mint, _) = ctx.config.token(token)?;
+    let ix = ctx.registrar.register(
+        ctx.config.strip_tld(name),
+        &signer.pubkey(),
+        &mint,
+        RegisterOptions {
+            space,
+            years,
+            metadata_url,
+            referrer,
+            domain_owner: owner,
+            ..Default::default()
+        },
+    )?;
+    ctx.execute(&[ix], &signer)
+}
+
+pub fn create_reverse_lookup(
+    ctx: &Context,
+    name: &str,
+    metadata_url: Option<String>,
+) -> Result<(), CliError> {
+    let signer = ctx.signer()?;
+    let (account_name, parent) = ctx.split_name(name);
+    let parent = parent.map(|parent| ParentName {
+        name_account: ctx.registrar.name_key(parent, None),
+        owner: signer.pubkey(),
+    });
+    let ix = ctx.registrar.create_reverse(
+        &account_name,
+        &signer.pubkey(),
+        ReverseOptions {
+            metadata_url,
+            root_domain: None,
+            parent,
+        },
+    )?;
+    ctx.execute(&[ix], &signer)
+}
+
+pub fn set_metadata(
+    ctx: &Context,
+    name: &str,
+    metadata_url: Option<String>,
+) -> Result<(), CliError> {
+    let signer = ctx.signer()?;
+    let ix = ctx.registrar.update_metadata(
+        ctx.config.strip_tld(name),
+        &signer.pubkey(),
+        metadata_url,
+        None,
+    )?;
+    ctx.execute(&[ix], &signer)
+}
+
+pub fn delete_name(ctx: &Context, name: &str, target: Option<Pubkey>) -> Result<(), CliError> {
+    let signer = ctx.signer()?;
+    let program_id = ctx.registrar.program_id();
+    let domain = ctx.registrar.name_key(ctx.config.strip_tld(name), None);
+    let (reselling_state, _) =
+        Pubkey::find_program_address(&[&domain.to_bytes(), &[1u8, 1u8]], &program_id);
+    let accounts = delete::Accounts {
+        name_service_id: &spl_name_service::ID,
+        system_program: &system_program::ID,
+        domain: &domain,
+        reverse: &ctx.registrar.reverse_key(&domain, None),
+        reselling_state: &reselling_state,
+        state: &ctx.registrar.state_key(&domain),
+        central_state: &ctx.registrar.central_state(),
+        owner: &signer.pubkey(),
+        target: &target.unwrap_or_else(|| signer.pubkey()),
+    };
+    let ix = instruction_auto::delete(program_id, accounts, delete::Params {});
+    ctx.execute(&[ix], &signer)
+}
+
+pub fn resolve(ctx: &Context, name: &str) -> Result<(), CliError> {
+    // `alice` is resolved as `alice.sol`, full names may be under any TLD
+    let resolution = if name.contains('.') {
+        ctx.resolver.resolve(name)?
+    } else {
+        ctx.resolver
+            .resolve(&format!("{}.{}", name, ctx.config.tld))?
+    };
+    println!("Name: {}", resolution.name);
+    println!("Name account: {}", resolution.name_account);
+    println!("Owner: {}", resolution.owner);
+    println!("Destination: {}", resolution.destination);
+    if resolution.tokenized {
+        println!("Tokenized: yes");
+    }
+    if let Some(expiry) = resolution.expiry {
+        println!("Expiry: {} (unix timestamp)", expiry);
+    }
+    if let Some(url) = resolution.metadata_url() {
+        println!("Metadata URL: {}", url);
+    }
+    for record in &resolution.records {
+        println!("{}: {}", record.record.as_str(), record.value);
+    }
+    Ok(())
+}
+
+pub fn reverse(ctx: &Context, name_account: &Pubkey) -> Result<(), CliError> {
+    match ctx.resolver.reverse(name_account)? {
+        Some(reverse_lookup) => {
+            println!("Name: {}", reverse_lookup.name);
+            if let Some(url) = reverse_lookup.metadata_url {
+                println!("Metadata URL: {}", url);
+            }
+        }
+        None => println!("No reverse lookup for {}", name_account),
+    }
+    Ok(())
+}
+
+/// Simulates `QuotePrice`, which sets the quote as return data
+pub fn price(
+    ctx: &Context,
+    name: &str,
+    token: &str,
+    years: u8,
+    referrer: Option<Pubkey>,
+) -> Result<(), CliError> {
+    let payer = ctx.signer()?;
+    let (mint, decimals) = ctx.config.token(token)?;
+    let name = ctx.config.strip_tld(name);
+    let accounts = quote_price::Accounts {
+        root_domain: &ctx.registrar.root_domain(),
+        state: &ctx.registrar.state_key(&ctx.registrar.name_key(name, None)),
+        pyth_feed_account: &pyth_feed_key(&mint)?,
+        tld_opt: None,
+    };
+    let params = quote_price::Params {
+        name: name.to_owned(),
+        years,
+        mint,
+        referrer_idx_opt: referrer.as_ref().map(referrer_index).transpose()?,
+    };
+    let ix = instruction_auto::quote_price(ctx.registrar.program_id(), accounts, params);
+    let blockhash = ctx.rpc().get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+
+    let simulation = ctx.rpc().simulate_transaction(&tx)?.value;
+    if let Some(err) = simulation.err {
+        return Err(CliError::Simulation(format!(
+            "{}\n{}",
+            err,
+            simulation.logs.unwrap_or_default().join("\n")
+        )));
+    }
+    let quote = simulation
+        .return_data
+        .and_then(|data| {
+            base64::engine::general_purpose::STANDARD
+                .decode(data.data.0)
+                .ok()
+        })
+        .and_then(|data| PriceQuote::try_from_slice(&data).ok())
+        .ok_or_else(|| CliError::Simulation("No price quote returned".to_owned()))?;
+
+    let amount = |amount: u64| {
+        format!(
+            "{:.*} {}",
+            decimals as usize,
+            amount as f64 / 10f64.powi(decimals as i32),
+            token.to_uppercase()
+        )
+    };
+    println!("Price: {}", amount(quote.base));
+    println!("Discount: {}", amount(quote.discount));
+    println!(
+        "Total: {}",
+        amount(quote.base.saturating_sub(quote.discount))
+    );
+    if referrer.is_some() {
+        println!("Referrer fee: {}", amount(quote.referrer_fee));
+    }
+    println!("Vault amount: {}", amount(quote.vault_amount));
+    Ok(())
+}
+
+/// Lists the domains of the root domain owned by `owner`, with their reverse lookups
+pub fn list_owned(ctx: &Context, owner: Option<Pubkey>) -> Result<(), CliError> {
+    let owner = match owner {
+        Some(owner) => owner,
+        None => ctx.signer()?.pubkey(),
+    };
+    let root_domain = ctx.registrar.root_domain();
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &root_domain.to_bytes())),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(32, &owner.to_bytes())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let name_accounts = ctx
+        .rpc()
+        .get_program_accounts_with_config(&spl_name_service::ID, config)?;
+
+    let reverse_keys = name_accounts
+        .iter()
+        .map(|(key, _)| ctx.registrar.reverse_key(key, None))
+        .collect::<Vec<_>>();
+    let mut reverses: Vec<Option<Account>> = Vec::with_capacity(reverse_keys.len());
+    for keys in reverse_keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        reverses.extend(ctx.rpc().get_multiple_accounts(keys)?);
+    }
+
+    let mut names = name_accounts
+        .iter()
+        .zip(reverses)
+        .map(|((key, _), reverse)| {
+            let name = reverse
+                .and_then(|account| decode_reverse_lookup(&account.data))
+                .map(|reverse_lookup| format!("{}.{}", reverse_lookup.name, ctx.config.tld));
+            (name, key)
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    for (name, key) in &names {
+        println!(
+            "{:<32} {}",
+            name.as_deref().unwrap_or("<no reverse lookup>"),
+            key
+        );
+    }
+    println!("{} domains owned by {}", names.len(), owner);
+    Ok(())
+}
+
+/// Registers the names of an import file, resuming from its journal
+#[allow(clippy::too_many_arguments)]
+pub fn import(
+    ctx: &Context,
+    file: &Path,
+    token: &str,
+    owner: Option<Pubkey>,
+    years: u8,
+    space: u32,
+    referrer: Option<Pubkey>,
+    journal: Option<PathBuf>,
+) -> Result<(), CliError> {
+    let signer = ctx.signer()?;
+    let (mint, _) = ctx.config.token(token)?;
+    let defaults = Defaults {
+        owner: owner.unwrap_or_else(|| signer.pubkey()),
+        years,
+        space,
+    };
+    let entries = import::validate(import::read_entries(file)?, &ctx.config, &defaults)?;
+    let usd_total = entries.iter().map(ImportEntry::usd_price).sum::<u64>();
+    println!(
+        "{} valid entries, ${:.2} before discounts and release premiums",
+        entries.len(),
+        usd_total as f64 / 1e6
+    );
+
+    let journal_path = journal.unwrap_or_else(|| {
+        let mut path = file.as_os_str().to_owned();
+        path.push(".journal");
+        PathBuf::from(path)
+    });
+    let mut journal = Journal::open(&journal_path)?;
+    if ctx.dry_run {
+        println!(
+            "{} transactions of the journal are pending",
+            journal.pending.len()
+        );
+    } else {
+        resolve_pending(ctx, &mut journal)?;
+    }
+
+    // Names registered by this import or otherwise are left out
+    let remaining = entries
+        .into_iter()
+        .filter(|entry| !journal.registered.contains(&entry.name))
+        .collect::<Vec<_>>();
+    let name_keys = remaining
+        .iter()
+        .map(|entry| ctx.registrar.name_key(&entry.name, None))
+        .collect::<Vec<_>>();
+    let mut name_accounts = Vec::with_capacity(name_keys.len());
+    for keys in name_keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        name_accounts.extend(ctx.rpc().get_multiple_accounts(keys)?);
+    }
+    let (registered, remaining): (Vec<_>, Vec<_>) = remaining
+        .into_iter()
+        .zip(name_accounts)
+        .partition(|(_, account)| account.is_some());
+    for (entry, _) in &registered {
+        println!("Skipping {}, already registered", entry.name);
+    }
+    let remaining = remaining
+        .into_iter()
+        .map(|(entry, _)| entry)
+        .collect::<Vec<_>>();
+
+    let batches = import::pack(
+        &ctx.registrar,
+        &remaining,
+        &signer.pubkey(),
+        &mint,
+        referrer,
+    )?;
+    println!(
+        "{} names to register in {} transactions, journal {}",
+        remaining.len(),
+        batches.len(),
+        journal_path.display()
+    );
+
+    for (idx, batch) in batches.iter().enumerate() {
+        println!("[{}/{}] {}", idx + 1, batches.len(), batch.names.join(", "));
+        if ctx.dry_run {
+            ctx.execute(&batch.instructions, &signer)?;
+            continue;
+        }
+        let (blockhash, last_valid_block_height) = ctx
+            .rpc()
+            .get_latest_blockhash_with_commitment(ctx.rpc().commitment())?;
+        let tx = Transaction::new_signed_with_payer(
+            &batch.instructions,
+            Some(&signer.pubkey()),
+            &[&signer],
+            blockhash,
+        );
+        let signature = tx.signatures[0].to_string();
+        journal.append(JournalRecord::Sent {
+            signature: signature.clone(),
+            last_valid_block_height,
+            names: batch.names.clone(),
+        })?;
+        match ctx.rpc().send_and_confirm_transaction_with_spinner(&tx) {
+            Ok(_) => {
+                journal.append(JournalRecord::Confirmed {
+                    signature: signature.clone(),
+                })?;
+                println!("Signature: {}", signature);
+            }
+            Err(e) => {
+                // Without a transaction error the outcome is unknown, the transaction stays
+                // pending in the journal and is resolved on the next run
+                if let Some(err) = e.get_transaction_error() {
+                    journal.append(JournalRecord::Failed {
+                        signature,
+                        error: err.to_string(),
+                    })?;
+                }
+                return Err(e.into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Waits for the outcome of the transactions of the journal sent without known outcome, they
+/// are expired once the block height exceeds their last valid block height
+fn resolve_pending(ctx: &Context, journal: &mut Journal) -> Result<(), CliError> {
+    while !journal.pending.is_empty() {
+        let signatures = journal.pending.keys().cloned().collect::<Vec<_>>();
+        let parsed = signatures
+            .iter()
+            .map(|s| s.parse::<Signature>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| CliError::Import(format!("Journal: {}", e)))?;
+        let statuses = ctx.rpc().get_signature_statuses(&parsed)?.value;
+        let block_height = ctx.rpc().get_block_height()?;
+        for (signature, status) in signatures.into_iter().zip(statuses) {
+            let record = match status {
+                Some(status) if status.satisfies_commitment(ctx.rpc().commitment()) => {
+                    match status.err {
+                        None => JournalRecord::Confirmed { signature },
+                        Some(err) => JournalRecord::Failed {
+                            signature,
+                            error: err.to_string(),
+                        },
+                    }
+                }
+                None if block_height > journal.pending[&signature].0 => JournalRecord::Failed {
+                    signature,
+                    error: "Expired".to_owned(),
+                },
+                _ => continue,
+            };
+            println!("Journal: {:?}", record);
+            journal.append(record)?;
+        }
+        if !journal.pending.is_empty() {
+            sleep(Duration::from_secs(2));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//! Configuration file of the CLI, in the format of `gorbchain-config.json`
+
+use std::{collections::HashMap, fs, path::Path, path::PathBuf, str::FromStr};
+
+use serde::{Deserialize, Deserializer};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::error::CliError;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    pub rpc_endpoint: String,
+    #[serde(default = "default_commitment")]
+    pub commitment: String,
+    #[serde(deserialize_with = "pubkey")]
+    pub program_id: Pubkey,
+    pub deployment: Deployment,
+    pub constants: Constants,
+    pub tokens: HashMap<String, Token>,
+    /// TLD of the root domain account, the names given without TLD are registered under it
+    #[serde(default = "default_tld")]
+    pub tld: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deployment {
+    /// Default keypair file, `~` is expanded to the home directory
+    pub keypair_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Constants {
+    #[serde(deserialize_with = "pubkey")]
+    pub vault_owner: Pubkey,
+    #[serde(deserialize_with = "pubkey")]
+    pub root_domain_account: Pubkey,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Token {
+    #[serde(deserialize_with = "pubkey")]
+    pub mint: Pubkey,
+    pub decimals: u8,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self, CliError> {
+        let file = fs::read_to_string(path)
+            .map_err(|e| CliError::Config(format!("{}: {}", path.display(), e)))?;
+        let config: Config = serde_json::from_str(&file)
+            .map_err(|e| CliError::Config(format!("{}: {}", path.display(), e)))?;
+        Ok(config)
+    }
+
+    /// Keys of the registrar deployment targeted by the configuration, the central state being
+    /// derived from `programId`
+    pub fn registrar(&self) -> sns_registrar_client::Deployment {
+        sns_registrar_client::Deployment::new(
+            self.program_id,
+            self.constants.root_domain_account,
+            self.constants.vault_owner,
+        )
+    }
+
+    /// Keypair file given on the command line, `deployment.keypairPath` otherwise
+    pub fn keypair_path(&self, keypair: Option<&Path>) -> PathBuf {
+        if let Some(keypair) = keypair {
+            return keypair.to_owned();
+        }
+        match (
+            self.deployment.keypair_path.strip_prefix("~/"),
+            std::env::var_os("HOME"),
+        ) {
+            (Some(path), Some(home)) => Path::new(&home).join(path),
+            _ => PathBuf::from(&self.deployment.keypair_path),
+        }
+    }
+
+    /// Mint and decimals of a token given by symbol (e.g. `USDC`) or by mint
+    pub fn token(&self, token: &str) -> Result<(Pubkey, u8), CliError> {
+        self.tokens
+            .iter()
+            .find(|(symbol, t)| symbol.eq_ignore_ascii_case(token) || t.mint.to_string() == token)
+            .map(|(_, t)| (t.mint, t.decimals))
+            .ok_or_else(|| CliError::UnknownToken(token.to_owned()))
+    }
+
+    /// Strips the TLD of the root domain, `alice.sol` and `alice` are both `alice`
+    pub fn strip_tld<'a>(&self, name: &'a str) -> &'a str {
+        name.strip_suffix(&self.tld)
+            .and_then(|name| name.strip_suffix('.'))
+            .unwrap_or(name)
+    }
+}
+
+fn default_commitment() -> String {
+    "confirmed".to_owned()
+}
+
+fn default_tld() -> String {
+    "sol".to_owned()
+}
+
+fn pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let key = String::deserialize(deserializer)?;
+    Pubkey::from_str(&key).map_err(|e| serde::de::Error::custom(format!("{}: {}", key, e)))
+}
+
+#[test]
+fn test_config() {
+    use sns_registrar::constants::{ROOT_DOMAIN_ACCOUNT, VAULT_OWNER};
+
+    let config = format!(
+        r#"{{
+            "rpcEndpoint": "http://localhost:8899",
+            "programId": "{}",
+            "deployment": {{ "keypairPath": "~/.config/solana/id.json" }},
+            "constants": {{ "vaultOwner": "{}", "rootDomainAccount": "{}" }},
+            "tokens": {{ "USDC": {{ "mint": "{}", "decimals": 6 }} }}
+        }}"#,
+        sns_registrar::ID,
+        VAULT_OWNER,
+        ROOT_DOMAIN_ACCOUNT,
+        Pubkey::default()
+    );
+    let config: Config = serde_json::from_str(&config).unwrap();
+    assert_eq!(
+        config.registrar(),
+        sns_registrar_client::Deployment::default()
+    );
+    assert_eq!(config.commitment, "confirmed");
+    assert_eq!(config.token("usdc").unwrap(), (Pubkey::default(), 6));
+    assert_eq!(config.token(&Pubkey::default().to_string()).unwrap().1, 6);
+    assert!(config.token("FIDA").is_err());
+    assert_eq!(config.strip_tld("alice.sol"), "alice");
+    assert_eq!(config.strip_tld("alice"), "alice");
+    assert_eq!(config.strip_tld("sub.alice.sol"), "sub.alice");
+    assert_eq!(config.strip_tld("alicesol"), "alicesol");
+
+    // Another deployment than the one the program crate was built for
+    let (program_id, root_domain) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let config: Config = serde_json::from_value(serde_json::json!({
+        "rpcEndpoint": "http://localhost:8899",
+        "programId": program_id.to_string(),
+        "deployment": { "keypairPath": "id.json" },
+        "constants": {
+            "vaultOwner": VAULT_OWNER.to_string(),
+            "rootDomainAccount": root_domain.to_string()
+        },
+        "tokens": {}
+    }))
+    .unwrap();
+    let registrar = config.registrar();
+    assert_eq!(registrar.program_id(), program_id);
+    assert_eq!(registrar.root_domain(), root_domain);
+    assert_ne!(registrar.central_state(), sns_registrar::central_state::KEY);
+}
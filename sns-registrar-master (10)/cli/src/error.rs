@@ -0,0 +1,28 @@
+use sns_registrar_client::{ClientError, ResolverError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+    #[error("Cannot read the keypair {0}: {1}")]
+    Keypair(String, String),
+    #[error("Unknown token {0}, expected a symbol or a mint of the `tokens` of the configuration")]
+    UnknownToken(String),
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error(transparent)]
+    Resolver(#[from] ResolverError),
+    #[error("RPC error: {0}")]
+    Rpc(String),
+    #[error("Import failed: {0}")]
+    Import(String),
+    #[error("Transaction simulation failed: {0}")]
+    Simulation(String),
+}
+
+impl From<solana_client::client_error::ClientError> for CliError {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(e.to_string())
+    }
+}
@@ -0,0 +1,454 @@
+//! Bulk registration of the names of a JSON or CSV file.
+//!
+//! Entries are validated against the rules of the program, grouped by owner and registration
+//! term, and packed into `CreateBatch` transactions that fit in a packet and in the compute budget.
+//! Each transaction is appended to a journal before being sent, so that an interrupted import can
+//! be resumed without registering a name twice.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sns_registrar::{
+    constants::MAX_REGISTRATION_YEARS,
+    processor::create_batch::BatchEntry,
+    utils::{get_grapheme_len, get_multi_year_usd_price, get_usd_price},
+};
+use sns_registrar_client::{instructions::check_name, Deployment, RegisterOptions};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey, transaction::Transaction,
+};
+
+use crate::{config::Config, error::CliError};
+
+/// Compute units budgeted for the fixed part of a `CreateBatch` instruction and for each name
+pub const COMPUTE_UNITS_BASE: u32 = 60_000;
+pub const COMPUTE_UNITS_PER_NAME: u32 = 90_000;
+pub const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// An entry of an import file, as written in the file
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawEntry {
+    #[serde(alias = "domain", alias = "domainName", alias = "domain_name")]
+    pub name: Option<String>,
+    #[serde(alias = "address")]
+    pub owner: Option<String>,
+    #[serde(alias = "metadata_url")]
+    pub metadata_url: Option<String>,
+    pub years: Option<u8>,
+    pub space: Option<u32>,
+}
+
+/// A validated entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportEntry {
+    /// The name without TLD
+    pub name: String,
+    pub owner: Pubkey,
+    pub metadata_url: Option<String>,
+    pub years: u8,
+    pub space: u32,
+}
+
+impl ImportEntry {
+    /// USD price of the entry with 6 decimals, before the release premium of expired names
+    pub fn usd_price(&self) -> u64 {
+        get_multi_year_usd_price(get_usd_price(get_grapheme_len(&self.name)), self.years)
+            .unwrap_or_default()
+    }
+}
+
+/// Values of the entries that leave a field out
+pub struct Defaults {
+    pub owner: Pubkey,
+    pub years: u8,
+    pub space: u32,
+}
+
+/// The names of a transaction and its instructions
+pub struct Batch {
+    pub names: Vec<String>,
+    pub instructions: Vec<Instruction>,
+}
+
+/// Reads a CSV file (`.csv`) or a JSON file holding an entry, an array of entries or an object of
+/// entries keyed by name
+pub fn read_entries(path: &Path) -> Result<Vec<RawEntry>, CliError> {
+    let import_error = |e: String| CliError::Import(format!("{}: {}", path.display(), e));
+    if path.extension().map_or(false, |ext| ext == "csv") {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_path(path)
+            .map_err(|e| import_error(e.to_string()))?;
+        return reader
+            .deserialize()
+            .collect::<Result<Vec<RawEntry>, _>>()
+            .map_err(|e| import_error(e.to_string()));
+    }
+    let file = fs::read_to_string(path).map_err(|e| import_error(e.to_string()))?;
+    let value: Value = serde_json::from_str(&file).map_err(|e| import_error(e.to_string()))?;
+    parse_json(value).map_err(|e| import_error(e.to_string()))
+}
+
+fn parse_json(value: Value) -> Result<Vec<RawEntry>, serde_json::Error> {
+    match value {
+        Value::Array(entries) => entries.into_iter().map(serde_json::from_value).collect(),
+        Value::Object(object)
+            if ["name", "domain", "domainName", "domain_name"]
+                .iter()
+                .any(|key| object.contains_key(*key)) =>
+        {
+            Ok(vec![serde_json::from_value(Value::Object(object))?])
+        }
+        Value::Object(object) => object
+            .into_iter()
+            .map(|(name, entry)| {
+                let mut entry: RawEntry = serde_json::from_value(entry)?;
+                entry.name.get_or_insert(name);
+                Ok(entry)
+            })
+            .collect(),
+        _ => Ok(vec![serde_json::from_value(value)?]),
+    }
+}
+
+/// Validates every entry and reports all the invalid ones at once
+pub fn validate(
+    raw_entries: Vec<RawEntry>,
+    config: &Config,
+    defaults: &Defaults,
+) -> Result<Vec<ImportEntry>, CliError> {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    let mut entries = Vec::with_capacity(raw_entries.len());
+    for (idx, raw) in raw_entries.into_iter().enumerate() {
+        let raw_name = raw.name.unwrap_or_default();
+        let mut error = |e: String| errors.push(format!("entry {} ({}): {}", idx + 1, raw_name, e));
+
+        let name = config.strip_tld(&raw_name).to_owned();
+        if check_name(&name).is_err() {
+            error(format!(
+                "names are lower case and have no dot or space, and only .{} names are imported",
+                config.tld
+            ));
+            continue;
+        }
+        if !seen.insert(name.clone()) {
+            error("duplicate name".to_owned());
+            continue;
+        }
+        let owner = match raw.owner.as_deref().map(Pubkey::from_str).transpose() {
+            Ok(owner) => owner.unwrap_or(defaults.owner),
+            Err(_) => {
+                error(format!(
+                    "invalid owner {}",
+                    raw.owner.as_deref().unwrap_or_default()
+                ));
+                continue;
+            }
+        };
+        let years = raw.years.unwrap_or(defaults.years);
+        if years == 0 || years > MAX_REGISTRATION_YEARS {
+            error(format!(
+                "registrations last from 1 to {} years",
+                MAX_REGISTRATION_YEARS
+            ));
+            continue;
+        }
+        entries.push(ImportEntry {
+            name,
+            owner,
+            metadata_url: raw.metadata_url.filter(|url| !url.is_empty()),
+            years,
+            space: raw.space.unwrap_or(defaults.space),
+        });
+    }
+    if !errors.is_empty() {
+        return Err(CliError::Import(format!(
+            "{} invalid entries\n{}",
+            errors.len(),
+            errors.join("\n")
+        )));
+    }
+    Ok(entries)
+}
+
+/// Packs the entries into transactions, one `CreateBatch` instruction of entries of the same owner
+/// and term each
+pub fn pack(
+    registrar: &Deployment,
+    entries: &[ImportEntry],
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    referrer: Option<Pubkey>,
+) -> Result<Vec<Batch>, CliError> {
+    let mut groups: Vec<((Pubkey, u8), Vec<&ImportEntry>)> = Vec::new();
+    for entry in entries {
+        let key = (entry.owner, entry.years);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(entry),
+            None => groups.push((key, vec![entry])),
+        }
+    }
+
+    let mut batches = Vec::new();
+    for ((owner, years), group) in groups {
+        let build = |entries: &[&ImportEntry]| {
+            let compute_units = COMPUTE_UNITS_BASE + COMPUTE_UNITS_PER_NAME * entries.len() as u32;
+            let ix = registrar.register_batch(
+                entries
+                    .iter()
+                    .map(|entry| BatchEntry {
+                        name: entry.name.clone(),
+                        space: entry.space,
+                        metadata_url: entry.metadata_url.clone(),
+                    })
+                    .collect(),
+                buyer,
+                mint,
+                RegisterOptions {
+                    years,
+                    referrer,
+                    domain_owner: Some(owner),
+                    ..Default::default()
+                },
+            )?;
+            let instructions = vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(compute_units),
+                ix,
+            ];
+            let fits = compute_units <= MAX_COMPUTE_UNITS
+                && transaction_size(&instructions, buyer) <= PACKET_DATA_SIZE;
+            Ok::<_, CliError>((instructions, fits))
+        };
+
+        let mut start = 0;
+        while start < group.len() {
+            let (mut instructions, fits) = build(&group[start..start + 1])?;
+            if !fits {
+                return Err(CliError::Import(format!(
+                    "{} does not fit in a transaction",
+                    group[start].name
+                )));
+            }
+            let mut end = start + 1;
+            while end < group.len() {
+                let (next, fits) = build(&group[start..end + 1])?;
+                if !fits {
+                    break;
+                }
+                instructions = next;
+                end += 1;
+            }
+            batches.push(Batch {
+                names: group[start..end].iter().map(|e| e.name.clone()).collect(),
+                instructions,
+            });
+            start = end;
+        }
+    }
+    Ok(batches)
+}
+
+/// Size of the signed transaction
+fn transaction_size(instructions: &[Instruction], payer: &Pubkey) -> usize {
+    let tx = Transaction::new_with_payer(instructions, Some(payer));
+    let signatures = tx.message.header.num_required_signatures as usize;
+    1 + 64 * signatures + tx.message.serialize().len()
+}
+
+/// A line of the journal
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum JournalRecord {
+    /// Written before the transaction is sent
+    #[serde(rename_all = "camelCase")]
+    Sent {
+        signature: String,
+        last_valid_block_height: u64,
+        names: Vec<String>,
+    },
+    Confirmed {
+        signature: String,
+    },
+    Failed {
+        signature: String,
+        error: String,
+    },
+}
+
+/// Append-only journal of the transactions of an import, one JSON record per line
+pub struct Journal {
+    file: File,
+    /// Names registered by confirmed transactions
+    pub registered: HashSet<String>,
+    /// Transactions sent without known outcome, by signature
+    pub pending: HashMap<String, (u64, Vec<String>)>,
+}
+
+impl Journal {
+    pub fn open(path: &Path) -> Result<Self, CliError> {
+        let journal_error =
+            |e: std::io::Error| CliError::Import(format!("Journal {}: {}", path.display(), e));
+        let mut sent = HashMap::new();
+        let mut registered = HashSet::new();
+        if path.exists() {
+            let reader = BufReader::new(File::open(path).map_err(journal_error)?);
+            for line in reader.lines() {
+                let line = line.map_err(journal_error)?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record = serde_json::from_str(&line)
+                    .map_err(|e| CliError::Import(format!("Journal {}: {}", path.display(), e)))?;
+                match record {
+                    JournalRecord::Sent {
+                        signature,
+                        last_valid_block_height,
+                        names,
+                    } => {
+                        sent.insert(signature, (last_valid_block_height, names));
+                    }
+                    JournalRecord::Confirmed { signature } => {
+                        if let Some((_, names)) = sent.remove(&signature) {
+                            registered.extend(names);
+                        }
+                    }
+                    JournalRecord::Failed { signature, .. } => {
+                        sent.remove(&signature);
+                    }
+                }
+            }
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(journal_error)?;
+        Ok(Self {
+            file,
+            registered,
+            pending: sent,
+        })
+    }
+
+    /// Appends a record and flushes it to disk
+    pub fn append(&mut self, record: JournalRecord) -> Result<(), CliError> {
+        let journal_error = |e: std::io::Error| CliError::Import(format!("Journal: {}", e));
+        match &record {
+            JournalRecord::Sent {
+                signature,
+                last_valid_block_height,
+                names,
+            } => {
+                self.pending
+                    .insert(signature.clone(), (*last_valid_block_height, names.clone()));
+            }
+            JournalRecord::Confirmed { signature } => {
+                if let Some((_, names)) = self.pending.remove(signature) {
+                    self.registered.extend(names);
+                }
+            }
+            JournalRecord::Failed { signature, .. } => {
+                self.pending.remove(signature);
+            }
+        }
+        let line = serde_json::to_string(&record).unwrap();
+        writeln!(self.file, "{}", line).map_err(journal_error)?;
+        self.file.sync_data().map_err(journal_error)
+    }
+}
+
+#[test]
+fn test_import() {
+    use sns_registrar::constants::{ROOT_DOMAIN_ACCOUNT, VAULT_OWNER};
+
+    let config: Config = serde_json::from_value(serde_json::json!({
+        "rpcEndpoint": "http://localhost:8899",
+        "programId": sns_registrar::ID.to_string(),
+        "deployment": { "keypairPath": "id.json" },
+        "constants": {
+            "vaultOwner": VAULT_OWNER.to_string(),
+            "rootDomainAccount": ROOT_DOMAIN_ACCOUNT.to_string()
+        },
+        "tokens": {}
+    }))
+    .unwrap();
+    let owner = Pubkey::new_unique();
+    let defaults = Defaults {
+        owner: Pubkey::new_unique(),
+        years: 1,
+        space: 1_000,
+    };
+
+    // `domain-registration-data.json` and `gorb-domain-registry.json` layouts
+    let single = parse_json(serde_json::json!({
+        "domainName": "employee.sol",
+        "owner": owner.to_string(),
+        "metadataUrl": "https://arweave.net/employee.json",
+    }))
+    .unwrap();
+    let keyed = parse_json(serde_json::json!({
+        "alice.sol": { "address": owner.to_string(), "years": 2 },
+        "Bob.sol": { "metadataUrl": "https://arweave.net/bob.json" },
+    }))
+    .unwrap();
+    let entries = validate(single, &config, &defaults).unwrap();
+    assert_eq!(entries[0].name, "employee");
+    assert_eq!(entries[0].owner, owner);
+    let err = validate(keyed, &config, &defaults).unwrap_err().to_string();
+    assert!(err.contains("1 invalid entries") && err.contains("Bob.sol"));
+
+    let raw = (0..20)
+        .map(|i| RawEntry {
+            name: Some(format!("name{}", i)),
+            years: Some(1 + i % 2),
+            ..Default::default()
+        })
+        .chain(std::iter::once(RawEntry {
+            name: Some("name0.sol".to_owned()),
+            ..Default::default()
+        }))
+        .collect();
+    let err = validate(raw, &config, &defaults).unwrap_err().to_string();
+    assert!(err.contains("entry 21 (name0.sol): duplicate name"));
+
+    let entries = (0..20)
+        .map(|i| ImportEntry {
+            name: format!("name{:02}", i),
+            owner: defaults.owner,
+            metadata_url: None,
+            years: 1 + (i % 2) as u8,
+            space: 1_000,
+        })
+        .collect::<Vec<_>>();
+    let total = entries.iter().map(ImportEntry::usd_price).sum::<u64>();
+    assert_eq!(
+        total,
+        10 * get_usd_price(6) + 10 * get_multi_year_usd_price(get_usd_price(6), 2).unwrap()
+    );
+
+    let buyer = Pubkey::new_unique();
+    let (usdc, _) = sns_registrar::constants::TOKENS_SYM_MINT_DECIMALS["USDC"];
+    let batches = pack(&Deployment::default(), &entries, &buyer, &usdc, None).unwrap();
+    let mut names = batches
+        .iter()
+        .flat_map(|b| b.names.iter().cloned())
+        .collect::<Vec<_>>();
+    assert_eq!(names.len(), 20);
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), 20);
+    assert!(batches.len() > 2);
+    for batch in &batches {
+        assert!(transaction_size(&batch.instructions, &buyer) <= PACKET_DATA_SIZE);
+    }
+}
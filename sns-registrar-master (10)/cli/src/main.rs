@@ -0,0 +1,177 @@
+//! `sns`, a command-line client of the SNS registrar.
+//!
+//! The transactions are signed with a keypair file and sent to the cluster of a configuration file
+//! in the format of `gorbchain-config.json`. With `--dry-run` they are simulated and the program
+//! logs are printed instead.
+
+use std::{path::PathBuf, process::exit};
+
+use clap::{Parser, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+
+mod commands;
+mod config;
+mod error;
+mod import;
+
+use crate::{commands::Context, config::Config, error::CliError};
+
+#[derive(Parser)]
+#[command(
+    name = "sns",
+    version,
+    about = "Command-line client of the SNS registrar"
+)]
+struct Cli {
+    /// Configuration file, in the format of `gorbchain-config.json`
+    #[arg(short, long, global = true, default_value = "gorbchain-config.json")]
+    config: PathBuf,
+    /// Keypair file signing the transactions, `deployment.keypairPath` of the configuration by default
+    #[arg(short, long, global = true)]
+    keypair: Option<PathBuf>,
+    /// Simulate the transactions and print the program logs instead of sending them
+    #[arg(long, global = true)]
+    dry_run: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Register a domain name, paid with a token of the configuration
+    Register {
+        /// Domain name, e.g. `alice` or `alice.sol`
+        name: String,
+        /// Symbol or mint of the payment token
+        #[arg(long, default_value = "USDC")]
+        token: String,
+        #[arg(long, default_value_t = 1)]
+        years: u8,
+        /// Space allocated to the name account
+        #[arg(long, default_value_t = sns_registrar_client::instructions::DEFAULT_SPACE)]
+        space: u32,
+        #[arg(long)]
+        metadata_url: Option<String>,
+        /// Whitelisted referrer wallet
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+        /// Owner of the name, the signer by default
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Create the reverse lookup of a domain or of a subdomain such as `sub.alice.sol`
+    CreateReverse {
+        name: String,
+        #[arg(long)]
+        metadata_url: Option<String>,
+    },
+    /// Set or clear the metadata URL of a domain
+    UpdateMetadata {
+        name: String,
+        /// New metadata URL, cleared if omitted
+        metadata_url: Option<String>,
+    },
+    /// Delete a domain and its reverse lookup
+    Delete {
+        name: String,
+        /// Account receiving the lamports of the deleted accounts, the signer by default
+        #[arg(long)]
+        target: Option<Pubkey>,
+    },
+    /// Resolve a domain name or a subdomain
+    Resolve { name: String },
+    /// Find the name of a name account
+    Reverse { name_account: Pubkey },
+    /// Quote the price of a registration
+    Price {
+        name: String,
+        #[arg(long, default_value = "USDC")]
+        token: String,
+        #[arg(long, default_value_t = 1)]
+        years: u8,
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+    },
+    /// List the domains owned by a wallet, the signer by default
+    ListOwned { owner: Option<Pubkey> },
+    /// Register the names of a JSON or CSV file, resuming an interrupted import from its journal
+    Import {
+        file: PathBuf,
+        /// Symbol or mint of the payment token
+        #[arg(long, default_value = "USDC")]
+        token: String,
+        /// Registration term of the entries without `years`
+        #[arg(long, default_value_t = 1)]
+        years: u8,
+        /// Space of the entries without `space`
+        #[arg(long, default_value_t = sns_registrar_client::instructions::DEFAULT_SPACE)]
+        space: u32,
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+        /// Owner of the entries without owner, the signer by default
+        #[arg(long)]
+        owner: Option<Pubkey>,
+        /// Progress journal, `<file>.journal` by default
+        #[arg(long)]
+        journal: Option<PathBuf>,
+    },
+}
+
+fn run(cli: Cli) -> Result<(), CliError> {
+    let config = Config::load(&cli.config)?;
+    let keypair = config.keypair_path(cli.keypair.as_deref());
+    let ctx = Context::new(config, keypair, cli.dry_run)?;
+
+    match cli.command {
+        Command::Register {
+            name,
+            token,
+            years,
+            space,
+            metadata_url,
+            referrer,
+            owner,
+        } => commands::register_name(
+            &ctx,
+            &name,
+            &token,
+            years,
+            space,
+            metadata_url,
+            referrer,
+            owner,
+        ),
+        Command::CreateReverse { name, metadata_url } => {
+            commands::create_reverse_lookup(&ctx, &name, metadata_url)
+        }
+        Command::UpdateMetadata { name, metadata_url } => {
+            commands::set_metadata(&ctx, &name, metadata_url)
+        }
+        Command::Delete { name, target } => commands::delete_name(&ctx, &name, target),
+        Command::Resolve { name } => commands::resolve(&ctx, &name),
+        Command::Reverse { name_account } => commands::reverse(&ctx, &name_account),
+        Command::Price {
+            name,
+            token,
+            years,
+            referrer,
+        } => commands::price(&ctx, &name, &token, years, referrer),
+        Command::ListOwned { owner } => commands::list_owned(&ctx, owner),
+        Command::Import {
+            file,
+            token,
+            years,
+            space,
+            referrer,
+            owner,
+            journal,
+        } => commands::import(&ctx, &file, &token, owner, years, space, referrer, journal),
+    }
+}
+
+fn main() {
+    if let Err(e) = run(Cli::parse()) {
+        eprintln!("Error: {}", e);
+        exit(1);
+    }
+}
@@ -0,0 +1,229 @@
+//! Keys of the accounts read and written by the registrar instructions
+
+use bonfida_utils::tokens::SupportedToken;
+use sns_registrar::{
+    central_state,
+    constants::{REFERRER_WHITELIST, ROOT_DOMAIN_ACCOUNT, VAULT_OWNER},
+    state::{
+        nft_record::NftRecord, primary_domain::PrimaryDomain, record::Record,
+        registration::RegistrationRecord, tld::Tld,
+    },
+    utils::get_hashed_name,
+};
+use solana_program::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address;
+use spl_name_service::state::get_seeds_and_key;
+
+use crate::ClientError;
+
+/// Keys of a deployment of the registrar: the program, its central state, the default root
+/// domain and the owner of its vaults. The default is the deployment the program crate was built
+/// for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deployment {
+    program_id: Pubkey,
+    central_state: Pubkey,
+    root_domain: Pubkey,
+    vault_owner: Pubkey,
+}
+
+impl Default for Deployment {
+    fn default() -> Self {
+        Self {
+            program_id: sns_registrar::ID,
+            central_state: central_state::KEY,
+            root_domain: ROOT_DOMAIN_ACCOUNT,
+            vault_owner: VAULT_OWNER,
+        }
+    }
+}
+
+impl Deployment {
+    /// The central state is derived from the program ID, as the program does
+    pub fn new(program_id: Pubkey, root_domain: Pubkey, vault_owner: Pubkey) -> Self {
+        let (central_state, _) =
+            Pubkey::find_program_address(&[&program_id.to_bytes()], &program_id);
+        Self {
+            program_id,
+            central_state,
+            root_domain,
+            vault_owner,
+        }
+    }
+
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    pub fn central_state(&self) -> Pubkey {
+        self.central_state
+    }
+
+    /// The default root domain, e.g. the root of `.sol`
+    pub fn root_domain(&self) -> Pubkey {
+        self.root_domain
+    }
+
+    /// Owner of the token accounts receiving the payments of the default root domain
+    pub fn vault_owner(&self) -> Pubkey {
+        self.vault_owner
+    }
+
+    /// Keys of a name registered directly under `root_domain`
+    pub fn registration_keys(&self, name: &str, root_domain: &Pubkey) -> RegistrationKeys {
+        let name = self.name_key(name, Some(root_domain));
+        RegistrationKeys {
+            name,
+            reverse_lookup: self.reverse_key(&name, None),
+            state: self.state_key(&name),
+            registration_record: self.registration_record_key(&name),
+        }
+    }
+
+    /// Key of the name account of `name` under `parent`, the default root domain if `None`
+    pub fn name_key(&self, name: &str, parent: Option<&Pubkey>) -> Pubkey {
+        let parent = parent.unwrap_or(&self.root_domain);
+        get_seeds_and_key(
+            &spl_name_service::ID,
+            get_hashed_name(name),
+            None,
+            Some(parent),
+        )
+        .0
+    }
+
+    /// Key of the reverse lookup account of a name account, `parent` is the parent of subdomains
+    pub fn reverse_key(&self, name_key: &Pubkey, parent: Option<&Pubkey>) -> Pubkey {
+        get_seeds_and_key(
+            &spl_name_service::ID,
+            get_hashed_name(&name_key.to_string()),
+            Some(&self.central_state),
+            parent,
+        )
+        .0
+    }
+
+    /// Key of the `record` account of a domain
+    pub fn record_key(&self, domain_key: &Pubkey, record: Record) -> Pubkey {
+        get_seeds_and_key(
+            &spl_name_service::ID,
+            get_hashed_name(&record.name()),
+            Some(&self.central_state),
+            Some(domain_key),
+        )
+        .0
+    }
+
+    /// Key of the state auction account of a name account
+    pub fn state_key(&self, name_key: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[&name_key.to_bytes()], &self.program_id).0
+    }
+
+    pub fn registration_record_key(&self, name_key: &Pubkey) -> Pubkey {
+        RegistrationRecord::find_key(name_key, &self.program_id).0
+    }
+
+    pub fn nft_record_key(&self, name_key: &Pubkey) -> Pubkey {
+        NftRecord::find_key(name_key, &self.program_id).0
+    }
+
+    /// Key of the TLD account of a root domain
+    pub fn tld_key(&self, root_domain: &Pubkey) -> Pubkey {
+        Tld::find_key(root_domain, &self.program_id).0
+    }
+
+    /// Key of the primary domain account of a wallet
+    pub fn primary_domain_key(&self, wallet: &Pubkey) -> Pubkey {
+        PrimaryDomain::find_key(wallet, &self.program_id).0
+    }
+}
+
+/// Accounts created by the registration of a name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistrationKeys {
+    pub name: Pubkey,
+    pub reverse_lookup: Pubkey,
+    /// The state auction account, holding the release premium of recently expired names
+    pub state: Pubkey,
+    pub registration_record: Pubkey,
+}
+
+impl RegistrationKeys {
+    /// Keys of a name registered directly under `root_domain`
+    pub fn new(name: &str, root_domain: &Pubkey) -> Self {
+        Deployment::default().registration_keys(name, root_domain)
+    }
+}
+
+/// Key of the name account of `name` under `parent`, the default root domain if `None`
+pub fn name_key(name: &str, parent: Option<&Pubkey>) -> Pubkey {
+    Deployment::default().name_key(name, parent)
+}
+
+/// Key of the reverse lookup account of a name account, `parent` is the parent of subdomains
+pub fn reverse_key(name_key: &Pubkey, parent: Option<&Pubkey>) -> Pubkey {
+    Deployment::default().reverse_key(name_key, parent)
+}
+
+/// Key of the state auction account of a name account
+pub fn state_key(name_key: &Pubkey) -> Pubkey {
+    Deployment::default().state_key(name_key)
+}
+
+/// Key of the TLD account of a root domain
+pub fn tld_key(root_domain: &Pubkey) -> Pubkey {
+    Deployment::default().tld_key(root_domain)
+}
+
+/// Key of the primary domain account of a wallet
+pub fn primary_domain_key(wallet: &Pubkey) -> Pubkey {
+    Deployment::default().primary_domain_key(wallet)
+}
+
+/// Token account receiving the registration payments in `mint`
+pub fn vault_key(vault_owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(vault_owner, mint)
+}
+
+/// Pyth feed of the USD price of `mint`
+pub fn pyth_feed_key(mint: &Pubkey) -> Result<Pubkey, ClientError> {
+    SupportedToken::from_mint(mint)
+        .map(|token| token.price_feed_account_key())
+        .map_err(|_| ClientError::UnsupportedMint(*mint))
+}
+
+/// Index of a referrer in the whitelist of the program
+pub fn referrer_index(referrer: &Pubkey) -> Result<u16, ClientError> {
+    REFERRER_WHITELIST
+        .iter()
+        .position(|k| k == referrer)
+        .map(|i| i as u16)
+        .ok_or(ClientError::UnknownReferrer(*referrer))
+}
+
+#[test]
+fn test_registration_keys() {
+    let root_domain = sns_registrar::constants::ROOT_DOMAIN_ACCOUNT;
+    let keys = RegistrationKeys::new("alice", &root_domain);
+    assert_eq!(
+        keys.name,
+        sns_registrar::utils::get_name_key("alice", None).unwrap()
+    );
+    assert_eq!(keys.reverse_lookup, reverse_key(&keys.name, None));
+    assert_eq!(
+        keys.reverse_lookup,
+        sns_registrar::utils::get_reverse_key(&keys.name, None).unwrap()
+    );
+    assert_eq!(
+        Deployment::new(sns_registrar::ID, root_domain, VAULT_OWNER),
+        Deployment::default()
+    );
+    let deployment = Deployment::new(Pubkey::new_unique(), root_domain, VAULT_OWNER);
+    let other_keys = deployment.registration_keys("alice", &root_domain);
+    assert_eq!(other_keys.name, keys.name);
+    assert_ne!(other_keys.reverse_lookup, keys.reverse_lookup);
+    assert_ne!(other_keys.registration_record, keys.registration_record);
+    assert_ne!(RegistrationKeys::new("alice", &Pubkey::new_unique()), keys);
+    assert_eq!(referrer_index(&REFERRER_WHITELIST[2]), Ok(2));
+    assert!(referrer_index(&Pubkey::new_unique()).is_err());
+}
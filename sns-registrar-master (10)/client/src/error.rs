@@ -0,0 +1,24 @@
+use solana_program::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ClientError {
+    #[error("Invalid name {0}, names must be lower case and have no dot or space")]
+    InvalidName(String),
+    #[error("The mint {0} is not supported")]
+    UnsupportedMint(Pubkey),
+    #[error("The referrer {0} is not whitelisted")]
+    UnknownReferrer(Pubkey),
+}
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ResolverError {
+    #[error("Invalid name {0}, expected a name such as alice.sol or sub.alice.sol")]
+    InvalidName(String),
+    #[error("The name {0} does not exist")]
+    NotFound(String),
+    #[error("The account {0} is not a valid name account")]
+    InvalidAccount(Pubkey),
+    #[error("RPC error: {0}")]
+    Rpc(String),
+}
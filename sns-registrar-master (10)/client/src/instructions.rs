@@ -0,0 +1,490 @@
+//! Builders of the registrar instructions
+
+use sns_registrar::{
+    instruction_auto,
+    processor::{
+        create_batch::{self, BatchEntry},
+        create_reverse as create_reverse_ix, create_split_v3, renew as renew_ix,
+        update_metadata as update_metadata_ix,
+    },
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey, system_program, sysvar};
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::{
+    derivation::{pyth_feed_key, referrer_index, vault_key, Deployment},
+    ClientError,
+};
+
+/// Space allocated to name accounts by default
+pub const DEFAULT_SPACE: u32 = 1_000;
+
+/// A TLD created with `CreateTld`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TldOptions {
+    /// The root name account of the TLD
+    pub root_domain: Pubkey,
+    /// Owner of the token accounts receiving the registration payments
+    pub vault_owner: Pubkey,
+}
+
+/// Options of `register`, the default registers a name of the default root domain for one year
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterOptions {
+    pub space: u32,
+    pub years: u8,
+    pub metadata_url: Option<String>,
+    /// Whitelisted referrer, its associated token account receives the referrer fee
+    pub referrer: Option<Pubkey>,
+    /// Owner of the name, the buyer by default
+    pub domain_owner: Option<Pubkey>,
+    /// Fee payer of the new accounts, the buyer by default
+    pub fee_payer: Option<Pubkey>,
+    /// Token account paying for the name, the associated token account of the buyer by default
+    pub buyer_token_source: Option<Pubkey>,
+    /// TLD to register the name under instead of the default root domain
+    pub tld: Option<TldOptions>,
+}
+
+impl Default for RegisterOptions {
+    fn default() -> Self {
+        Self {
+            space: DEFAULT_SPACE,
+            years: 1,
+            metadata_url: None,
+            referrer: None,
+            domain_owner: None,
+            fee_payer: None,
+            buyer_token_source: None,
+            tld: None,
+        }
+    }
+}
+
+/// Parent of a subdomain, whose owner signs the creation of reverse lookups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParentName {
+    pub name_account: Pubkey,
+    pub owner: Pubkey,
+}
+
+/// Options of `create_reverse`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReverseOptions {
+    pub metadata_url: Option<String>,
+    /// Root domain of a TLD, the default root domain if `None`
+    pub root_domain: Option<Pubkey>,
+    /// Parent domain, for the reverse lookup of a subdomain
+    pub parent: Option<ParentName>,
+}
+
+impl Deployment {
+    /// Registers `name` (e.g. `alice` for `alice.sol`), paid by `buyer` in `mint`
+    pub fn register(
+        &self,
+        name: &str,
+        buyer: &Pubkey,
+        mint: &Pubkey,
+        opts: RegisterOptions,
+    ) -> Result<Instruction, ClientError> {
+        check_name(name)?;
+        let (root_domain, vault_owner) = opts
+            .tld
+            .map_or((self.root_domain(), self.vault_owner()), |tld| {
+                (tld.root_domain, tld.vault_owner)
+            });
+        let keys = self.registration_keys(name, &root_domain);
+        let referrer = opts
+            .referrer
+            .map(|referrer| {
+                Ok::<_, ClientError>((
+                    referrer_index(&referrer)?,
+                    get_associated_token_address(&referrer, mint),
+                ))
+            })
+            .transpose()?;
+        let tld = self.tld_account(&root_domain);
+
+        let accounts = create_split_v3::Accounts {
+            naming_service_program: &spl_name_service::ID,
+            root_domain: &root_domain,
+            name: &keys.name,
+            reverse_lookup: &keys.reverse_lookup,
+            system_program: &system_program::ID,
+            central_state: &self.central_state(),
+            buyer,
+            domain_owner: &opts.domain_owner.unwrap_or(*buyer),
+            fee_payer: &opts.fee_payer.unwrap_or(*buyer),
+            buyer_token_source: &opts
+                .buyer_token_source
+                .unwrap_or_else(|| get_associated_token_address(buyer, mint)),
+            pyth_feed_account: &pyth_feed_key(mint)?,
+            vault: &vault_key(&vault_owner, mint),
+            spl_token_program: &spl_token::ID,
+            rent_sysvar: &sysvar::rent::ID,
+            state: &keys.state,
+            registration_record: &keys.registration_record,
+            tld_opt: tld.as_ref(),
+            referrer_account_opt: referrer.as_ref().map(|(_, account)| account),
+        };
+        let params = create_split_v3::Params {
+            name: name.to_owned(),
+            space: opts.space,
+            referrer_idx_opt: referrer.map(|(idx, _)| idx),
+            metadata_url: opts.metadata_url,
+            years: opts.years,
+        };
+        Ok(instruction_auto::create_split_v3(
+            self.program_id(),
+            accounts,
+            params,
+        ))
+    }
+
+    /// Extends the registration of `name` by `years` years, signed by its owner `domain_owner`
+    /// and paid by `buyer` in `mint`. Tokenized names also take the token account holding their
+    /// NFT.
+    #[allow(clippy::too_many_arguments)]
+    pub fn renew(
+        &self,
+        name: &str,
+        domain_owner: &Pubkey,
+        buyer: &Pubkey,
+        mint: &Pubkey,
+        years: u8,
+        tld: Option<TldOptions>,
+        nft_token_account: Option<Pubkey>,
+    ) -> Result<Instruction, ClientError> {
+        check_name(name)?;
+        let (root_domain, vault_owner) = tld
+            .map_or((self.root_domain(), self.vault_owner()), |tld| {
+                (tld.root_domain, tld.vault_owner)
+            });
+        let keys = self.registration_keys(name, &root_domain);
+        let tld = self.tld_account(&root_domain);
+        let nft_record = nft_token_account.map(|_| self.nft_record_key(&keys.name));
+
+        let accounts = renew_ix::Accounts {
+            root_domain: &root_domain,
+            name: &keys.name,
+            registration_record: &keys.registration_record,
+            domain_owner,
+            buyer,
+            buyer_token_source: &get_associated_token_address(buyer, mint),
+            pyth_feed_account: &pyth_feed_key(mint)?,
+            vault: &vault_key(&vault_owner, mint),
+            spl_token_program: &spl_token::ID,
+            tld_opt: tld.as_ref(),
+            nft_record_opt: nft_record.as_ref(),
+            nft_token_account_opt: nft_token_account.as_ref(),
+        };
+        let params = renew_ix::Params {
+            name: name.to_owned(),
+            years,
+        };
+        Ok(instruction_auto::renew(self.program_id(), accounts, params))
+    }
+
+    /// Registers several names of the same owner with `CreateBatch`, the space and metadata URL of
+    /// `opts` are ignored in favor of those of the entries
+    pub fn register_batch(
+        &self,
+        entries: Vec<BatchEntry>,
+        buyer: &Pubkey,
+        mint: &Pubkey,
+        opts: RegisterOptions,
+    ) -> Result<Instruction, ClientError> {
+        for entry in &entries {
+            check_name(&entry.name)?;
+        }
+        let (root_domain, vault_owner) = opts
+            .tld
+            .map_or((self.root_domain(), self.vault_owner()), |tld| {
+                (tld.root_domain, tld.vault_owner)
+            });
+        let names = entries
+            .iter()
+            .flat_map(|entry| {
+                let keys = self.registration_keys(&entry.name, &root_domain);
+                [
+                    keys.name,
+                    keys.reverse_lookup,
+                    keys.state,
+                    keys.registration_record,
+                ]
+            })
+            .collect::<Vec<_>>();
+        let referrer = opts
+            .referrer
+            .map(|referrer| {
+                Ok::<_, ClientError>((
+                    referrer_index(&referrer)?,
+                    get_associated_token_address(&referrer, mint),
+                ))
+            })
+            .transpose()?;
+        let tld = self.tld_account(&root_domain);
+
+        let accounts = create_batch::Accounts {
+            naming_service_program: &spl_name_service::ID,
+            root_domain: &root_domain,
+            system_program: &system_program::ID,
+            central_state: &self.central_state(),
+            buyer,
+            domain_owner: &opts.domain_owner.unwrap_or(*buyer),
+            fee_payer: &opts.fee_payer.unwrap_or(*buyer),
+            buyer_token_source: &opts
+                .buyer_token_source
+                .unwrap_or_else(|| get_associated_token_address(buyer, mint)),
+            pyth_feed_account: &pyth_feed_key(mint)?,
+            vault: &vault_key(&vault_owner, mint),
+            spl_token_program: &spl_token::ID,
+            rent_sysvar: &sysvar::rent::ID,
+            names: &names,
+            referrer_account_opt: referrer.as_ref().map(|(_, account)| account),
+            tld_opt: tld.as_ref(),
+        };
+        let params = create_batch::Params {
+            entries,
+            referrer_idx_opt: referrer.map(|(idx, _)| idx),
+            years: opts.years,
+        };
+        Ok(instruction_auto::create_batch(
+            self.program_id(),
+            accounts,
+            params,
+        ))
+    }
+
+    /// Creates the reverse lookup of `name`, a domain or a subdomain of `opts.parent`
+    pub fn create_reverse(
+        &self,
+        name: &str,
+        fee_payer: &Pubkey,
+        opts: ReverseOptions,
+    ) -> Result<Instruction, ClientError> {
+        check_name(name)?;
+        let root_domain = opts.root_domain.unwrap_or_else(|| self.root_domain());
+        let parent = opts.parent.map(|parent| parent.name_account);
+        let name_account = self.name_key(name, Some(parent.as_ref().unwrap_or(&root_domain)));
+        let tld = self.tld_account(&root_domain);
+
+        let accounts = create_reverse_ix::Accounts {
+            naming_service_program: &spl_name_service::ID,
+            root_domain: &root_domain,
+            reverse_lookup: &self.reverse_key(&name_account, parent.as_ref()),
+            system_program: &system_program::ID,
+            central_state: &self.central_state(),
+            fee_payer,
+            rent_sysvar: &sysvar::rent::ID,
+            parent_name: opts.parent.as_ref().map(|parent| &parent.name_account),
+            parent_name_owner: opts.parent.as_ref().map(|parent| &parent.owner),
+            tld_opt: tld.as_ref(),
+        };
+        let params = create_reverse_ix::Params {
+            name: name.to_owned(),
+            metadata_url: opts.metadata_url,
+        };
+        Ok(instruction_auto::create_reverse(
+            self.program_id(),
+            accounts,
+            params,
+        ))
+    }
+
+    /// Sets the metadata URL stored in the reverse lookup of the domain `name`
+    pub fn update_metadata(
+        &self,
+        name: &str,
+        domain_owner: &Pubkey,
+        metadata_url: Option<String>,
+        root_domain: Option<&Pubkey>,
+    ) -> Result<Instruction, ClientError> {
+        check_name(name)?;
+        let root_domain = root_domain.copied().unwrap_or_else(|| self.root_domain());
+        let tld = self.tld_account(&root_domain);
+        let name_account = self.name_key(name, Some(&root_domain));
+
+        let accounts = update_metadata_ix::Accounts {
+            naming_service_program: &spl_name_service::ID,
+            root_domain: &root_domain,
+            reverse_lookup: &self.reverse_key(&name_account, None),
+            system_program: &system_program::ID,
+            central_state: &self.central_state(),
+            domain_owner,
+            rent_sysvar: &sysvar::rent::ID,
+            tld_opt: tld.as_ref(),
+        };
+        let params = update_metadata_ix::Params { metadata_url };
+        Ok(instruction_auto::update_metadata(
+            self.program_id(),
+            accounts,
+            params,
+        ))
+    }
+
+    /// The TLD account passed along `root_domain`, the default root domain has none
+    fn tld_account(&self, root_domain: &Pubkey) -> Option<Pubkey> {
+        (root_domain != &self.root_domain).then(|| self.tld_key(root_domain))
+    }
+}
+
+/// Registers `name` (e.g. `alice` for `alice.sol`) with the default deployment, see
+/// [`Deployment::register`]
+pub fn register(
+    name: &str,
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    opts: RegisterOptions,
+) -> Result<Instruction, ClientError> {
+    Deployment::default().register(name, buyer, mint, opts)
+}
+
+/// See [`Deployment::renew`]
+pub fn renew(
+    name: &str,
+    domain_owner: &Pubkey,
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    years: u8,
+    tld: Option<TldOptions>,
+    nft_token_account: Option<Pubkey>,
+) -> Result<Instruction, ClientError> {
+    Deployment::default().renew(
+        name,
+        domain_owner,
+        buyer,
+        mint,
+        years,
+        tld,
+        nft_token_account,
+    )
+}
+
+/// See [`Deployment::register_batch`]
+pub fn register_batch(
+    entries: Vec<BatchEntry>,
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    opts: RegisterOptions,
+) -> Result<Instruction, ClientError> {
+    Deployment::default().register_batch(entries, buyer, mint, opts)
+}
+
+/// See [`Deployment::create_reverse`]
+pub fn create_reverse(
+    name: &str,
+    fee_payer: &Pubkey,
+    opts: ReverseOptions,
+) -> Result<Instruction, ClientError> {
+    Deployment::default().create_reverse(name, fee_payer, opts)
+}
+
+/// See [`Deployment::update_metadata`]
+pub fn update_metadata(
+    name: &str,
+    domain_owner: &Pubkey,
+    metadata_url: Option<String>,
+    root_domain: Option<&Pubkey>,
+) -> Result<Instruction, ClientError> {
+    Deployment::default().update_metadata(name, domain_owner, metadata_url, root_domain)
+}
+
+/// Same rule as the program, names are lower case and have no dot or space
+pub fn check_name(name: &str) -> Result<(), ClientError> {
+    if name.is_empty() || name != name.trim().to_lowercase() || name.contains(['.', ' ']) {
+        return Err(ClientError::InvalidName(name.to_owned()));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_register() {
+    use crate::derivation::{tld_key, RegistrationKeys};
+    use sns_registrar::constants::{
+        REFERRER_WHITELIST, ROOT_DOMAIN_ACCOUNT, TOKENS_SYM_MINT_DECIMALS,
+    };
+
+    let buyer = Pubkey::new_unique();
+    let (usdc, _) = TOKENS_SYM_MINT_DECIMALS["USDC"];
+    let referrer = REFERRER_WHITELIST[1];
+    let tld = TldOptions {
+        root_domain: Pubkey::new_unique(),
+        vault_owner: Pubkey::new_unique(),
+    };
+    let ix = register(
+        "alice",
+        &buyer,
+        &usdc,
+        RegisterOptions {
+            referrer: Some(referrer),
+            tld: Some(tld),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let keys = RegistrationKeys::new("alice", &tld.root_domain);
+    let account_keys = ix.accounts.iter().map(|a| a.pubkey).collect::<Vec<_>>();
+    assert_eq!(account_keys[1], tld.root_domain);
+    assert_eq!(account_keys[2], keys.name);
+    assert_eq!(account_keys[3], keys.reverse_lookup);
+    assert_eq!(account_keys[9], get_associated_token_address(&buyer, &usdc));
+    assert_eq!(account_keys[11], vault_key(&tld.vault_owner, &usdc));
+    assert_eq!(account_keys[14], keys.state);
+    assert_eq!(
+        account_keys[15..],
+        [
+            keys.registration_record,
+            tld_key(&tld.root_domain),
+            get_associated_token_address(&referrer, &usdc)
+        ]
+    );
+    assert!(ix.accounts[6].is_signer && ix.accounts[8].is_signer);
+
+    assert!(register("Alice", &buyer, &usdc, RegisterOptions::default()).is_err());
+
+    let entries = ["alice", "bob"]
+        .iter()
+        .map(|name| BatchEntry {
+            name: name.to_string(),
+            space: DEFAULT_SPACE,
+            metadata_url: None,
+        })
+        .collect();
+    let ix = register_batch(entries, &buyer, &usdc, RegisterOptions::default()).unwrap();
+    let bob = RegistrationKeys::new("bob", &ROOT_DOMAIN_ACCOUNT);
+    assert_eq!(ix.accounts.len(), 12 + 2 * 4);
+    assert_eq!(ix.accounts[16].pubkey, bob.name);
+    assert_eq!(ix.accounts[19].pubkey, bob.registration_record);
+    assert!(ix.accounts[16..].iter().all(|a| a.is_writable));
+    let unsupported_mint = Pubkey::new_unique();
+    assert_eq!(
+        register(
+            "alice",
+            &buyer,
+            &unsupported_mint,
+            RegisterOptions::default()
+        ),
+        Err(ClientError::UnsupportedMint(unsupported_mint))
+    );
+
+    let deployment = Deployment::new(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let ix = deployment
+        .register("alice", &buyer, &usdc, RegisterOptions::default())
+        .unwrap();
+    let keys = deployment.registration_keys("alice", &deployment.root_domain());
+    assert_eq!(ix.program_id, deployment.program_id());
+    assert_eq!(ix.accounts[1].pubkey, deployment.root_domain());
+    assert_eq!(ix.accounts[3].pubkey, keys.reverse_lookup);
+    assert_eq!(ix.accounts[5].pubkey, deployment.central_state());
+    assert_eq!(
+        ix.accounts[11].pubkey,
+        vault_key(&deployment.vault_owner(), &usdc)
+    );
+    assert_eq!(ix.accounts.len(), 16);
+}
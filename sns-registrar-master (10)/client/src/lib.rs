@@ -0,0 +1,19 @@
+//! Client of the SNS registrar: instruction builders and an off-chain resolver.
+//!
+//! The builders derive every account of an instruction from the name and the wallets involved,
+//! with the same functions as the program (`get_name_key`, `get_reverse_key` and the PDA seeds of
+//! the program accounts), and return instructions ready to be signed. They target the deployment
+//! the program crate was built for, or any other through the methods of [`Deployment`].
+
+pub mod derivation;
+pub mod error;
+pub mod instructions;
+pub mod resolver;
+
+pub use derivation::Deployment;
+pub use error::{ClientError, ResolverError};
+pub use instructions::{
+    create_reverse, register, register_batch, renew, update_metadata, ParentName, RegisterOptions,
+    ReverseOptions, TldOptions,
+};
+pub use resolver::{AccountFetcher, MemoryStore, Resolution, Resolver};
@@ -0,0 +1,861 @@
+//! Off-chain resolution of domain names, subdomains and their records.
+//!
+//! `Resolver` reads accounts through an `AccountFetcher`, implemented for `RpcClient` with the
+//! `rpc` feature and by `MemoryStore` for tests. Names are resolved in two batches of
+//! `getMultipleAccounts` requests however many names are resolved at once: the name chains first,
+//! then the registration records, NFT records, reverse lookups and records of the names. The
+//! holders of the NFTs of tokenized names are then looked up together, with one
+//! `getTokenLargestAccounts` request per NFT and one batch for the holder token accounts.
+
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+};
+
+use borsh::BorshDeserialize;
+use sns_registrar::{
+    state::{
+        nft_record::NftRecord,
+        primary_domain::{get_verified_primary_domain, PrimaryDomain},
+        record::{Record, RecordData},
+        registration::RegistrationRecord,
+        ReverseLookup, Tag,
+    },
+    utils::get_hashed_name,
+};
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use spl_name_service::state::{get_seeds_and_key, NameRecordHeader};
+
+use crate::{derivation::Deployment, ResolverError};
+
+/// Maximum number of keys of a `getMultipleAccounts` request
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+/// Prefix of the names of subdomain accounts, `sub.alice.sol` is named `"\0sub"` under `alice`
+pub const SUBDOMAIN_PREFIX: &str = "\0";
+/// Records fetched for every resolved name
+pub const RECORDS: [Record; 12] = [
+    Record::Sol,
+    Record::Eth,
+    Record::Btc,
+    Record::Bsc,
+    Record::Inj,
+    Record::A,
+    Record::Aaaa,
+    Record::Cname,
+    Record::Txt,
+    Record::Mx,
+    Record::Ipfs,
+    Record::Arwv,
+];
+
+/// An account returned by an `AccountFetcher`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedAccount {
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+/// Read access to the accounts of a cluster
+pub trait AccountFetcher {
+    /// Fetches at most `MAX_MULTIPLE_ACCOUNTS` accounts, `None` for the accounts that do not exist
+    fn get_multiple_accounts(
+        &self,
+        keys: &[Pubkey],
+    ) -> Result<Vec<Option<FetchedAccount>>, ResolverError>;
+
+    /// Owners of the token accounts holding the NFTs of `mints`, `None` for the NFTs nobody holds.
+    /// The RPC implementation makes one `getTokenLargestAccounts` request per mint, only the
+    /// holder token accounts are fetched in one batch.
+    fn get_nft_holders(&self, mints: &[Pubkey]) -> Result<Vec<Option<Pubkey>>, ResolverError>;
+}
+
+/// A record of a resolved name, written by its current owner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedRecord {
+    pub record: Record,
+    /// The value rendered by `Record::display`
+    pub value: String,
+    pub right_of_association: bool,
+}
+
+/// Everything known about a domain name or a subdomain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    /// The resolved name, e.g. `sub.alice.sol`
+    pub name: String,
+    pub name_account: Pubkey,
+    pub parent: Pubkey,
+    /// The owner of the name account, or the holder of the NFT if the name is tokenized
+    pub owner: Pubkey,
+    pub tokenized: bool,
+    /// Expiry of the registration of the domain, `None` for domains without registration record
+    pub expiry: Option<i64>,
+    /// The SOL record if it proved its right of association, the owner otherwise
+    pub destination: Pubkey,
+    /// The reverse lookup of the name, `None` if it has not been created
+    pub reverse_lookup: Option<ReverseLookup>,
+    pub records: Vec<ResolvedRecord>,
+}
+
+impl Resolution {
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expiry.map_or(false, |expiry| expiry < now)
+    }
+
+    pub fn metadata_url(&self) -> Option<&str> {
+        self.reverse_lookup.as_ref()?.metadata_url.as_deref()
+    }
+
+    pub fn record(&self, record: Record) -> Option<&ResolvedRecord> {
+        self.records.iter().find(|r| r.record == record)
+    }
+}
+
+/// Decodes the header of a name account
+pub fn decode_header(data: &[u8]) -> Option<NameRecordHeader> {
+    NameRecordHeader::unpack_from_slice(data.get(..NameRecordHeader::LEN)?).ok()
+}
+
+/// Decodes a reverse lookup account. Legacy accounts only hold the name, without the metadata URL,
+/// and accounts may be padded with zeros.
+pub fn decode_reverse_lookup(data: &[u8]) -> Option<ReverseLookup> {
+    let mut data = data.get(NameRecordHeader::LEN..)?;
+    let name = String::deserialize(&mut data).ok()?;
+    let metadata_url = match data.first() {
+        Some(1) => Option::<String>::deserialize(&mut data).ok().flatten(),
+        _ => None,
+    };
+    Some(ReverseLookup { name, metadata_url })
+}
+
+/// Resolves names with the accounts of an `AccountFetcher`
+pub struct Resolver<F> {
+    fetcher: F,
+    default_tld: String,
+    deployment: Deployment,
+}
+
+impl<F: AccountFetcher> Resolver<F> {
+    /// `default_tld` is the TLD of the default root domain (e.g. `sol`), the root domains of the
+    /// other TLDs are the name accounts created by `CreateTld`
+    pub fn new(fetcher: F, default_tld: &str) -> Self {
+        Self::with_deployment(fetcher, default_tld, Deployment::default())
+    }
+
+    /// Resolves the names of another deployment of the registrar, `default_tld` being the TLD of
+    /// its default root domain
+    pub fn with_deployment(fetcher: F, default_tld: &str, deployment: Deployment) -> Self {
+        Self {
+            fetcher,
+            default_tld: default_tld.to_owned(),
+            deployment,
+        }
+    }
+
+    pub fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+
+    /// Name accounts from the root domain down to `name`, e.g. `sol`, `alice.sol`, `sub.alice.sol`
+    pub fn name_chain(&self, name: &str) -> Result<Vec<Pubkey>, ResolverError> {
+        let labels = name
+            .trim_end_matches('.')
+            .split('.')
+            .rev()
+            .collect::<Vec<_>>();
+        if labels.len() < 2
+            || labels
+                .iter()
+                .any(|l| l.is_empty() || *l != l.to_lowercase())
+        {
+            return Err(ResolverError::InvalidName(name.to_owned()));
+        }
+        let root = if labels[0] == self.default_tld {
+            self.deployment.root_domain()
+        } else {
+            get_seeds_and_key(
+                &spl_name_service::ID,
+                get_hashed_name(labels[0]),
+                None,
+                None,
+            )
+            .0
+        };
+        let mut chain = vec![root];
+        for (depth, label) in labels[1..].iter().enumerate() {
+            let account_name = if depth == 0 {
+                label.to_string()
+            } else {
+                format!("{}{}", SUBDOMAIN_PREFIX, label)
+            };
+            let (key, _) = get_seeds_and_key(
+                &spl_name_service::ID,
+                get_hashed_name(&account_name),
+                None,
+                chain.last(),
+            );
+            chain.push(key);
+        }
+        Ok(chain)
+    }
+
+    pub fn resolve(&self, name: &str) -> Result<Resolution, ResolverError> {
+        self.resolve_many(&[name])?.remove(0)
+    }
+
+    /// Resolves names in bulk, the outer error is a failure of the fetcher
+    pub fn resolve_many(
+        &self,
+        names: &[&str],
+    ) -> Result<Vec<Result<Resolution, ResolverError>>, ResolverError> {
+        let chains = names
+            .iter()
+            .map(|name| self.name_chain(name))
+            .collect::<Vec<_>>();
+        let name_accounts = self.fetch(chains.iter().flatten().flatten().copied())?;
+
+        // Name chains
+        let headers = names
+            .iter()
+            .zip(&chains)
+            .map(|(name, chain)| {
+                let chain = chain.as_ref().map_err(Clone::clone)?;
+                let mut parent = Pubkey::default();
+                let mut header = None;
+                for key in chain {
+                    let account = name_accounts
+                        .get(key)
+                        .ok_or_else(|| ResolverError::NotFound(name.to_string()))?;
+                    let h = Some(account)
+                        .filter(|a| a.owner == spl_name_service::ID)
+                        .and_then(|a| decode_header(&a.data))
+                        .filter(|h| h.parent_name == parent)
+                        .ok_or(ResolverError::InvalidAccount(*key))?;
+                    parent = *key;
+                    header = Some(h);
+                }
+                Ok(header.unwrap())
+            })
+            .collect::<Vec<Result<NameRecordHeader, ResolverError>>>();
+
+        // Accounts of the names
+        let mut keys = Vec::new();
+        for (chain, header) in chains.iter().zip(&headers) {
+            if let (Ok(chain), Ok(header)) = (chain, header) {
+                keys.extend(self.dependent_keys(chain, header));
+            }
+        }
+        let accounts = self.fetch(keys)?;
+
+        // Holders of the tokenized names
+        let mints = chains
+            .iter()
+            .zip(&headers)
+            .filter_map(|(chain, header)| {
+                let (chain, header) = (chain.as_ref().ok()?, header.as_ref().ok()?);
+                let nft_record = self
+                    .nft_record(&chain[chain.len() - 1], header, &accounts)
+                    .ok()??;
+                Some(nft_record.nft_mint)
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let holders = if mints.is_empty() {
+            HashMap::new()
+        } else {
+            mints
+                .iter()
+                .copied()
+                .zip(self.fetcher.get_nft_holders(&mints)?)
+                .filter_map(|(mint, holder)| Some((mint, holder?)))
+                .collect::<HashMap<_, _>>()
+        };
+
+        Ok(names
+            .iter()
+            .zip(chains)
+            .zip(headers)
+            .map(|((name, chain), header)| {
+                let (chain, header) = (chain?, header?);
+                self.build_resolution(name, &chain, header, &accounts, &holders)
+            })
+            .collect())
+    }
+
+    /// Reverse lookup of a domain or subdomain account
+    pub fn reverse(&self, name_account: &Pubkey) -> Result<Option<ReverseLookup>, ResolverError> {
+        let accounts = self.fetch([*name_account])?;
+        let header = match accounts.get(name_account) {
+            Some(account) => {
+                decode_header(&account.data).ok_or(ResolverError::InvalidAccount(*name_account))?
+            }
+            None => return Ok(None),
+        };
+        let domain_reverse = self.deployment.reverse_key(name_account, None);
+        let subdomain_reverse = self
+            .deployment
+            .reverse_key(name_account, Some(&header.parent_name));
+        let reverses = self.fetch([domain_reverse, subdomain_reverse])?;
+        Ok([domain_reverse, subdomain_reverse]
+            .iter()
+            .filter_map(|key| reverses.get(key))
+            .find_map(|account| decode_reverse_lookup(&account.data)))
+    }
+
+    /// Primary domain of a wallet, `None` if it has none or if it is stale: the name was
+    /// transferred away or its registration expired before `now`
+    pub fn primary_domain(
+        &self,
+        wallet: &Pubkey,
+        now: i64,
+    ) -> Result<Option<String>, ResolverError> {
+        let primary_domain_key = self.deployment.primary_domain_key(wallet);
+        let accounts = self.fetch([primary_domain_key])?;
+        let primary_domain_data = match accounts.get(&primary_domain_key) {
+            Some(account) if account.owner == self.deployment.program_id() => &account.data,
+            _ => return Ok(None),
+        };
+        let name_account = PrimaryDomain::from_buffer(primary_domain_data)
+            .map_err(|_| ResolverError::InvalidAccount(primary_domain_key))?
+            .name_account;
+
+        let name_accounts = self.fetch([name_account])?;
+        let name_account_data = match name_accounts.get(&name_account) {
+            Some(account) if account.owner == spl_name_service::ID => &account.data,
+            _ => return Ok(None),
+        };
+        let header =
+            decode_header(name_account_data).ok_or(ResolverError::InvalidAccount(name_account))?;
+
+        // Domains and subdomains, the registration record is the one of the domain
+        let reverse_keys = [
+            self.deployment.reverse_key(&name_account, None),
+            self.deployment
+                .reverse_key(&name_account, Some(&header.parent_name)),
+        ];
+        let registration_keys = [
+            self.deployment.registration_record_key(&name_account),
+            self.deployment.registration_record_key(&header.parent_name),
+        ];
+        let dependents = self.fetch(reverse_keys.iter().chain(&registration_keys).copied())?;
+        let find = |keys: &[Pubkey]| {
+            keys.iter()
+                .find_map(|key| Some((*key, dependents.get(key)?.data.as_slice())))
+        };
+        let (reverse_lookup, reverse_lookup_data) = match find(&reverse_keys) {
+            Some(reverse_lookup) => reverse_lookup,
+            None => return Ok(None),
+        };
+        let registration_record = find(&registration_keys);
+
+        Ok(get_verified_primary_domain(
+            &self.deployment.program_id(),
+            primary_domain_data,
+            (&name_account, name_account_data),
+            (&reverse_lookup, reverse_lookup_data),
+            registration_record.as_ref().map(|(key, data)| (key, *data)),
+            now,
+        ))
+    }
+
+    /// Registration record, NFT record, reverse lookup and records of the last name of `chain`
+    fn dependent_keys(&self, chain: &[Pubkey], header: &NameRecordHeader) -> Vec<Pubkey> {
+        let name_account = chain[chain.len() - 1];
+        let reverse_parent = (chain.len() > 2).then(|| chain[chain.len() - 2]);
+        let mut keys = vec![
+            self.deployment.registration_record_key(&chain[1]),
+            self.deployment
+                .reverse_key(&name_account, reverse_parent.as_ref()),
+        ];
+        if header.owner == self.deployment.nft_record_key(&name_account) {
+            keys.push(header.owner);
+        }
+        keys.extend(
+            RECORDS
+                .iter()
+                .map(|r| self.deployment.record_key(&name_account, *r)),
+        );
+        keys
+    }
+
+    fn build_resolution(
+        &self,
+        name: &str,
+        chain: &[Pubkey],
+        header: NameRecordHeader,
+        accounts: &HashMap<Pubkey, FetchedAccount>,
+        holders: &HashMap<Pubkey, Pubkey>,
+    ) -> Result<Resolution, ResolverError> {
+        let name_account = chain[chain.len() - 1];
+        let keys = self.dependent_keys(chain, &header);
+        let get = |key: &Pubkey| accounts.get(key).map(|a| a.data.as_slice());
+
+        let expiry = get(&keys[0])
+            .filter(|data| data.first() == Some(&(Tag::RegistrationRecord as u8)))
+            .map(|mut data| RegistrationRecord::deserialize(&mut data))
+            .transpose()
+            .map_err(|_| ResolverError::InvalidAccount(keys[0]))?
+            .map(|record| record.expiry);
+        let reverse_lookup = get(&keys[1]).and_then(decode_reverse_lookup);
+
+        let nft_record = self.nft_record(&name_account, &header, accounts)?;
+        let tokenized = nft_record.is_some();
+        let owner = match nft_record {
+            Some(nft_record) => *holders
+                .get(&nft_record.nft_mint)
+                .ok_or_else(|| ResolverError::NotFound(name.to_owned()))?,
+            None => header.owner,
+        };
+
+        let records = RECORDS
+            .iter()
+            .filter_map(|record| {
+                let account = accounts.get(&self.deployment.record_key(&name_account, *record))?;
+                if account.owner != spl_name_service::ID {
+                    return None;
+                }
+                let data = RecordData::from_buffer(account.data.get(NameRecordHeader::LEN..)?)
+                    .ok()
+                    .filter(|data| data.record == *record && !data.is_stale(&header.owner))?;
+                Some(ResolvedRecord {
+                    record: *record,
+                    value: record.display(&data.content)?,
+                    right_of_association: data.right_of_association,
+                })
+            })
+            .collect::<Vec<_>>();
+        let destination = records
+            .iter()
+            .find(|r| r.record == Record::Sol && r.right_of_association)
+            .and_then(|r| r.value.parse().ok())
+            .unwrap_or(owner);
+
+        Ok(Resolution {
+            name: name.trim_end_matches('.').to_owned(),
+            name_account,
+            parent: header.parent_name,
+            owner,
+            tokenized,
+            expiry,
+            destination,
+            reverse_lookup,
+            records,
+        })
+    }
+
+    /// Active NFT record of a name, `None` if the name is not tokenized
+    fn nft_record(
+        &self,
+        name_account: &Pubkey,
+        header: &NameRecordHeader,
+        accounts: &HashMap<Pubkey, FetchedAccount>,
+    ) -> Result<Option<NftRecord>, ResolverError> {
+        let nft_record_key = self.deployment.nft_record_key(name_account);
+        if header.owner != nft_record_key {
+            return Ok(None);
+        }
+        accounts
+            .get(&nft_record_key)
+            .map(|account| account.data.as_slice())
+            .filter(|data| data.first() == Some(&(Tag::ActiveNftRecord as u8)))
+            .and_then(|mut data| NftRecord::deserialize(&mut data).ok())
+            .map(Some)
+            .ok_or(ResolverError::InvalidAccount(nft_record_key))
+    }
+
+    /// Fetches the accounts of `keys` in batches of `MAX_MULTIPLE_ACCOUNTS`, skipping duplicates
+    fn fetch(
+        &self,
+        keys: impl IntoIterator<Item = Pubkey>,
+    ) -> Result<HashMap<Pubkey, FetchedAccount>, ResolverError> {
+        let mut seen = HashSet::new();
+        let keys = keys
+            .into_iter()
+            .filter(|k| seen.insert(*k))
+            .collect::<Vec<_>>();
+        let mut accounts = HashMap::with_capacity(keys.len());
+        for chunk in keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let fetched = self.fetcher.get_multiple_accounts(chunk)?;
+            accounts.extend(
+                chunk
+                    .iter()
+                    .zip(fetched)
+                    .filter_map(|(key, account)| Some((*key, account?))),
+            );
+        }
+        Ok(accounts)
+    }
+}
+
+/// In-memory `AccountFetcher`, counting the `getMultipleAccounts` requests and the NFT holder
+/// lookups
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    accounts: HashMap<Pubkey, FetchedAccount>,
+    requests: Cell<usize>,
+    nft_holder_lookups: Cell<usize>,
+}
+
+impl MemoryStore {
+    pub fn insert(&mut self, key: Pubkey, owner: Pubkey, data: Vec<u8>) {
+        self.accounts.insert(key, FetchedAccount { owner, data });
+    }
+
+    /// Number of `getMultipleAccounts` requests served
+    pub fn requests(&self) -> usize {
+        self.requests.get()
+    }
+
+    /// Number of NFTs whose holder was looked up, a `getTokenLargestAccounts` request each over RPC
+    pub fn nft_holder_lookups(&self) -> usize {
+        self.nft_holder_lookups.get()
+    }
+}
+
+impl AccountFetcher for MemoryStore {
+    fn get_multiple_accounts(
+        &self,
+        keys: &[Pubkey],
+    ) -> Result<Vec<Option<FetchedAccount>>, ResolverError> {
+        if keys.len() > MAX_MULTIPLE_ACCOUNTS {
+            return Err(ResolverError::Rpc("Too many keys".to_owned()));
+        }
+        self.requests.set(self.requests.get() + 1);
+        Ok(keys.iter().map(|k| self.accounts.get(k).cloned()).collect())
+    }
+
+    fn get_nft_holders(&self, mints: &[Pubkey]) -> Result<Vec<Option<Pubkey>>, ResolverError> {
+        self.nft_holder_lookups
+            .set(self.nft_holder_lookups.get() + mints.len());
+        Ok(mints
+            .iter()
+            .map(|mint| {
+                self.accounts
+                    .values()
+                    .filter(|a| a.owner == spl_token::ID)
+                    .filter_map(|a| spl_token::state::Account::unpack(&a.data).ok())
+                    .find(|a| a.mint == *mint && a.amount == 1)
+                    .map(|a| a.owner)
+            })
+            .collect())
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl AccountFetcher for solana_client::rpc_client::RpcClient {
+    fn get_multiple_accounts(
+        &self,
+        keys: &[Pubkey],
+    ) -> Result<Vec<Option<FetchedAccount>>, ResolverError> {
+        let accounts = solana_client::rpc_client::RpcClient::get_multiple_accounts(self, keys)
+            .map_err(|e| ResolverError::Rpc(e.to_string()))?;
+        Ok(accounts
+            .into_iter()
+            .map(|account| {
+                account.map(|a| FetchedAccount {
+                    owner: a.owner,
+                    data: a.data,
+                })
+            })
+            .collect())
+    }
+
+    fn get_nft_holders(&self, mints: &[Pubkey]) -> Result<Vec<Option<Pubkey>>, ResolverError> {
+        let rpc_error =
+            |e: solana_client::client_error::ClientError| ResolverError::Rpc(e.to_string());
+        // The token account holding each NFT, then the owners of these accounts in batches
+        let token_accounts = mints
+            .iter()
+            .map(|mint| {
+                let holder = self
+                    .get_token_largest_accounts(mint)
+                    .map_err(rpc_error)?
+                    .into_iter()
+                    .find(|balance| balance.amount.amount == "1");
+                holder
+                    .map(|holder| {
+                        holder
+                            .address
+                            .parse::<Pubkey>()
+                            .map_err(|e| ResolverError::Rpc(e.to_string()))
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, ResolverError>>()?;
+        let keys = token_accounts.iter().flatten().copied().collect::<Vec<_>>();
+        let mut owners = HashMap::with_capacity(keys.len());
+        for chunk in keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let accounts = solana_client::rpc_client::RpcClient::get_multiple_accounts(self, chunk)
+                .map_err(rpc_error)?;
+            for (key, account) in chunk.iter().zip(accounts) {
+                if let Some(account) = account {
+                    let token_account = spl_token::state::Account::unpack(&account.data)
+                        .map_err(|_| ResolverError::InvalidAccount(*key))?;
+                    owners.insert(*key, token_account.owner);
+                }
+            }
+        }
+        Ok(token_accounts
+            .iter()
+            .map(|address| owners.get(address.as_ref()?).copied())
+            .collect())
+    }
+}
+
+#[test]
+fn test_resolve_many() {
+    use crate::derivation::reverse_key;
+    use borsh::BorshSerialize;
+    use sns_registrar::{constants::ROOT_DOMAIN_ACCOUNT, utils::get_record_key};
+
+    let name_account = |parent: Pubkey, owner: Pubkey, data: &[u8]| {
+        let mut account = vec![0; NameRecordHeader::LEN];
+        NameRecordHeader {
+            parent_name: parent,
+            owner,
+            class: Pubkey::default(),
+        }
+        .pack_into_slice(&mut account);
+        account.extend_from_slice(data);
+        account
+    };
+    let mut store = MemoryStore::default();
+    let chain = Resolver::new(MemoryStore::default(), "sol")
+        .name_chain("sub.alice.sol")
+        .unwrap();
+
+    let alice_wallet = Pubkey::new_unique();
+    let sub_wallet = Pubkey::new_unique();
+    let (root, alice, sub) = (chain[0], chain[1], chain[2]);
+    assert_eq!(root, ROOT_DOMAIN_ACCOUNT);
+    assert_eq!(alice, crate::derivation::name_key("alice", None));
+    let deployment = Deployment::new(sns_registrar::ID, Pubkey::new_unique(), Pubkey::default());
+    let other_chain = Resolver::with_deployment(MemoryStore::default(), "sol", deployment)
+        .name_chain("alice.sol")
+        .unwrap();
+    assert_eq!(
+        other_chain,
+        [deployment.root_domain(), deployment.name_key("alice", None)]
+    );
+
+    store.insert(
+        root,
+        spl_name_service::ID,
+        name_account(Pubkey::default(), Pubkey::new_unique(), &[]),
+    );
+    store.insert(
+        alice,
+        spl_name_service::ID,
+        name_account(root, alice_wallet, &[0; 1_000]),
+    );
+    store.insert(
+        sub,
+        spl_name_service::ID,
+        name_account(alice, sub_wallet, &[]),
+    );
+    let registration = RegistrationRecord {
+        tag: Tag::RegistrationRecord,
+        nonce: 0,
+        name_account: alice,
+        expiry: 1_000,
+    };
+    store.insert(
+        RegistrationRecord::find_key(&alice, &sns_registrar::ID).0,
+        sns_registrar::ID,
+        registration.try_to_vec().unwrap(),
+    );
+
+    // Legacy reverse lookup of alice, padded, and current reverse lookup of the subdomain
+    let mut legacy_reverse = "alice".to_owned().try_to_vec().unwrap();
+    legacy_reverse.extend_from_slice(&[0; 8]);
+    store.insert(
+        reverse_key(&alice, None),
+        spl_name_service::ID,
+        name_account(Pubkey::default(), Pubkey::new_unique(), &legacy_reverse),
+    );
+    let sub_reverse = ReverseLookup {
+        name: "\0sub".to_owned(),
+        metadata_url: Some("https://example.com/sub.json".to_owned()),
+    };
+    store.insert(
+        reverse_key(&sub, Some(&alice)),
+        spl_name_service::ID,
+        name_account(
+            alice,
+            Pubkey::new_unique(),
+            &sub_reverse.try_to_vec().unwrap(),
+        ),
+    );
+
+    // SOL record with right of association and stale ETH record
+    let destination = Pubkey::new_unique();
+    let mut sol_record =
+        RecordData::new(Record::Sol, &destination.to_string(), alice_wallet).unwrap();
+    sol_record
+        .set_sol_right_of_association(&destination)
+        .unwrap();
+    store.insert(
+        get_record_key(&alice, Record::Sol),
+        spl_name_service::ID,
+        name_account(alice, Pubkey::new_unique(), &sol_record.to_padded_vec()),
+    );
+    let eth_record = RecordData::new(
+        Record::Eth,
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        Pubkey::new_unique(),
+    )
+    .unwrap();
+    store.insert(
+        get_record_key(&alice, Record::Eth),
+        spl_name_service::ID,
+        name_account(alice, Pubkey::new_unique(), &eth_record.to_padded_vec()),
+    );
+
+    // Tokenized name, held by another wallet than the one which tokenized it
+    let carol = crate::derivation::name_key("carol", None);
+    let (nft_record_key, _) = NftRecord::find_key(&carol, &sns_registrar::ID);
+    let (nft_mint, nft_holder, nft_account) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    store.insert(
+        carol,
+        spl_name_service::ID,
+        name_account(root, nft_record_key, &[]),
+    );
+    store.insert(
+        nft_record_key,
+        sns_registrar::ID,
+        NftRecord::new(0, carol, Pubkey::new_unique(), nft_mint)
+            .try_to_vec()
+            .unwrap(),
+    );
+    let mut token_account = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: nft_mint,
+        owner: nft_holder,
+        amount: 1,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut token_account);
+    store.insert(nft_account, spl_token::ID, token_account);
+
+    let resolver = Resolver::new(store, "sol");
+    let results = resolver
+        .resolve_many(&[
+            "sub.alice.sol",
+            "alice.sol",
+            "bob.sol",
+            "Alice.sol",
+            "carol.sol",
+        ])
+        .unwrap();
+    assert_eq!(resolver.fetcher().requests(), 2);
+    assert_eq!(resolver.fetcher().nft_holder_lookups(), 1);
+
+    let sub_resolution = results[0].as_ref().unwrap();
+    assert_eq!(sub_resolution.name_account, sub);
+    assert_eq!(sub_resolution.owner, sub_wallet);
+    assert_eq!(sub_resolution.destination, sub_wallet);
+    assert_eq!(sub_resolution.expiry, Some(1_000));
+    assert_eq!(
+        sub_resolution.metadata_url(),
+        Some("https://example.com/sub.json")
+    );
+
+    let alice_resolution = results[1].as_ref().unwrap();
+    assert_eq!(alice_resolution.owner, alice_wallet);
+    assert_eq!(alice_resolution.destination, destination);
+    assert!(alice_resolution.is_expired(1_001));
+    assert_eq!(
+        alice_resolution.reverse_lookup.as_ref().unwrap().name,
+        "alice"
+    );
+    assert_eq!(alice_resolution.metadata_url(), None);
+    assert_eq!(alice_resolution.records.len(), 1);
+    assert!(alice_resolution.record(Record::Eth).is_none());
+
+    assert_eq!(
+        results[2],
+        Err(ResolverError::NotFound("bob.sol".to_owned()))
+    );
+    assert!(matches!(results[3], Err(ResolverError::InvalidName(_))));
+    let carol_resolution = results[4].as_ref().unwrap();
+    assert!(carol_resolution.tokenized);
+    assert_eq!(carol_resolution.owner, nft_holder);
+    assert_eq!(carol_resolution.expiry, None);
+    assert_eq!(resolver.reverse(&sub).unwrap(), Some(sub_reverse));
+}
+
+#[test]
+fn test_primary_domain() {
+    use crate::derivation::{primary_domain_key, reverse_key};
+    use borsh::BorshSerialize;
+    use sns_registrar::constants::ROOT_DOMAIN_ACCOUNT;
+
+    let name_account = |owner: Pubkey, data: &[u8]| {
+        let mut account = vec![0; NameRecordHeader::LEN];
+        NameRecordHeader {
+            parent_name: ROOT_DOMAIN_ACCOUNT,
+            owner,
+            class: Pubkey::default(),
+        }
+        .pack_into_slice(&mut account);
+        account.extend_from_slice(data);
+        account
+    };
+    let wallet = Pubkey::new_unique();
+    let alice = crate::derivation::name_key("alice", None);
+    let mut store = MemoryStore::default();
+    store.insert(
+        primary_domain_key(&wallet),
+        sns_registrar::ID,
+        PrimaryDomain::new(0, wallet, alice).try_to_vec().unwrap(),
+    );
+    store.insert(alice, spl_name_service::ID, name_account(wallet, &[]));
+    store.insert(
+        reverse_key(&alice, None),
+        spl_name_service::ID,
+        name_account(
+            Pubkey::new_unique(),
+            &ReverseLookup {
+                name: "alice".to_owned(),
+                metadata_url: None,
+            }
+            .try_to_vec()
+            .unwrap(),
+        ),
+    );
+    store.insert(
+        RegistrationRecord::find_key(&alice, &sns_registrar::ID).0,
+        sns_registrar::ID,
+        RegistrationRecord::new(0, alice, 1_000)
+            .try_to_vec()
+            .unwrap(),
+    );
+
+    let resolver = Resolver::new(store, "sol");
+    assert_eq!(
+        resolver.primary_domain(&wallet, 1_000).unwrap(),
+        Some("alice".to_owned())
+    );
+    assert_eq!(resolver.fetcher().requests(), 3);
+    assert_eq!(resolver.primary_domain(&wallet, 1_001).unwrap(), None);
+    assert_eq!(
+        resolver.primary_domain(&Pubkey::new_unique(), 0).unwrap(),
+        None
+    );
+
+    // Transferred away
+    let mut store = resolver.fetcher;
+    store.insert(
+        alice,
+        spl_name_service::ID,
+        name_account(Pubkey::new_unique(), &[]),
+    );
+    let resolver = Resolver::new(store, "sol");
+    assert_eq!(resolver.primary_domain(&wallet, 0).unwrap(), None);
+}
@@ -0,0 +1,470 @@
+//! SQLite database of the indexed registry.
+//!
+//! Keys are stored in base58. Owners and expiries carry the slot of the data they come from, so
+//! that a snapshot or a transaction older than the indexed state does not overwrite it.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::error::IndexerError;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS names (
+    name_account TEXT PRIMARY KEY,
+    name TEXT,
+    parent TEXT,
+    owner TEXT,
+    owner_slot INTEGER NOT NULL DEFAULT 0,
+    reverse_lookup TEXT,
+    expiry INTEGER,
+    expiry_slot INTEGER NOT NULL DEFAULT 0,
+    deleted INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS names_owner ON names (owner);
+CREATE INDEX IF NOT EXISTS names_reverse_lookup ON names (reverse_lookup);
+
+CREATE TABLE IF NOT EXISTS tld_roots (
+    root_domain TEXT PRIMARY KEY
+);
+
+CREATE TABLE IF NOT EXISTS reverse_lookups (
+    reverse_lookup TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    metadata_url TEXT,
+    slot INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS payments (
+    signature TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    name_account TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    referrer TEXT,
+    mint TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS payments_name_account ON payments (name_account);
+
+CREATE TABLE IF NOT EXISTS primary_domains (
+    wallet TEXT PRIMARY KEY,
+    name_account TEXT NOT NULL,
+    slot INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS transactions (
+    signature TEXT PRIMARY KEY,
+    slot INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS cursors (
+    source TEXT PRIMARY KEY,
+    signature TEXT NOT NULL
+);
+
+CREATE VIEW IF NOT EXISTS registry AS
+SELECT
+    n.name_account,
+    COALESCE(n.name, r.name) AS name,
+    n.parent,
+    n.owner,
+    n.expiry,
+    r.metadata_url
+FROM names n LEFT JOIN reverse_lookups r ON r.reverse_lookup = n.reverse_lookup
+WHERE n.deleted = 0 AND n.owner IS NOT NULL;
+";
+
+/// Kind of a payment, stored in the `kind` column
+pub const REGISTRATION_PAYMENT: &str = "registration";
+pub const REFERRER_PAYMENT: &str = "referrer";
+pub const RENEWAL_PAYMENT: &str = "renewal";
+
+/// A row of the `registry` view
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameInfo {
+    pub name_account: String,
+    pub name: Option<String>,
+    pub owner: Option<String>,
+    pub expiry: Option<i64>,
+    pub metadata_url: Option<String>,
+}
+
+/// Total of the referrer fees received by a referrer token account in a mint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferrerTotal {
+    pub referrer: String,
+    pub mint: String,
+    pub registrations: u64,
+    pub amount: u64,
+}
+
+pub struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    /// Opens or creates the database at `path`, in memory for `:memory:`
+    pub fn open(path: &Path) -> Result<Self, IndexerError> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Runs `f` in a transaction, committed if `f` succeeds
+    pub fn transaction<T>(
+        &self,
+        f: impl FnOnce() -> Result<T, IndexerError>,
+    ) -> Result<T, IndexerError> {
+        self.conn.execute_batch("BEGIN")?;
+        match f() {
+            Ok(result) => {
+                self.conn.execute_batch("COMMIT")?;
+                Ok(result)
+            }
+            Err(e) => {
+                self.conn.execute_batch("ROLLBACK")?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Records a name account as of `slot`, a newer owner is kept
+    pub fn upsert_name_account(
+        &self,
+        name_account: &Pubkey,
+        parent: &Pubkey,
+        owner: &Pubkey,
+        reverse_lookup: &Pubkey,
+        slot: u64,
+    ) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT INTO names (name_account, parent, owner, owner_slot, reverse_lookup)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT (name_account) DO UPDATE SET
+                parent = excluded.parent,
+                reverse_lookup = excluded.reverse_lookup,
+                owner = CASE WHEN excluded.owner_slot >= owner_slot
+                    THEN excluded.owner ELSE owner END,
+                deleted = CASE WHEN excluded.owner_slot >= owner_slot THEN 0 ELSE deleted END,
+                owner_slot = MAX(owner_slot, excluded.owner_slot)",
+            params![
+                name_account.to_string(),
+                parent.to_string(),
+                owner.to_string(),
+                slot as i64,
+                reverse_lookup.to_string()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Sets the owner of a name account indexed before, as of `slot`
+    pub fn set_owner(
+        &self,
+        name_account: &Pubkey,
+        owner: &Pubkey,
+        slot: u64,
+    ) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "UPDATE names SET owner = ?2, owner_slot = ?3
+             WHERE name_account = ?1 AND owner_slot <= ?3",
+            params![name_account.to_string(), owner.to_string(), slot as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_name(
+        &self,
+        name_account: &Pubkey,
+        name: &str,
+        reverse_lookup: Option<&Pubkey>,
+    ) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT INTO names (name_account, name, reverse_lookup) VALUES (?1, ?2, ?3)
+             ON CONFLICT (name_account) DO UPDATE SET
+                name = excluded.name,
+                reverse_lookup = COALESCE(excluded.reverse_lookup, reverse_lookup)",
+            params![
+                name_account.to_string(),
+                name,
+                reverse_lookup.map(|k| k.to_string())
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_expiry(
+        &self,
+        name_account: &Pubkey,
+        expiry: i64,
+        slot: u64,
+    ) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT INTO names (name_account, expiry, expiry_slot) VALUES (?1, ?2, ?3)
+             ON CONFLICT (name_account) DO UPDATE SET
+                expiry = CASE WHEN excluded.expiry_slot >= expiry_slot
+                    THEN excluded.expiry ELSE expiry END,
+                expiry_slot = MAX(expiry_slot, excluded.expiry_slot)",
+            params![name_account.to_string(), expiry, slot as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_name(&self, name_account: &Pubkey, slot: u64) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "UPDATE names SET deleted = 1, owner_slot = ?2
+             WHERE name_account = ?1 AND owner_slot <= ?2",
+            params![name_account.to_string(), slot as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Whether `name_account` is an indexed name account
+    pub fn has_name(&self, name_account: &Pubkey) -> Result<bool, IndexerError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT 1 FROM names WHERE name_account = ?1 AND owner IS NOT NULL",
+                params![name_account.to_string()],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    }
+
+    /// Records the root name account of a TLD created with `CreateTld`
+    pub fn insert_tld_root(&self, root_domain: &Pubkey) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO tld_roots (root_domain) VALUES (?1)",
+            params![root_domain.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Whether `root_domain` is the root name account of an indexed TLD
+    pub fn is_tld_root(&self, root_domain: &Pubkey) -> Result<bool, IndexerError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT 1 FROM tld_roots WHERE root_domain = ?1",
+                params![root_domain.to_string()],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    }
+
+    pub fn upsert_reverse_lookup(
+        &self,
+        reverse_lookup: &Pubkey,
+        name: &str,
+        metadata_url: Option<&str>,
+        slot: u64,
+    ) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT INTO reverse_lookups (reverse_lookup, name, metadata_url, slot)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (reverse_lookup) DO UPDATE SET
+                name = excluded.name,
+                metadata_url = excluded.metadata_url,
+                slot = excluded.slot
+             WHERE excluded.slot >= slot",
+            params![reverse_lookup.to_string(), name, metadata_url, slot as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Records the name of a reverse lookup created at `slot`, keeping its indexed metadata URL
+    pub fn set_reverse_name(
+        &self,
+        reverse_lookup: &Pubkey,
+        name: &str,
+        slot: u64,
+    ) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT INTO reverse_lookups (reverse_lookup, name, slot) VALUES (?1, ?2, ?3)
+             ON CONFLICT (reverse_lookup) DO UPDATE SET name = excluded.name",
+            params![reverse_lookup.to_string(), name, slot as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Sets the metadata URL of a reverse lookup indexed before
+    pub fn set_metadata_url(
+        &self,
+        reverse_lookup: &Pubkey,
+        metadata_url: Option<&str>,
+        slot: u64,
+    ) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "UPDATE reverse_lookups SET metadata_url = ?2, slot = ?3
+             WHERE reverse_lookup = ?1 AND slot <= ?3",
+            params![reverse_lookup.to_string(), metadata_url, slot as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_reverse_lookup(
+        &self,
+        reverse_lookup: &Pubkey,
+        slot: u64,
+    ) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "DELETE FROM reverse_lookups WHERE reverse_lookup = ?1 AND slot <= ?2",
+            params![reverse_lookup.to_string(), slot as i64],
+        )?;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert_payment(
+        &self,
+        signature: &str,
+        event_index: usize,
+        slot: u64,
+        name_account: &Pubkey,
+        kind: &str,
+        referrer: Option<&Pubkey>,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO payments
+                (signature, event_index, slot, name_account, kind, referrer, mint, amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                signature,
+                event_index as i64,
+                slot as i64,
+                name_account.to_string(),
+                kind,
+                referrer.map(|k| k.to_string()),
+                mint.to_string(),
+                amount as i64
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_primary_domain(
+        &self,
+        wallet: &Pubkey,
+        name_account: &Pubkey,
+        slot: u64,
+    ) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT INTO primary_domains (wallet, name_account, slot) VALUES (?1, ?2, ?3)
+             ON CONFLICT (wallet) DO UPDATE SET
+                name_account = excluded.name_account,
+                slot = excluded.slot
+             WHERE excluded.slot >= slot",
+            params![wallet.to_string(), name_account.to_string(), slot as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Records an ingested transaction, returns `false` if it was ingested before
+    pub fn insert_transaction(&self, signature: &str, slot: u64) -> Result<bool, IndexerError> {
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO transactions (signature, slot) VALUES (?1, ?2)",
+            params![signature, slot as i64],
+        )?;
+        Ok(inserted == 1)
+    }
+
+    /// Newest transaction ingested from `source`
+    pub fn cursor(&self, source: &str) -> Result<Option<String>, IndexerError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT signature FROM cursors WHERE source = ?1",
+                params![source],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn set_cursor(&self, source: &str, signature: &str) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT INTO cursors (source, signature) VALUES (?1, ?2)
+             ON CONFLICT (source) DO UPDATE SET signature = excluded.signature",
+            params![source, signature],
+        )?;
+        Ok(())
+    }
+
+    /// Names owned by `owner`
+    pub fn names_owned_by(&self, owner: &Pubkey) -> Result<Vec<NameInfo>, IndexerError> {
+        self.query_names("WHERE owner = ?1", params![owner.to_string()])
+    }
+
+    /// Every name, or only the names with a metadata URL
+    pub fn names(&self, with_metadata: bool) -> Result<Vec<NameInfo>, IndexerError> {
+        if with_metadata {
+            self.query_names("WHERE metadata_url IS NOT NULL", params![])
+        } else {
+            self.query_names("", params![])
+        }
+    }
+
+    fn query_names(
+        &self,
+        filter: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<NameInfo>, IndexerError> {
+        let mut statement = self.conn.prepare(&format!(
+            "SELECT name_account, name, owner, expiry, metadata_url FROM registry {}
+             ORDER BY name",
+            filter
+        ))?;
+        let names = statement
+            .query_map(params, |row| {
+                Ok(NameInfo {
+                    name_account: row.get(0)?,
+                    name: row.get(1)?,
+                    owner: row.get(2)?,
+                    expiry: row.get(3)?,
+                    metadata_url: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(names)
+    }
+
+    /// Referrer fees received by each referrer token account, by mint
+    pub fn referrer_totals(&self) -> Result<Vec<ReferrerTotal>, IndexerError> {
+        let mut statement = self.conn.prepare(
+            "SELECT referrer, mint, COUNT(*), SUM(amount) FROM payments
+             WHERE kind = ?1 GROUP BY referrer, mint ORDER BY referrer, mint",
+        )?;
+        let totals = statement
+            .query_map(params![REFERRER_PAYMENT], |row| {
+                Ok(ReferrerTotal {
+                    referrer: row.get(0)?,
+                    mint: row.get(1)?,
+                    registrations: row.get::<_, i64>(2)? as u64,
+                    amount: row.get::<_, i64>(3)? as u64,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(totals)
+    }
+
+    /// Registration payments of a name account, as (mint, amount)
+    pub fn payments(&self, name_account: &Pubkey) -> Result<Vec<(String, u64)>, IndexerError> {
+        let mut statement = self.conn.prepare(
+            "SELECT mint, amount FROM payments WHERE name_account = ?1 AND kind = ?2
+             ORDER BY slot",
+        )?;
+        let payments = statement
+            .query_map(
+                params![name_account.to_string(), REGISTRATION_PAYMENT],
+                |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as u64)),
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(payments)
+    }
+}
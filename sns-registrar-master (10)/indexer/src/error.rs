@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IndexerError {
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("Invalid input {0}: {1}")]
+    Input(String, String),
+    #[error("RPC error: {0}")]
+    Rpc(String),
+}
+
+impl From<solana_client::client_error::ClientError> for IndexerError {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(e.to_string())
+    }
+}
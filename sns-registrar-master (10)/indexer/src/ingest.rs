@@ -0,0 +1,567 @@
+//! Application of account snapshots and registrar events to the database
+
+use base64::Engine;
+use borsh::BorshDeserialize;
+use sns_registrar::{
+    central_state,
+    constants::ROOT_DOMAIN_ACCOUNT,
+    events::Event,
+    state::{primary_domain::PrimaryDomain, registration::RegistrationRecord, tld::Tld, Tag},
+};
+use sns_registrar_client::{
+    derivation::reverse_key,
+    resolver::{decode_header, decode_reverse_lookup},
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    db::{Database, REFERRER_PAYMENT, REGISTRATION_PAYMENT, RENEWAL_PAYMENT},
+    error::IndexerError,
+};
+
+/// Events logged by the registrar in the log messages of a transaction, in order.
+///
+/// `Program data:` lines are attributed to the program at the top of the invocation stack, data
+/// logged by other programs is ignored even if it decodes as an event.
+pub fn parse_events(logs: &[String]) -> Vec<Event> {
+    let registrar = sns_registrar::ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for log in logs {
+        let words = log.split(' ').collect::<Vec<_>>();
+        match words.as_slice() {
+            ["Program", "data:", fields @ ..] => {
+                if stack.last() != Some(&registrar.as_str()) {
+                    continue;
+                }
+                events.extend(
+                    fields
+                        .iter()
+                        .filter_map(|field| {
+                            base64::engine::general_purpose::STANDARD.decode(field).ok()
+                        })
+                        .filter_map(|data| Event::decode(&data)),
+                );
+            }
+            ["Program", program, "invoke", ..] => stack.push(*program),
+            ["Program", _, "success"] => {
+                stack.pop();
+            }
+            ["Program", _, status, ..] if status.starts_with("failed") => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+pub struct Indexer {
+    db: Database,
+}
+
+impl Indexer {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub fn db(&self) -> &Database {
+        &self.db
+    }
+
+    /// Ingests accounts read at `slot`: name accounts of the root domain and of the TLDs and their
+    /// subdomains, reverse lookups, TLDs, registration records and primary domains. Other accounts
+    /// are ignored. Only the header of name accounts is read.
+    pub fn ingest_accounts<'a>(
+        &self,
+        accounts: impl IntoIterator<Item = (&'a Pubkey, &'a Pubkey, &'a [u8])>,
+        slot: u64,
+    ) -> Result<usize, IndexerError> {
+        // Registrar accounts first for the TLD roots, then the name accounts from the roots down:
+        // subdomains are only indexed under indexed names, the accounts left out are retried
+        // until a pass indexes nothing
+        let (registrar, mut pending): (Vec<_>, Vec<_>) = accounts
+            .into_iter()
+            .partition(|(_, owner, _)| **owner == sns_registrar::ID);
+        self.db.transaction(|| {
+            let mut ingested = 0;
+            for (key, owner, data) in registrar {
+                if self.ingest_account(key, owner, data, slot)? {
+                    ingested += 1;
+                }
+            }
+            loop {
+                let mut left_out = Vec::new();
+                for (key, owner, data) in pending.iter().copied() {
+                    if self.ingest_account(key, owner, data, slot)? {
+                        ingested += 1;
+                    } else {
+                        left_out.push((key, owner, data));
+                    }
+                }
+                if left_out.len() == pending.len() {
+                    return Ok(ingested);
+                }
+                pending = left_out;
+            }
+        })
+    }
+
+    fn ingest_account(
+        &self,
+        key: &Pubkey,
+        owner: &Pubkey,
+        data: &[u8],
+        slot: u64,
+    ) -> Result<bool, IndexerError> {
+        if *owner == spl_name_service::ID {
+            let header = match decode_header(data) {
+                Some(header) => header,
+                None => return Ok(false),
+            };
+            if header.class == central_state::KEY {
+                // Reverse lookups and records are classed by the central state
+                if let Some(reverse_lookup) = decode_reverse_lookup(data) {
+                    self.db.upsert_reverse_lookup(
+                        key,
+                        &reverse_lookup.name,
+                        reverse_lookup.metadata_url.as_deref(),
+                        slot,
+                    )?;
+                    return Ok(true);
+                }
+                return Ok(false);
+            }
+            let reverse_lookup = if header.parent_name == ROOT_DOMAIN_ACCOUNT
+                || self.db.is_tld_root(&header.parent_name)?
+            {
+                reverse_key(key, None)
+            } else if self.db.has_name(&header.parent_name)? {
+                reverse_key(key, Some(&header.parent_name))
+            } else {
+                return Ok(false);
+            };
+            self.db.upsert_name_account(
+                key,
+                &header.parent_name,
+                &header.owner,
+                &reverse_lookup,
+                slot,
+            )?;
+            return Ok(true);
+        }
+
+        if *owner == sns_registrar::ID {
+            let mut data = data;
+            match data.first() {
+                Some(tag) if *tag == Tag::RegistrationRecord as u8 => {
+                    if let Ok(record) = RegistrationRecord::deserialize(&mut data) {
+                        self.db
+                            .set_expiry(&record.name_account, record.expiry, slot)?;
+                        return Ok(true);
+                    }
+                }
+                Some(tag) if *tag == Tag::Tld as u8 => {
+                    if let Ok(tld) = Tld::deserialize(&mut data) {
+                        self.db.insert_tld_root(&tld.root_domain)?;
+                        return Ok(true);
+                    }
+                }
+                Some(tag) if *tag == Tag::PrimaryDomain as u8 => {
+                    if let Ok(primary) = PrimaryDomain::deserialize(&mut data) {
+                        self.db
+                            .set_primary_domain(&primary.wallet, &primary.name_account, slot)?;
+                        return Ok(true);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    /// Ingests the events of a successful transaction, returns `None` if it was ingested before and
+    /// the reverse lookups it created otherwise. Their metadata URLs are not logged, callers
+    /// fetch the accounts to index them.
+    pub fn ingest_transaction(
+        &self,
+        signature: &str,
+        slot: u64,
+        logs: &[String],
+    ) -> Result<Option<Vec<Pubkey>>, IndexerError> {
+        self.db.transaction(|| {
+            if !self.db.insert_transaction(signature, slot)? {
+                return Ok(None);
+            }
+            let mut reverse_lookups = Vec::new();
+            for (idx, event) in parse_events(logs).into_iter().enumerate() {
+                if let Event::ReverseCreated { reverse_lookup, .. } = &event {
+                    reverse_lookups.push(*reverse_lookup);
+                }
+                self.apply_event(signature, idx, slot, event)?;
+            }
+            Ok(Some(reverse_lookups))
+        })
+    }
+
+    fn apply_event(
+        &self,
+        signature: &str,
+        idx: usize,
+        slot: u64,
+        event: Event,
+    ) -> Result<(), IndexerError> {
+        match event {
+            Event::Registered {
+                name_account,
+                owner,
+                root_domain,
+                name,
+                mint,
+                amount,
+                expiry,
+            } => {
+                if root_domain != ROOT_DOMAIN_ACCOUNT {
+                    self.db.insert_tld_root(&root_domain)?;
+                }
+                let reverse_lookup = reverse_key(&name_account, None);
+                self.db.upsert_name_account(
+                    &name_account,
+                    &root_domain,
+                    &owner,
+                    &reverse_lookup,
+                    slot,
+                )?;
+                self.db
+                    .set_name(&name_account, &name, Some(&reverse_lookup))?;
+                if let Some(expiry) = expiry {
+                    self.db.set_expiry(&name_account, expiry, slot)?;
+                }
+                if mint != Pubkey::default() {
+                    self.db.insert_payment(
+                        signature,
+                        idx,
+                        slot,
+                        &name_account,
+                        REGISTRATION_PAYMENT,
+                        None,
+                        &mint,
+                        amount,
+                    )?;
+                }
+            }
+            Event::ReverseCreated {
+                name_account,
+                reverse_lookup,
+                name,
+            } => {
+                self.db.set_reverse_name(&reverse_lookup, &name, slot)?;
+                self.db
+                    .set_name(&name_account, &name, Some(&reverse_lookup))?;
+            }
+            Event::MetadataUpdated {
+                reverse_lookup,
+                metadata_url,
+            } => {
+                self.db
+                    .set_metadata_url(&reverse_lookup, metadata_url.as_deref(), slot)?;
+            }
+            Event::Deleted {
+                name_account,
+                reverse_lookup,
+            } => {
+                self.db.delete_name(&name_account, slot)?;
+                self.db.delete_reverse_lookup(&reverse_lookup, slot)?;
+            }
+            Event::ReferrerPaid {
+                name_account,
+                referrer,
+                mint,
+                amount,
+            } => {
+                self.db.insert_payment(
+                    signature,
+                    idx,
+                    slot,
+                    &name_account,
+                    REFERRER_PAYMENT,
+                    Some(&referrer),
+                    &mint,
+                    amount,
+                )?;
+            }
+            Event::PrimaryDomainSet {
+                wallet,
+                name_account,
+            } => {
+                self.db.set_primary_domain(&wallet, &name_account, slot)?;
+            }
+            Event::Renewed {
+                name_account,
+                mint,
+                amount,
+                expiry,
+            } => {
+                self.db.set_expiry(&name_account, expiry, slot)?;
+                self.db.insert_payment(
+                    signature,
+                    idx,
+                    slot,
+                    &name_account,
+                    RENEWAL_PAYMENT,
+                    None,
+                    &mint,
+                    amount,
+                )?;
+            }
+            Event::OwnerChanged {
+                name_account,
+                owner,
+            } => {
+                self.db.set_owner(&name_account, &owner, slot)?;
+            }
+            Event::TldConfigured { root_domain, .. } => {
+                self.db.insert_tld_root(&root_domain)?;
+            }
+            Event::RecordSet { .. }
+            | Event::RecordDeleted { .. }
+            | Event::RecordVerified { .. }
+            | Event::AuctionStarted { .. }
+            | Event::BidPlaced { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_indexer() {
+    use borsh::BorshSerialize;
+    use sns_registrar::events::EVENT_DISCRIMINATOR;
+    use solana_sdk::program_pack::Pack;
+    use spl_name_service::state::NameRecordHeader;
+
+    let data_log = |event: &Event| {
+        let data = [EVENT_DISCRIMINATOR.as_ref(), &event.try_to_vec().unwrap()].concat();
+        format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(data)
+        )
+    };
+    let registrar = sns_registrar::ID;
+    let other_program = Pubkey::new_unique();
+    let (owner, buyer, referrer, mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let name_account = Pubkey::new_unique();
+    let reverse_lookup = reverse_key(&name_account, None);
+    let registered = Event::Registered {
+        name_account,
+        owner,
+        root_domain: ROOT_DOMAIN_ACCOUNT,
+        name: "alice".to_owned(),
+        mint,
+        amount: 20_000_000,
+        expiry: Some(1_900_000_000),
+    };
+    let logs = vec![
+        format!("Program {} invoke [1]", registrar),
+        format!("Program {} invoke [2]", spl_name_service::ID),
+        format!("Program {} success", spl_name_service::ID),
+        data_log(&Event::ReferrerPaid {
+            name_account,
+            referrer,
+            mint,
+            amount: 1_000_000,
+        }),
+        data_log(&registered),
+        data_log(&Event::ReverseCreated {
+            name_account,
+            reverse_lookup,
+            name: "alice".to_owned(),
+        }),
+        format!("Program {} success", registrar),
+        // Forged by another program
+        format!("Program {} invoke [1]", other_program),
+        data_log(&Event::Deleted {
+            name_account,
+            reverse_lookup,
+        }),
+        format!("Program {} success", other_program),
+    ];
+    assert_eq!(parse_events(&logs).len(), 3);
+
+    let indexer = Indexer::new(Database::open(":memory:".as_ref()).unwrap());
+    assert_eq!(
+        indexer.ingest_transaction("sig1", 100, &logs).unwrap(),
+        Some(vec![reverse_lookup])
+    );
+    assert_eq!(
+        indexer.ingest_transaction("sig1", 100, &logs).unwrap(),
+        None
+    );
+
+    // The reverse lookup account holds the metadata URL
+    let header = |parent: Pubkey, owner: Pubkey, class: Pubkey| {
+        let mut data = vec![0; NameRecordHeader::LEN];
+        NameRecordHeader {
+            parent_name: parent,
+            owner,
+            class,
+        }
+        .pack_into_slice(&mut data);
+        data
+    };
+    let mut reverse_data = header(Pubkey::default(), central_state::KEY, central_state::KEY);
+    sns_registrar::state::ReverseLookup {
+        name: "alice".to_owned(),
+        metadata_url: Some("https://example.com/alice.json".to_owned()),
+    }
+    .serialize(&mut reverse_data)
+    .unwrap();
+    indexer
+        .ingest_accounts(
+            vec![(&reverse_lookup, &spl_name_service::ID, &reverse_data[..])],
+            105,
+        )
+        .unwrap();
+    // A snapshot older than the registration does not revert the owner
+    let stale_name_data = header(ROOT_DOMAIN_ACCOUNT, buyer, Pubkey::default());
+    indexer
+        .ingest_accounts(
+            vec![(&name_account, &spl_name_service::ID, &stale_name_data[..])],
+            90,
+        )
+        .unwrap();
+
+    let names = indexer.db().names_owned_by(&owner).unwrap();
+    assert_eq!(names.len(), 1);
+    assert_eq!(names[0].name.as_deref(), Some("alice"));
+    assert_eq!(names[0].expiry, Some(1_900_000_000));
+    assert_eq!(
+        names[0].metadata_url.as_deref(),
+        Some("https://example.com/alice.json")
+    );
+    assert_eq!(
+        indexer.db().payments(&name_account).unwrap(),
+        vec![(mint.to_string(), 20_000_000)]
+    );
+    let referrers = indexer.db().referrer_totals().unwrap();
+    assert_eq!(referrers.len(), 1);
+    assert_eq!(referrers[0].amount, 1_000_000);
+
+    // Transfer seen in a newer snapshot, then deletion
+    let name_data = header(ROOT_DOMAIN_ACCOUNT, buyer, Pubkey::default());
+    indexer
+        .ingest_accounts(
+            vec![(&name_account, &spl_name_service::ID, &name_data[..])],
+            110,
+        )
+        .unwrap();
+    assert!(indexer.db().names_owned_by(&owner).unwrap().is_empty());
+    assert_eq!(indexer.db().names_owned_by(&buyer).unwrap().len(), 1);
+
+    // Tokenized, the NFT record owns the name
+    let nft_record = Pubkey::new_unique();
+    let logs = vec![
+        format!("Program {} invoke [1]", registrar),
+        data_log(&Event::OwnerChanged {
+            name_account,
+            owner: nft_record,
+        }),
+        format!("Program {} success", registrar),
+    ];
+    indexer.ingest_transaction("sig3", 115, &logs).unwrap();
+    assert!(indexer.db().names_owned_by(&buyer).unwrap().is_empty());
+    assert_eq!(indexer.db().names_owned_by(&nft_record).unwrap().len(), 1);
+
+    let logs = vec![
+        format!("Program {} invoke [1]", registrar),
+        data_log(&Event::Deleted {
+            name_account,
+            reverse_lookup,
+        }),
+        format!("Program {} success", registrar),
+    ];
+    indexer.ingest_transaction("sig2", 120, &logs).unwrap();
+    assert!(indexer.db().names(false).unwrap().is_empty());
+}
+
+#[test]
+fn test_tld_and_subdomains() {
+    use borsh::BorshSerialize;
+    use solana_sdk::program_pack::Pack;
+    use spl_name_service::state::NameRecordHeader;
+
+    let header = |parent: Pubkey, owner: Pubkey| {
+        let mut data = vec![0; NameRecordHeader::LEN];
+        NameRecordHeader {
+            parent_name: parent,
+            owner,
+            class: Pubkey::default(),
+        }
+        .pack_into_slice(&mut data);
+        data
+    };
+    let (tld_root, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (alice, sub, subsub, bob, orphan) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let tld = Tld {
+        tag: Tag::Tld,
+        nonce: 0,
+        root_domain: tld_root,
+        vault_owner: Pubkey::new_unique(),
+        enabled: true,
+        usd_prices: [0; 10],
+    }
+    .try_to_vec()
+    .unwrap();
+    let tld_key = Tld::find_key(&tld_root, &sns_registrar::ID).0;
+    let (subsub_data, sub_data, alice_data, bob_data, orphan_data) = (
+        header(sub, owner),
+        header(alice, owner),
+        header(tld_root, owner),
+        header(ROOT_DOMAIN_ACCOUNT, owner),
+        header(Pubkey::new_unique(), owner),
+    );
+
+    // Deepest names first, the TLD last
+    let indexer = Indexer::new(Database::open(":memory:".as_ref()).unwrap());
+    let ingested = indexer
+        .ingest_accounts(
+            vec![
+                (&subsub, &spl_name_service::ID, &subsub_data[..]),
+                (&orphan, &spl_name_service::ID, &orphan_data[..]),
+                (&sub, &spl_name_service::ID, &sub_data[..]),
+                (&alice, &spl_name_service::ID, &alice_data[..]),
+                (&bob, &spl_name_service::ID, &bob_data[..]),
+                (&tld_key, &sns_registrar::ID, &tld[..]),
+            ],
+            100,
+        )
+        .unwrap();
+    assert_eq!(ingested, 5);
+    assert!(indexer.db().is_tld_root(&tld_root).unwrap());
+    assert!(!indexer.db().has_name(&orphan).unwrap());
+    let mut names = indexer
+        .db()
+        .names_owned_by(&owner)
+        .unwrap()
+        .into_iter()
+        .map(|name| name.name_account)
+        .collect::<Vec<_>>();
+    names.sort();
+    let mut expected = [alice, sub, subsub, bob]
+        .iter()
+        .map(|key| key.to_string())
+        .collect::<Vec<_>>();
+    expected.sort();
+    assert_eq!(names, expected);
+}
@@ -0,0 +1,130 @@
+//! `sns-indexer`, a local indexer of the SNS registrar.
+//!
+//! Account snapshots and registrar transaction logs, read from an RPC node such as a local
+//! validator or from JSON exports, are applied to a SQLite database of names, owners, metadata
+//! URLs and payments. Every source can be ingested again: the database only moves forward.
+
+use std::{path::PathBuf, process::exit};
+
+use clap::{Parser, Subcommand};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+mod db;
+mod error;
+mod ingest;
+mod source;
+
+use crate::{
+    db::{Database, NameInfo},
+    error::IndexerError,
+    ingest::Indexer,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "sns-indexer",
+    version,
+    about = "Local SQLite indexer of the SNS registrar"
+)]
+struct Cli {
+    /// SQLite database, created if missing
+    #[arg(long, global = true, default_value = "sns-registry.db")]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index the accounts of an RPC node, then the registrar transactions since the previous sync
+    Sync {
+        #[arg(long, default_value = "http://127.0.0.1:8899")]
+        rpc: String,
+        /// Only ingest the new transactions
+        #[arg(long)]
+        skip_snapshot: bool,
+    },
+    /// Index a JSON export of accounts
+    IngestAccounts {
+        file: PathBuf,
+        /// Slot at which the accounts were read
+        #[arg(long, default_value_t = 0)]
+        slot: u64,
+    },
+    /// Ingest a JSON export of `getTransaction` results
+    IngestTransactions { file: PathBuf },
+    /// List the names owned by a wallet
+    Owned { wallet: Pubkey },
+    /// List the indexed names
+    List {
+        /// Only the names with a metadata URL
+        #[arg(long)]
+        with_metadata: bool,
+    },
+    /// List the registration payments of a name account
+    Payments { name_account: Pubkey },
+    /// Total referrer fees by referrer token account and mint
+    Referrers,
+}
+
+fn print_names(names: &[NameInfo]) {
+    for info in names {
+        println!(
+            "{:<32} {} owner={} expiry={} metadata={}",
+            info.name.as_deref().unwrap_or("?").trim_start_matches('\0'),
+            info.name_account,
+            info.owner.as_deref().unwrap_or("-"),
+            info.expiry.map_or("-".to_owned(), |e| e.to_string()),
+            info.metadata_url.as_deref().unwrap_or("-"),
+        );
+    }
+    println!("{} names", names.len());
+}
+
+fn run(cli: Cli) -> Result<(), IndexerError> {
+    let indexer = Indexer::new(Database::open(&cli.db)?);
+
+    match cli.command {
+        Command::Sync { rpc, skip_snapshot } => {
+            let rpc = RpcClient::new_with_commitment(rpc, CommitmentConfig::confirmed());
+            if !skip_snapshot {
+                let accounts = source::snapshot(&rpc, &indexer)?;
+                println!("Indexed {} accounts", accounts);
+            }
+            let transactions = source::sync(&rpc, &indexer)?;
+            println!("Ingested {} transactions", transactions);
+        }
+        Command::IngestAccounts { file, slot } => {
+            let accounts = source::ingest_account_file(&file, slot, &indexer)?;
+            println!("Indexed {} accounts", accounts);
+        }
+        Command::IngestTransactions { file } => {
+            let transactions = source::ingest_transaction_file(&file, &indexer)?;
+            println!("Ingested {} transactions", transactions);
+        }
+        Command::Owned { wallet } => print_names(&indexer.db().names_owned_by(&wallet)?),
+        Command::List { with_metadata } => print_names(&indexer.db().names(with_metadata)?),
+        Command::Payments { name_account } => {
+            for (mint, amount) in indexer.db().payments(&name_account)? {
+                println!("mint={} amount={}", mint, amount);
+            }
+        }
+        Command::Referrers => {
+            for total in indexer.db().referrer_totals()? {
+                println!(
+                    "{} mint={} registrations={} amount={}",
+                    total.referrer, total.mint, total.registrations, total.amount
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run(Cli::parse()) {
+        eprintln!("Error: {}", e);
+        exit(1);
+    }
+}
@@ -0,0 +1,240 @@
+//! Sources of accounts and transactions: an RPC node such as a local validator, or JSON exports.
+//! Ledger directories are not read directly, a validator started on the ledger serves them.
+
+use std::{fs, path::Path};
+
+use serde_json::Value;
+use sns_registrar::central_state;
+use sns_registrar_client::resolver::{decode_header, MAX_MULTIPLE_ACCOUNTS};
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::{
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+    rpc_response::RpcKeyedAccount,
+};
+use solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiTransactionEncoding,
+};
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{error::IndexerError, ingest::Indexer};
+
+/// Name of the cursor of the registrar transactions read from an RPC node
+pub const RPC_CURSOR: &str = "rpc";
+/// Page size of `getSignaturesForAddress`
+const SIGNATURES_PAGE: usize = 1_000;
+
+/// Indexes the registrar accounts, the accounts classed by the central state (reverse lookups
+/// and records) and the name accounts under the root domain and the TLD roots, subdomains
+/// included. Only the headers of name accounts are fetched, the ingester keeps those descending
+/// from a root.
+pub fn snapshot(rpc: &RpcClient, indexer: &Indexer) -> Result<usize, IndexerError> {
+    let slot = rpc.get_slot()?;
+    let queries = [
+        (sns_registrar::ID, vec![], None),
+        (
+            spl_name_service::ID,
+            vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                64,
+                &central_state::KEY.to_bytes(),
+            ))],
+            None,
+        ),
+        (
+            spl_name_service::ID,
+            vec![],
+            Some(UiDataSliceConfig {
+                offset: 0,
+                length: NameRecordHeader::LEN,
+            }),
+        ),
+    ];
+    let mut accounts = Vec::new();
+    for (program_id, filters, data_slice) in queries {
+        let headers_only = data_slice.is_some();
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters).filter(|f| !f.is_empty()),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let fetched = rpc.get_program_accounts_with_config(&program_id, config)?;
+        if !headers_only {
+            accounts.extend(fetched);
+            continue;
+        }
+        // The accounts classed by the central state were fetched whole
+        accounts.extend(fetched.into_iter().filter(|(_, account)| {
+            decode_header(&account.data).map_or(false, |h| h.class != central_state::KEY)
+        }));
+    }
+    indexer.ingest_accounts(
+        accounts
+            .iter()
+            .map(|(key, account)| (key, &account.owner, account.data.as_slice())),
+        slot,
+    )
+}
+
+/// Ingests the registrar transactions confirmed since the previous sync, oldest first, and
+/// refreshes the reverse lookups they created
+pub fn sync(rpc: &RpcClient, indexer: &Indexer) -> Result<usize, IndexerError> {
+    let until = indexer
+        .db()
+        .cursor(RPC_CURSOR)?
+        .map(|s| parse_signature(&s))
+        .transpose()?;
+    let mut statuses = Vec::new();
+    let mut before = None;
+    loop {
+        let page = rpc.get_signatures_for_address_with_config(
+            &sns_registrar::ID,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until,
+                limit: Some(SIGNATURES_PAGE),
+                commitment: None,
+            },
+        )?;
+        let last_page = page.len() < SIGNATURES_PAGE;
+        before = page
+            .last()
+            .map(|status| parse_signature(&status.signature))
+            .transpose()?;
+        statuses.extend(page);
+        if last_page {
+            break;
+        }
+    }
+
+    let mut ingested = 0;
+    let mut reverse_lookups = Vec::new();
+    for status in statuses.into_iter().rev() {
+        if status.err.is_none() {
+            let tx = rpc.get_transaction_with_config(
+                &parse_signature(&status.signature)?,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: None,
+                    max_supported_transaction_version: Some(0),
+                },
+            )?;
+            if let Some(created) =
+                indexer.ingest_transaction(&status.signature, tx.slot, &log_messages(&tx))?
+            {
+                ingested += 1;
+                reverse_lookups.extend(created);
+            }
+        }
+        indexer.db().set_cursor(RPC_CURSOR, &status.signature)?;
+    }
+    refresh(rpc, indexer, &reverse_lookups)?;
+    Ok(ingested)
+}
+
+/// Indexes the current state of `keys`
+pub fn refresh(rpc: &RpcClient, indexer: &Indexer, keys: &[Pubkey]) -> Result<(), IndexerError> {
+    let slot = rpc.get_slot()?;
+    for keys in keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = rpc.get_multiple_accounts(keys)?;
+        indexer.ingest_accounts(
+            keys.iter().zip(&accounts).filter_map(|(key, account)| {
+                account.as_ref().map(|a| (key, &a.owner, a.data.as_slice()))
+            }),
+            slot,
+        )?;
+    }
+    Ok(())
+}
+
+/// Indexes a JSON export of accounts read at `slot`, in the format of `getProgramAccounts` or of
+/// `solana account --output json`. The file holds one account, an array of accounts or one JSON
+/// value per line.
+pub fn ingest_account_file(
+    path: &Path,
+    slot: u64,
+    indexer: &Indexer,
+) -> Result<usize, IndexerError> {
+    let mut accounts = Vec::new();
+    for value in read_values(path)? {
+        let keyed: RpcKeyedAccount = serde_json::from_value(value).map_err(input_error(path))?;
+        let key = keyed.pubkey.parse::<Pubkey>().map_err(input_error(path))?;
+        let account = keyed.account.decode::<Account>().ok_or_else(|| {
+            IndexerError::Input(path.display().to_string(), "undecodable account".to_owned())
+        })?;
+        accounts.push((key, account));
+    }
+    indexer.ingest_accounts(
+        accounts
+            .iter()
+            .map(|(key, account)| (key, &account.owner, account.data.as_slice())),
+        slot,
+    )
+}
+
+/// Ingests a JSON export of `getTransaction` results, failed transactions are skipped
+pub fn ingest_transaction_file(path: &Path, indexer: &Indexer) -> Result<usize, IndexerError> {
+    let mut ingested = 0;
+    for value in read_values(path)? {
+        let tx: EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_value(value).map_err(input_error(path))?;
+        let failed = tx
+            .transaction
+            .meta
+            .as_ref()
+            .map_or(false, |meta| meta.err.is_some());
+        let signature = match &tx.transaction.transaction {
+            EncodedTransaction::Json(ui) => ui.signatures.first().cloned(),
+            encoded => encoded
+                .decode()
+                .and_then(|t| t.signatures.first().map(|s| s.to_string())),
+        }
+        .ok_or_else(|| {
+            IndexerError::Input(path.display().to_string(), "missing signature".to_owned())
+        })?;
+        if !failed
+            && indexer
+                .ingest_transaction(&signature, tx.slot, &log_messages(&tx))?
+                .is_some()
+        {
+            ingested += 1;
+        }
+    }
+    Ok(ingested)
+}
+
+fn log_messages(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<String> {
+    tx.transaction
+        .meta
+        .as_ref()
+        .and_then(|meta| Option::<Vec<String>>::from(meta.log_messages.clone()))
+        .unwrap_or_default()
+}
+
+/// JSON values of a file, flattening top level arrays
+fn read_values(path: &Path) -> Result<Vec<Value>, IndexerError> {
+    let file = fs::read_to_string(path).map_err(input_error(path))?;
+    let mut values = Vec::new();
+    for value in serde_json::Deserializer::from_str(&file).into_iter::<Value>() {
+        match value.map_err(input_error(path))? {
+            Value::Array(array) => values.extend(array),
+            value => values.push(value),
+        }
+    }
+    Ok(values)
+}
+
+fn parse_signature(signature: &str) -> Result<Signature, IndexerError> {
+    signature
+        .parse()
+        .map_err(|_| IndexerError::Rpc(format!("Invalid signature {}", signature)))
+}
+
+fn input_error<E: std::fmt::Display>(path: &Path) -> impl Fn(E) -> IndexerError + '_ {
+    move |e| IndexerError::Input(path.display().to_string(), e.to_string())
+}
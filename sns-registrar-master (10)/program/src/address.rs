@@ -0,0 +1,238 @@
+//! Parsing of the blockchain addresses stored in address records.
+//!
+//! Every parser only accepts the canonical form of an address and returns `None` otherwise:
+//! base58 public keys, EIP-55 checksummed hex for EVM chains and bech32/bech32m for Bitcoin
+//! and Cosmos chains.
+
+use std::str::FromStr;
+
+use solana_program::{keccak, pubkey::Pubkey};
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+const BECH32_MAX_LEN: usize = 90;
+/// Half of the order of secp256k1, the largest `s` value of a canonical signature
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Maximum length of a bech32 address
+pub const MAX_BECH32_LEN: usize = BECH32_MAX_LEN;
+
+pub fn parse_sol(value: &str) -> Option<Pubkey> {
+    Pubkey::from_str(value).ok()
+}
+
+/// Parses a `0x` prefixed EVM address, the letters of which must follow the EIP-55 checksum
+pub fn parse_evm(value: &str) -> Option<[u8; 20]> {
+    let hex = value.strip_prefix("0x")?;
+    if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut address = [0; 20];
+    for (i, byte) in address.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+    }
+    (to_checksum_address(&address) == value).then_some(address)
+}
+
+/// Encodes an EVM address with the EIP-55 mixed case checksum
+pub fn to_checksum_address(address: &[u8; 20]) -> String {
+    let lower = address
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    let hash = keccak::hash(lower.as_bytes()).to_bytes();
+    let checksummed = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0xf;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+    format!("0x{}", checksummed)
+}
+
+/// Hash signed by `personal_sign` (EIP-191 version 0x45) for `message`
+pub fn eth_personal_message_hash(message: &[u8]) -> [u8; 32] {
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+    keccak::hashv(&[prefix.as_bytes(), message]).to_bytes()
+}
+
+/// EVM address of an uncompressed secp256k1 public key, without its `0x04` prefix
+pub fn eth_address_from_pubkey(pubkey: &[u8; 64]) -> [u8; 20] {
+    let mut address = [0; 20];
+    address.copy_from_slice(&keccak::hash(pubkey).to_bytes()[12..]);
+    address
+}
+
+/// Whether the `s` value of a `r || s` secp256k1 signature is in the lower half of the curve
+/// order. EIP-2 rejects the other half, which makes a second valid signature of every message.
+pub fn is_low_s(signature: &[u8; 64]) -> bool {
+    signature[32..] <= SECP256K1_HALF_ORDER[..]
+}
+
+/// Parses a native segwit Bitcoin address, returned in lower case
+pub fn parse_btc(value: &str) -> Option<String> {
+    let (hrp, data, checksum) = decode_bech32(value)?;
+    let (version, program) = data.split_first()?;
+    let program = convert_bits(program, 5, 8, false)?;
+    let expected_checksum = if *version == 0 {
+        BECH32_CONST
+    } else {
+        BECH32M_CONST
+    };
+    let valid = hrp == "bc"
+        && *version <= 16
+        && (2..=40).contains(&program.len())
+        && (*version != 0 || program.len() == 20 || program.len() == 32)
+        && checksum == expected_checksum;
+    valid.then(|| value.to_lowercase())
+}
+
+/// Parses an Injective address, returned in lower case
+pub fn parse_inj(value: &str) -> Option<String> {
+    let (hrp, data, checksum) = decode_bech32(value)?;
+    let account = convert_bits(&data, 5, 8, false)?;
+    let valid = hrp == "inj" && account.len() == 20 && checksum == BECH32_CONST;
+    valid.then(|| value.to_lowercase())
+}
+
+/// Decodes a bech32 or bech32m string into its human readable part, its 5-bit data without the
+/// checksum and the checksum constant
+fn decode_bech32(value: &str) -> Option<(String, Vec<u8>, u32)> {
+    if value.len() > BECH32_MAX_LEN
+        || (value.to_lowercase() != value && value.to_uppercase() != value)
+    {
+        return None;
+    }
+    let value = value.to_lowercase();
+    let separator = value.rfind('1')?;
+    if separator == 0 || separator + 7 > value.len() {
+        return None;
+    }
+    let (hrp, data) = (&value[..separator], &value[separator + 1..]);
+    if !hrp.bytes().all(|b| (33..=126).contains(&b)) {
+        return None;
+    }
+    let data = data
+        .bytes()
+        .map(|b| BECH32_CHARSET.iter().position(|c| *c == b).map(|i| i as u8))
+        .collect::<Option<Vec<u8>>>()?;
+
+    let mut values = hrp.bytes().map(|b| b >> 5).collect::<Vec<u8>>();
+    values.push(0);
+    values.extend(hrp.bytes().map(|b| b & 31));
+    values.extend_from_slice(&data);
+    let checksum = bech32_polymod(&values);
+    if checksum != BECH32_CONST && checksum != BECH32M_CONST {
+        return None;
+    }
+
+    Some((hrp.to_owned(), data[..data.len() - 6].to_vec(), checksum))
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut chk = 1u32;
+    for v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ *v as u32;
+        for (i, g) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+/// Regroups bits, padding the last group with zeros if `pad` and otherwise requiring the leftover
+/// bits to be zeros
+pub(crate) fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let mut result = Vec::with_capacity(data.len() * from as usize / to as usize);
+    let max = (1 << to) - 1;
+    for v in data {
+        acc = (acc << from) | *v as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            result.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            result.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return None;
+    }
+    Some(result)
+}
+
+#[test]
+fn test_parse_addresses() {
+    let eth = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    assert!(parse_evm(eth).is_some());
+    assert!(parse_evm(&eth.to_lowercase()).is_none());
+    assert!(parse_evm(&eth[2..]).is_none());
+
+    let sol = Pubkey::new_unique();
+    assert_eq!(parse_sol(&sol.to_string()), Some(sol));
+    assert!(parse_sol("0OIl").is_none());
+
+    let btc = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    assert_eq!(parse_btc(&btc.to_uppercase()).as_deref(), Some(btc));
+    assert!(parse_btc("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5").is_none());
+    assert!(parse_btc("bc1pqqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0sg5tmnz").is_some());
+
+    assert!(parse_inj("inj1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnflx7rp").is_some());
+    assert!(parse_inj(btc).is_none());
+}
+
+#[test]
+fn test_eth_personal_sign_recovery() {
+    use solana_program::secp256k1_recover::secp256k1_recover;
+
+    let hex = |value: &str| {
+        (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+            .collect::<Vec<u8>>()
+    };
+    // personal_sign of "I♥SF" by the private key
+    // 0xb25c7db31feed9122727bf0939dc769a96564b2de4c4726d035b36ecf1e5b364
+    let hash = eth_personal_message_hash("I♥SF".as_bytes());
+    assert_eq!(
+        hash.to_vec(),
+        hex("1476abb745d423bf09273f1afd887d951181d25adc66c4834a70491911b7f750")
+    );
+    let mut signature = [0; 64];
+    signature.copy_from_slice(&hex(
+        "e6ca9bba58c88611fad66a6ce8f996908195593807c4b38bd528d2cff09d4eb3\
+         3e5bfbbf4d3e39b1a2fd816a7680c19ebebaf3a141b239934ad43cb33fcec8ce",
+    ));
+    let recovery_id = 28 - 27;
+    let signer = secp256k1_recover(&hash, recovery_id, &signature).unwrap();
+    assert_eq!(
+        to_checksum_address(&eth_address_from_pubkey(&signer.to_bytes())),
+        "0x5ce9454909639D2D17A3F753ce7d93fa0b9aB12E"
+    );
+    assert!(is_low_s(&signature));
+
+    // Same signature with s replaced by n - s
+    signature[32..].copy_from_slice(&hex(
+        "c1a40440b2c1c64e5d027e95897f3e5ffbf3e9456d9666a874fe21d990677873",
+    ));
+    assert!(!is_low_s(&signature));
+    assert!(is_low_s(&[0; 64]));
+}
@@ -0,0 +1,125 @@
+//! Helpers for programs invoking the registrar through CPI.
+//!
+//! `CreateSplitV2`, `CreateSplitV3`, `CreateWithNft` and `CreateReverse` set a Borsh-encoded
+//! `RegistrationResult` as return data, `Resolve` sets a `Resolution`. The `invoke_*` functions
+//! below build the instruction, invoke it with the provided account infos and read the result
+//! back, e.g.
+//!
+//! ```ignore
+//! let result = sns_registrar::composability::invoke_create_split_v2(
+//!     accounts,      // create_split_v2::Accounts<Pubkey>
+//!     params,        // create_split_v2::Params
+//!     account_infos, // the `AccountInfo`s of the accounts, and of the registrar program
+//!     &[],           // signer seeds when the buyer or the fee payer is a PDA
+//! )?;
+//! msg!("Registered {} for {}", result.name_account, result.amount);
+//! ```
+//!
+//! Depend on the registrar with the `no-entrypoint` feature to use this module.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    msg,
+    program::{get_return_data, invoke, invoke_signed, set_return_data},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    instruction_auto,
+    processor::{
+        create_reverse, create_split_v2, create_split_v3, create_with_nft,
+        resolve::{self, Resolution},
+    },
+};
+
+/// Return data of the registration instructions
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct RegistrationResult {
+    /// The name account
+    pub name_account: Pubkey,
+    /// The reverse lookup account of the name
+    pub reverse_lookup: Pubkey,
+    /// Amount of tokens charged to the buyer, 0 for free registrations
+    pub amount: u64,
+}
+
+impl RegistrationResult {
+    pub fn set_return_data(&self) {
+        set_return_data(&self.try_to_vec().unwrap())
+    }
+
+    /// Reads the result of the last registrar instruction invoked by the calling program
+    pub fn get_return_data() -> Result<Self, ProgramError> {
+        read_return_data()
+    }
+}
+
+fn read_return_data<T: BorshDeserialize>() -> Result<T, ProgramError> {
+    match get_return_data() {
+        Some((program_id, data)) if program_id == crate::ID => Ok(T::try_from_slice(&data)?),
+        _ => {
+            msg!("The registrar did not set return data");
+            Err(ProgramError::InvalidAccountData)
+        }
+    }
+}
+
+/// Registers a domain name with `CreateSplitV2`
+pub fn invoke_create_split_v2(
+    accounts: create_split_v2::Accounts<Pubkey>,
+    params: create_split_v2::Params,
+    account_infos: &[AccountInfo],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<RegistrationResult, ProgramError> {
+    let ix = instruction_auto::create_split_v2(crate::ID, accounts, params);
+    invoke_signed(&ix, account_infos, signer_seeds)?;
+    RegistrationResult::get_return_data()
+}
+
+/// Registers a domain name for several years with `CreateSplitV3`
+pub fn invoke_create_split_v3(
+    accounts: create_split_v3::Accounts<Pubkey>,
+    params: create_split_v3::Params,
+    account_infos: &[AccountInfo],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<RegistrationResult, ProgramError> {
+    let ix = instruction_auto::create_split_v3(crate::ID, accounts, params);
+    invoke_signed(&ix, account_infos, signer_seeds)?;
+    RegistrationResult::get_return_data()
+}
+
+/// Registers a domain name with `CreateWithNft`
+pub fn invoke_create_with_nft(
+    accounts: create_with_nft::Accounts<Pubkey>,
+    params: create_with_nft::Params,
+    account_infos: &[AccountInfo],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<RegistrationResult, ProgramError> {
+    let ix = instruction_auto::create_with_nft(crate::ID, accounts, params);
+    invoke_signed(&ix, account_infos, signer_seeds)?;
+    RegistrationResult::get_return_data()
+}
+
+/// Creates the reverse lookup account of a domain name with `CreateReverse`
+pub fn invoke_create_reverse(
+    accounts: create_reverse::Accounts<Pubkey>,
+    params: create_reverse::Params,
+    account_infos: &[AccountInfo],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<RegistrationResult, ProgramError> {
+    let ix = instruction_auto::create_reverse(crate::ID, accounts, params);
+    invoke_signed(&ix, account_infos, signer_seeds)?;
+    RegistrationResult::get_return_data()
+}
+
+/// Resolves a domain name or a subdomain with `Resolve`, fails if the registration expired
+pub fn invoke_resolve(
+    accounts: resolve::Accounts<Pubkey>,
+    account_infos: &[AccountInfo],
+) -> Result<Resolution, ProgramError> {
+    let ix = instruction_auto::resolve(crate::ID, accounts, resolve::Params {});
+    invoke(&ix, account_infos)?;
+    read_return_data()
+}
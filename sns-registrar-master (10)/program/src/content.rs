@@ -0,0 +1,188 @@
+//! Parsing of the content identifiers stored in content records.
+//!
+//! IPFS records hold a binary CID: a CIDv0 is stored as its sha2-256 multihash and a CIDv1 as
+//! `<version><multicodec><multihash>` with unsigned varints. Values are accepted as a base58btc
+//! CIDv0 (`Qm...`) or as a multibase CIDv1 (base32, base58btc or base16) and are displayed as a
+//! CIDv0 or a base32 CIDv1. Arweave records hold the 32 bytes of a transaction id, written in
+//! unpadded base64url.
+
+use crate::{address::convert_bits, state::record::Record};
+
+/// Maximum length of a binary CID: version, multicodec, hash function, digest length and digest
+pub const MAX_CID_LEN: usize = 1 + 3 + 3 + 1 + 64;
+/// Length of an Arweave transaction id
+pub const ARWEAVE_TX_ID_LEN: usize = 32;
+
+/// Default gateways of `gateway_url`
+pub const IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+pub const ARWEAVE_GATEWAY: &str = "https://arweave.net/";
+
+const SHA2_256: u8 = 0x12;
+const DAG_PB: u8 = 0x70;
+/// Multicodecs of CIDv1 contents: raw, dag-pb, dag-cbor, libp2p-key and dag-json
+const CONTENT_CODECS: [u64; 5] = [0x55, 0x70, 0x71, 0x72, 0x0129];
+/// Multihash functions and their digest length: sha2-256, sha2-512, blake3 and blake2b-256
+const HASH_FUNCTIONS: [(u64, u64); 4] = [(0x12, 32), (0x13, 64), (0x1e, 32), (0xb220, 32)];
+
+const BASE16_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Parses a CIDv0 or a multibase CIDv1 and returns the binary CID
+pub fn parse_ipfs(value: &str) -> Option<Vec<u8>> {
+    if value.starts_with("Qm") {
+        let multihash = bs58::decode(value).into_vec().ok()?;
+        return is_cid_v0(&multihash).then_some(multihash);
+    }
+    let mut chars = value.chars();
+    let base = chars.next()?;
+    let encoded = chars.as_str();
+    let has_lowercase = encoded.bytes().any(|b| b.is_ascii_lowercase());
+    let cid = match base {
+        'b' => decode_base(encoded, BASE32_ALPHABET, 5)?,
+        'B' if !has_lowercase => decode_base(&encoded.to_lowercase(), BASE32_ALPHABET, 5)?,
+        'z' => bs58::decode(encoded).into_vec().ok()?,
+        'f' => decode_base(encoded, BASE16_ALPHABET, 4)?,
+        'F' if !has_lowercase => decode_base(&encoded.to_lowercase(), BASE16_ALPHABET, 4)?,
+        _ => return None,
+    };
+    is_cid_v1(&cid).then_some(cid)
+}
+
+/// Renders a binary CID as a CIDv0 or a base32 CIDv1
+pub fn display_ipfs(cid: &[u8]) -> Option<String> {
+    if is_cid_v0(cid) {
+        Some(bs58::encode(cid).into_string())
+    } else if is_cid_v1(cid) {
+        Some(format!("b{}", encode_base(cid, BASE32_ALPHABET, 5)))
+    } else {
+        None
+    }
+}
+
+/// Parses an Arweave transaction id
+pub fn parse_arweave(value: &str) -> Option<[u8; ARWEAVE_TX_ID_LEN]> {
+    let bytes = decode_base(value, BASE64URL_ALPHABET, 6)?;
+    if bytes.len() != ARWEAVE_TX_ID_LEN {
+        return None;
+    }
+    let mut tx_id = [0; ARWEAVE_TX_ID_LEN];
+    tx_id.copy_from_slice(&bytes);
+    Some(tx_id)
+}
+
+pub fn display_arweave(tx_id: &[u8]) -> Option<String> {
+    (tx_id.len() == ARWEAVE_TX_ID_LEN).then(|| encode_base(tx_id, BASE64URL_ALPHABET, 6))
+}
+
+/// URL of the content of an IPFS or Arweave record on a path gateway, `IPFS_GATEWAY` and
+/// `ARWEAVE_GATEWAY` by default
+pub fn gateway_url(record: Record, content: &[u8], gateway: Option<&str>) -> Option<String> {
+    let (id, default_gateway) = match record {
+        Record::Ipfs => (display_ipfs(content)?, IPFS_GATEWAY),
+        Record::Arwv => (display_arweave(content)?, ARWEAVE_GATEWAY),
+        _ => return None,
+    };
+    let gateway = gateway.unwrap_or(default_gateway);
+    Some(format!("{}/{}", gateway.trim_end_matches('/'), id))
+}
+
+/// URL of the content of an IPFS record on a subdomain gateway such as `dweb.link`, which serves
+/// every website from its own origin
+pub fn ipfs_subdomain_url(cid: &[u8], gateway_host: &str) -> Option<String> {
+    let cid_v1 = if is_cid_v0(cid) {
+        [&[1, DAG_PB], cid].concat()
+    } else if is_cid_v1(cid) {
+        cid.to_vec()
+    } else {
+        return None;
+    };
+    let label = format!("b{}", encode_base(&cid_v1, BASE32_ALPHABET, 5));
+    (label.len() <= 63).then(|| format!("https://{}.ipfs.{}/", label, gateway_host))
+}
+
+fn is_cid_v0(cid: &[u8]) -> bool {
+    cid.len() == 34 && cid[..2] == [SHA2_256, 32]
+}
+
+fn is_cid_v1(mut cid: &[u8]) -> bool {
+    let mut header = || read_varint(&mut cid);
+    match (header(), header(), header(), header()) {
+        (Some(1), Some(codec), Some(hash), Some(len)) => {
+            CONTENT_CODECS.contains(&codec)
+                && HASH_FUNCTIONS.contains(&(hash, len))
+                && cid.len() as u64 == len
+        }
+        _ => false,
+    }
+}
+
+/// Reads a minimally encoded unsigned varint
+fn read_varint(data: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for i in 0..9 {
+        let (byte, rest) = data.split_first()?;
+        *data = rest;
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            return (i == 0 || *byte != 0).then_some(value);
+        }
+    }
+    None
+}
+
+fn decode_base(value: &str, alphabet: &[u8], bits: u32) -> Option<Vec<u8>> {
+    let values = value
+        .bytes()
+        .map(|b| alphabet.iter().position(|c| *c == b).map(|i| i as u8))
+        .collect::<Option<Vec<u8>>>()?;
+    convert_bits(&values, bits, 8, false)
+}
+
+fn encode_base(data: &[u8], alphabet: &[u8], bits: u32) -> String {
+    convert_bits(data, 8, bits, true)
+        .unwrap_or_default()
+        .iter()
+        .map(|v| alphabet[*v as usize] as char)
+        .collect()
+}
+
+#[test]
+fn test_content_ids() {
+    let cid_v0 = "QmbWqxBEKC3P8tqsKc98xmWNzrzDtRLMiMPL8wBuTGsMnR";
+    let cid_v1 = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    let cid = parse_ipfs(cid_v0).unwrap();
+    assert_eq!(display_ipfs(&cid).as_deref(), Some(cid_v0));
+    assert_eq!(
+        ipfs_subdomain_url(&cid, "dweb.link").unwrap(),
+        format!("https://{}.ipfs.dweb.link/", cid_v1)
+    );
+    assert_eq!(
+        gateway_url(Record::Ipfs, &cid, None).unwrap(),
+        format!("https://ipfs.io/ipfs/{}", cid_v0)
+    );
+
+    let cid = parse_ipfs(cid_v1).unwrap();
+    assert_eq!(cid.len(), 36);
+    assert_eq!(display_ipfs(&cid).as_deref(), Some(cid_v1));
+    assert_eq!(parse_ipfs(&cid_v1.to_uppercase()), Some(cid.clone()));
+    let base16 = format!("f{}", encode_base(&cid, BASE16_ALPHABET, 4));
+    assert_eq!(parse_ipfs(&base16), Some(cid.clone()));
+    let base58 = format!("z{}", bs58::encode(&cid).into_string());
+    assert_eq!(parse_ipfs(&base58), Some(cid));
+    assert!(parse_ipfs(&cid_v1[1..]).is_none());
+    assert!(parse_ipfs(&cid_v1[..cid_v1.len() - 2]).is_none());
+    assert!(parse_ipfs("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdj").is_none());
+    assert!(parse_ipfs("QmbWqxBEKC3P8tqsKc98xmWNzrzDtRLMiMPL8wBuTGsMn").is_none());
+
+    let tx_id = "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U";
+    let bytes = parse_arweave(tx_id).unwrap();
+    assert_eq!(display_arweave(&bytes).as_deref(), Some(tx_id));
+    assert_eq!(
+        gateway_url(Record::Arwv, &bytes, Some("https://ar-io.net/")).unwrap(),
+        format!("https://ar-io.net/{}", tx_id)
+    );
+    assert!(parse_arweave("bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_V").is_none());
+    assert!(parse_arweave("bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_").is_none());
+}
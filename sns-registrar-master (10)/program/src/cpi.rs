@@ -3,6 +3,7 @@ use borsh::BorshSerialize;
 use solana_program::{
     account_info::AccountInfo,
     entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
     program::{invoke, invoke_signed},
     program_pack::Pack,
     pubkey::Pubkey,
@@ -62,6 +63,9 @@ impl Cpi {
         )
     }
 
+    /// Creates a name account under `root_name_account`, signed by its owner `authority`.
+    /// Without `root_name_account` the name is a root name, which anyone can create: the caller has
+    /// to handle the account being taken beforehand.
     #[allow(clippy::too_many_arguments)]
     pub fn create_name_account<'a>(
         name_service_program: &AccountInfo<'a>,
@@ -69,7 +73,7 @@ impl Cpi {
         name_account: &AccountInfo<'a>,
         fee_payer: &AccountInfo<'a>,
         new_owner_account: &AccountInfo<'a>,
-        root_name_account: &AccountInfo<'a>,
+        root_name_account: Option<&AccountInfo<'a>>,
         authority: &AccountInfo<'a>,
         hashed_name: Vec<u8>,
         lamports: u64,
@@ -87,23 +91,23 @@ impl Cpi {
             *fee_payer.key,
             *new_owner_account.key,
             None,
-            Some(*root_name_account.key),
-            Some(*authority.key),
+            root_name_account.map(|root_name_account| *root_name_account.key),
+            root_name_account.map(|_| *authority.key),
         )?;
 
-        invoke_signed(
-            &create_name_instruction,
-            &[
-                name_service_program.clone(),
-                fee_payer.clone(),
-                name_account.clone(),
-                new_owner_account.clone(),
-                system_program_account.clone(),
-                root_name_account.clone(),
-                authority.clone(),
-            ],
-            &[signer_seeds],
-        )
+        let mut account_infos = vec![
+            name_service_program.clone(),
+            fee_payer.clone(),
+            name_account.clone(),
+            new_owner_account.clone(),
+            system_program_account.clone(),
+        ];
+        if let Some(root_name_account) = root_name_account {
+            account_infos.push(root_name_account.clone());
+            account_infos.push(authority.clone());
+        }
+
+        invoke_signed(&create_name_instruction, &account_infos, &[signer_seeds])
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -179,6 +183,87 @@ impl Cpi {
         Ok(())
     }
 
+    /// Creates a name account under `parent_name` owned and classed by `authority`, then writes `data`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_child_name_account<'a>(
+        name_service_program: &AccountInfo<'a>,
+        system_program_account: &AccountInfo<'a>,
+        name_account: &AccountInfo<'a>,
+        fee_payer: &AccountInfo<'a>,
+        parent_name: &AccountInfo<'a>,
+        parent_name_owner: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        hashed_name: Vec<u8>,
+        data: Vec<u8>,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(NameRecordHeader::LEN + data.len());
+        let create_name_instruction = spl_name_service::instruction::create(
+            *name_service_program.key,
+            NameRegistryInstruction::Create {
+                hashed_name,
+                lamports,
+                space: data.len() as u32,
+            },
+            *name_account.key,
+            *fee_payer.key,
+            *authority.key,
+            Some(*authority.key),
+            Some(*parent_name.key),
+            Some(*parent_name_owner.key),
+        )?;
+
+        invoke_signed(
+            &create_name_instruction,
+            &[
+                name_service_program.clone(),
+                fee_payer.clone(),
+                authority.clone(),
+                name_account.clone(),
+                system_program_account.clone(),
+                parent_name.clone(),
+                parent_name_owner.clone(),
+            ],
+            &[signer_seeds],
+        )?;
+
+        Self::write_name_account(
+            name_service_program,
+            name_account,
+            authority,
+            data,
+            signer_seeds,
+        )
+    }
+
+    /// Overwrites the data of a name account classed by `authority`
+    pub fn write_name_account<'a>(
+        name_service_program: &AccountInfo<'a>,
+        name_account: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        data: Vec<u8>,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let update_instruction = spl_name_service::instruction::update(
+            *name_service_program.key,
+            0,
+            data,
+            *name_account.key,
+            *authority.key,
+            None,
+        )?;
+
+        invoke_signed(
+            &update_instruction,
+            &[
+                name_service_program.clone(),
+                name_account.clone(),
+                authority.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+
     pub fn transfer_name_account<'a>(
         name_service_program: &AccountInfo<'a>,
         old_owner_account: &AccountInfo<'a>,
@@ -243,4 +328,96 @@ impl Cpi {
             &[signer_seeds],
         )
     }
+
+    /// Transfers a name account using the signature of the owner of its parent
+    pub fn transfer_name_account_as_parent_owner<'a>(
+        name_service_program: &AccountInfo<'a>,
+        name_account: &AccountInfo<'a>,
+        parent_name: &AccountInfo<'a>,
+        parent_name_owner: &AccountInfo<'a>,
+        new_owner_key: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        // The name service expects the (unused) class account before the parent name
+        let transfer_name_instruction = Instruction {
+            program_id: *name_service_program.key,
+            accounts: vec![
+                AccountMeta::new(*name_account.key, false),
+                AccountMeta::new_readonly(*parent_name_owner.key, true),
+                AccountMeta::new_readonly(*parent_name_owner.key, true),
+                AccountMeta::new_readonly(*parent_name.key, false),
+            ],
+            data: NameRegistryInstruction::Transfer {
+                new_owner: *new_owner_key,
+            }
+            .try_to_vec()?,
+        };
+
+        invoke_signed(
+            &transfer_name_instruction,
+            &[
+                name_service_program.clone(),
+                name_account.clone(),
+                parent_name_owner.clone(),
+                parent_name.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+
+    pub fn delete_name_account<'a>(
+        name_service_program: &AccountInfo<'a>,
+        name_account: &AccountInfo<'a>,
+        owner: &AccountInfo<'a>,
+        refund_target: &AccountInfo<'a>,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let delete_instruction = spl_name_service::instruction::delete(
+            *name_service_program.key,
+            *name_account.key,
+            *owner.key,
+            *refund_target.key,
+        )?;
+
+        invoke_signed(
+            &delete_instruction,
+            &[
+                name_service_program.clone(),
+                owner.clone(),
+                name_account.clone(),
+                refund_target.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+
+    /// Transfers tokens out of an account owned by a program derived address
+    pub fn transfer_tokens_signed<'a>(
+        spl_token_program: &AccountInfo<'a>,
+        source: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        amount: u64,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let transfer_instruction = spl_token::instruction::transfer(
+            &spl_token::ID,
+            source.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+        )?;
+
+        invoke_signed(
+            &transfer_instruction,
+            &[
+                spl_token_program.clone(),
+                source.clone(),
+                destination.clone(),
+                authority.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
 }
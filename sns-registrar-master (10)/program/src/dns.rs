@@ -0,0 +1,360 @@
+//! Wire format of the DNS records stored under domain names.
+//!
+//! A DNS record account holds a whole RRset: the TTL (`u32`, big endian) followed by the RDATA of
+//! every resource record, each prefixed by its length (`u16`, big endian) as in a DNS message.
+//! Values are written in zone file presentation format, one resource record per line, optionally
+//! preceded by a `$TTL <seconds>` line. Domain names are stored uncompressed and in lower case.
+
+use std::{
+    convert::TryInto,
+    net::{Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use crate::state::record::Record;
+
+/// TTL of the RRsets written without a `$TTL` line
+pub const DEFAULT_TTL: u32 = 300;
+/// The `IN` class
+pub const CLASS_IN: u16 = 1;
+/// Maximum length of a domain name in wire format
+pub const MAX_NAME_LEN: usize = 255;
+/// Maximum number of addresses of an A or AAAA RRset
+pub const MAX_ADDRESSES: usize = 8;
+/// Maximum length of the content of TXT and MX records
+pub const MAX_RRSET_LEN: usize = 512;
+
+/// DNS type code of a record, `None` for address records
+pub fn record_type(record: Record) -> Option<u16> {
+    match record {
+        Record::A => Some(1),
+        Record::Cname => Some(5),
+        Record::Mx => Some(15),
+        Record::Txt => Some(16),
+        Record::Aaaa => Some(28),
+        _ => None,
+    }
+}
+
+/// Maximum length of the content of a DNS record
+pub fn max_content_len(record: Record) -> usize {
+    match record {
+        Record::A => 4 + MAX_ADDRESSES * (2 + 4),
+        Record::Aaaa => 4 + MAX_ADDRESSES * (2 + 16),
+        Record::Cname => 4 + 2 + MAX_NAME_LEN,
+        Record::Txt | Record::Mx => MAX_RRSET_LEN,
+        _ => 0,
+    }
+}
+
+/// Validates an RRset in presentation format and encodes it into the content of a DNS record
+pub fn encode_rrset(record: Record, value: &str) -> Option<Vec<u8>> {
+    let mut lines = value
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .peekable();
+    let ttl = match lines.peek().copied().and_then(|l| l.strip_prefix("$TTL")) {
+        Some(ttl) => {
+            lines.next();
+            ttl.trim().parse::<u32>().ok()?
+        }
+        None => DEFAULT_TTL,
+    };
+
+    let mut rdatas: Vec<Vec<u8>> = Vec::new();
+    let mut content = ttl.to_be_bytes().to_vec();
+    for line in lines {
+        let rdata = encode_rdata(record, line)?;
+        if rdatas.contains(&rdata) {
+            return None;
+        }
+        content.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        content.extend_from_slice(&rdata);
+        rdatas.push(rdata);
+    }
+
+    // RFC 2181: TTLs fit in 31 bits and a CNAME RRset has a single record
+    let valid = ttl <= i32::MAX as u32
+        && !rdatas.is_empty()
+        && (record != Record::Cname || rdatas.len() == 1)
+        && content.len() <= max_content_len(record);
+    valid.then_some(content)
+}
+
+/// Renders the content of a DNS record in the format accepted by `encode_rrset`
+pub fn display_rrset(record: Record, content: &[u8]) -> Option<String> {
+    let (ttl, rdatas) = split_rrset(content)?;
+    let mut lines = Vec::with_capacity(rdatas.len() + 1);
+    if ttl != DEFAULT_TTL {
+        lines.push(format!("$TTL {}", ttl));
+    }
+    for rdata in rdatas {
+        lines.push(rdata_to_string(record, rdata)?);
+    }
+    Some(lines.join("\n"))
+}
+
+/// A set of resource records of the same name and type, as served by a DNS server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RRset {
+    /// Fully qualified name of the records, e.g. `alice.gorb.`
+    pub name: String,
+    pub rtype: u16,
+    pub class: u16,
+    pub ttl: u32,
+    /// RDATA of each resource record, in wire format
+    pub rdata: Vec<Vec<u8>>,
+}
+
+impl RRset {
+    /// Encodes the resource records as in the answer section of a DNS message, without name
+    /// compression
+    pub fn to_wire(&self) -> Option<Vec<u8>> {
+        let name = encode_name(&self.name)?;
+        let mut wire = Vec::new();
+        for rdata in &self.rdata {
+            wire.extend_from_slice(&name);
+            wire.extend_from_slice(&self.rtype.to_be_bytes());
+            wire.extend_from_slice(&self.class.to_be_bytes());
+            wire.extend_from_slice(&self.ttl.to_be_bytes());
+            wire.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+            wire.extend_from_slice(rdata);
+        }
+        Some(wire)
+    }
+
+    /// Renders the resource records as zone file lines
+    pub fn to_presentation(&self, record: Record) -> Option<Vec<String>> {
+        self.rdata
+            .iter()
+            .map(|rdata| {
+                Some(format!(
+                    "{} {} IN {} {}",
+                    self.name,
+                    self.ttl,
+                    record.as_str(),
+                    rdata_to_string(record, rdata)?
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Decodes the content of the DNS record `record` of `domain` (e.g. `alice.gorb`) into an RRset
+pub fn decode_rrset(domain: &str, record: Record, content: &[u8]) -> Option<RRset> {
+    let rtype = record_type(record)?;
+    let name = format!("{}.", domain.trim_end_matches('.').to_ascii_lowercase());
+    encode_name(&name)?;
+    let (ttl, rdatas) = split_rrset(content)?;
+    let mut rdata = Vec::with_capacity(rdatas.len());
+    for data in rdatas {
+        rdata_to_string(record, data)?;
+        rdata.push(data.to_vec());
+    }
+    Some(RRset {
+        name,
+        rtype,
+        class: CLASS_IN,
+        ttl,
+        rdata,
+    })
+}
+
+/// Encodes a domain name in wire format, the trailing dot is optional
+pub fn encode_name(name: &str) -> Option<Vec<u8>> {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    let mut wire = Vec::with_capacity(name.len() + 2);
+    if !name.is_empty() {
+        for label in name.split('.') {
+            let valid = (1..=63).contains(&label.len())
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+                && !label.starts_with('-')
+                && !label.ends_with('-');
+            if !valid {
+                return None;
+            }
+            wire.push(label.len() as u8);
+            wire.extend(label.to_ascii_lowercase().bytes());
+        }
+    }
+    wire.push(0);
+    (wire.len() <= MAX_NAME_LEN).then_some(wire)
+}
+
+/// Renders the RDATA of a resource record in presentation format
+pub fn rdata_to_string(record: Record, rdata: &[u8]) -> Option<String> {
+    match record {
+        Record::A => {
+            let octets: [u8; 4] = rdata.try_into().ok()?;
+            Some(Ipv4Addr::from(octets).to_string())
+        }
+        Record::Aaaa => {
+            let octets: [u8; 16] = rdata.try_into().ok()?;
+            Some(Ipv6Addr::from(octets).to_string())
+        }
+        Record::Cname => decode_full_name(rdata),
+        Record::Mx => {
+            let preference = u16::from_be_bytes(rdata.get(..2)?.try_into().ok()?);
+            Some(format!("{} {}", preference, decode_full_name(&rdata[2..])?))
+        }
+        Record::Txt => decode_character_strings(rdata),
+        _ => None,
+    }
+}
+
+fn encode_rdata(record: Record, value: &str) -> Option<Vec<u8>> {
+    match record {
+        Record::A => Some(Ipv4Addr::from_str(value).ok()?.octets().to_vec()),
+        Record::Aaaa => Some(Ipv6Addr::from_str(value).ok()?.octets().to_vec()),
+        Record::Cname => encode_name(value),
+        Record::Mx => {
+            let (preference, exchange) = value.split_once(char::is_whitespace)?;
+            let mut rdata = preference.parse::<u16>().ok()?.to_be_bytes().to_vec();
+            rdata.extend(encode_name(exchange.trim())?);
+            Some(rdata)
+        }
+        Record::Txt => encode_character_strings(value),
+        _ => None,
+    }
+}
+
+/// Splits the content of a DNS record into its TTL and the RDATA of its resource records
+fn split_rrset(content: &[u8]) -> Option<(u32, Vec<&[u8]>)> {
+    let ttl = u32::from_be_bytes(content.get(..4)?.try_into().ok()?);
+    let mut rest = &content[4..];
+    let mut rdatas = Vec::new();
+    while !rest.is_empty() {
+        let len = u16::from_be_bytes(rest.get(..2)?.try_into().ok()?) as usize;
+        rdatas.push(rest.get(2..2 + len)?);
+        rest = &rest[2 + len..];
+    }
+    Some((ttl, rdatas))
+}
+
+/// Decodes a domain name taking the whole `wire` slice
+fn decode_full_name(wire: &[u8]) -> Option<String> {
+    let mut labels = Vec::new();
+    let mut offset = 0;
+    loop {
+        let len = *wire.get(offset)? as usize;
+        offset += 1;
+        if len == 0 {
+            break;
+        }
+        labels.push(std::str::from_utf8(wire.get(offset..offset + len)?).ok()?);
+        offset += len;
+    }
+    let name = format!("{}.", labels.join("."));
+    // Re-encoding checks the labels and rejects compression pointers and trailing bytes
+    (encode_name(&name)? == wire).then_some(name)
+}
+
+/// Encodes TXT data: quoted strings separated by spaces, or a single unquoted string
+fn encode_character_strings(value: &str) -> Option<Vec<u8>> {
+    let mut rdata = Vec::new();
+    if !value.starts_with('"') {
+        if value.contains(['"', '\\']) {
+            return None;
+        }
+        push_character_string(&mut rdata, value)?;
+        return Some(rdata);
+    }
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => string.push(chars.next()?),
+                        c => string.push(c),
+                    }
+                }
+                push_character_string(&mut rdata, &string)?;
+            }
+            c if c.is_whitespace() => continue,
+            _ => return None,
+        }
+    }
+    Some(rdata)
+}
+
+fn push_character_string(rdata: &mut Vec<u8>, string: &str) -> Option<()> {
+    let len: u8 = string.len().try_into().ok()?;
+    rdata.push(len);
+    rdata.extend_from_slice(string.as_bytes());
+    Some(())
+}
+
+fn decode_character_strings(mut rdata: &[u8]) -> Option<String> {
+    let mut strings = Vec::new();
+    while let Some((len, rest)) = rdata.split_first() {
+        let string = std::str::from_utf8(rest.get(..*len as usize)?).ok()?;
+        strings.push(format!(
+            "\"{}\"",
+            string.replace('\\', "\\\\").replace('"', "\\\"")
+        ));
+        rdata = &rest[*len as usize..];
+    }
+    (!strings.is_empty()).then(|| strings.join(" "))
+}
+
+#[test]
+fn test_dns_rrset() {
+    let content = encode_rrset(Record::A, "$TTL 60\n93.184.216.34\n93.184.216.35").unwrap();
+    let rrset = decode_rrset("Alice.gorb", Record::A, &content).unwrap();
+    assert_eq!(rrset.name, "alice.gorb.");
+    assert_eq!(rrset.ttl, 60);
+    assert_eq!(
+        rrset.rdata,
+        vec![vec![93, 184, 216, 34], vec![93, 184, 216, 35]]
+    );
+    let wire = rrset.to_wire().unwrap();
+    assert_eq!(wire.len(), 2 * (12 + 10 + 4));
+    assert_eq!(
+        &wire[..22],
+        b"\x05alice\x04gorb\x00\x00\x01\x00\x01\x00\x00\x00\x3c\x00\x04"
+    );
+    assert_eq!(
+        rrset.to_presentation(Record::A).unwrap()[0],
+        "alice.gorb. 60 IN A 93.184.216.34"
+    );
+
+    for (record, value, displayed) in [
+        (Record::Aaaa, "2001:DB8::1", "2001:db8::1"),
+        (Record::Cname, "Web.Example.com", "web.example.com."),
+        (
+            Record::Mx,
+            "10 mail.example.com.\n0 .",
+            "10 mail.example.com.\n0 .",
+        ),
+        (Record::Txt, "hello", "\"hello\""),
+        (
+            Record::Txt,
+            r#""v=spf1 -all" "a\"b""#,
+            r#""v=spf1 -all" "a\"b""#,
+        ),
+    ] {
+        let content = encode_rrset(record, value).unwrap();
+        assert_eq!(display_rrset(record, &content).unwrap(), displayed);
+        assert_eq!(encode_rrset(record, displayed).unwrap(), content);
+        assert!(decode_rrset("alice.gorb", record, &content).is_some());
+    }
+
+    assert!(encode_rrset(Record::A, "256.0.0.1").is_none());
+    assert!(encode_rrset(Record::A, "1.1.1.1\n1.1.1.1").is_none());
+    assert!(encode_rrset(Record::A, "").is_none());
+    let addresses = (1..=9).map(|i| format!("10.0.0.{}", i)).collect::<Vec<_>>();
+    assert!(encode_rrset(Record::A, &addresses[..8].join("\n")).is_some());
+    assert!(encode_rrset(Record::A, &addresses.join("\n")).is_none());
+    assert!(encode_rrset(Record::Cname, "a.com\nb.com").is_none());
+    assert!(encode_rrset(Record::Cname, "-bad.com").is_none());
+    assert!(encode_rrset(Record::Mx, "mail.example.com").is_none());
+    assert!(encode_rrset(Record::Txt, &"a".repeat(256)).is_none());
+    assert!(encode_rrset(Record::Txt, "\"unterminated").is_none());
+    assert!(encode_rrset(Record::Sol, "1.1.1.1").is_none());
+}
@@ -40,6 +40,37 @@ impl PrintProgramError for Error {
             Error::DeprecatedInstruction => {
                 msg!("Error: The instruction is deprecated")
             }
+            Error::DataTypeMismatch => msg!("Error: Data type mismatch"),
+            Error::PermissionDenied => msg!("Error: Permission denied"),
+            Error::NameNotExpired => {
+                msg!("Error: The domain name has not expired")
+            }
+            Error::AuctionInProgress => {
+                msg!("Error: The auction is still in progress")
+            }
+            Error::AuctionEnded => msg!("Error: The auction has ended"),
+            Error::BidTooLow => msg!("Error: The bid is too low"),
+            Error::NameTooLong => msg!("Error: The domain name is too long"),
+            Error::CampaignExhausted => {
+                msg!("Error: The campaign has reached its maximum number of claims")
+            }
+            Error::NftAlreadyClaimed => {
+                msg!("Error: The NFT has already been used to claim a domain name")
+            }
+            Error::InvalidVoucher => msg!("Error: The voucher is invalid"),
+            Error::VoucherExpired => msg!("Error: The voucher has expired"),
+            Error::VoucherAlreadyRedeemed => {
+                msg!("Error: The voucher has already been redeemed")
+            }
+            Error::InvalidMerkleProof => msg!("Error: The Merkle proof is invalid"),
+            Error::AlreadyClaimed => msg!("Error: The name has already been claimed"),
+            Error::TldDisabled => msg!("Error: The TLD is disabled"),
+            Error::NameExpired => msg!("Error: The domain name registration has expired"),
+            Error::InvalidRecord => msg!("Error: The record value is invalid"),
+            Error::InvalidSignature => msg!("Error: The signature is invalid"),
+            Error::ReleasePremiumActive => {
+                msg!("Error: The domain name is being released with a premium")
+            }
         }
     }
 }
@@ -12,6 +12,46 @@ pub enum Error {
     AlreadyRegistered,
     #[error("The instruction is deprecated")]
     DeprecatedInstruction,
+    #[error("Data type mismatch")]
+    DataTypeMismatch,
+    #[error("Permission denied")]
+    PermissionDenied,
+    #[error("The domain name has not expired")]
+    NameNotExpired,
+    #[error("The auction is still in progress")]
+    AuctionInProgress,
+    #[error("The auction has ended")]
+    AuctionEnded,
+    #[error("The bid is too low")]
+    BidTooLow,
+    #[error("The domain name is too long")]
+    NameTooLong,
+    #[error("The campaign has reached its maximum number of claims")]
+    CampaignExhausted,
+    #[error("The NFT has already been used to claim a domain name")]
+    NftAlreadyClaimed,
+    #[error("The voucher is invalid")]
+    InvalidVoucher,
+    #[error("The voucher has expired")]
+    VoucherExpired,
+    #[error("The voucher has already been redeemed")]
+    VoucherAlreadyRedeemed,
+    #[error("The Merkle proof is invalid")]
+    InvalidMerkleProof,
+    #[error("The name has already been claimed")]
+    AlreadyClaimed,
+    #[error("The TLD is disabled")]
+    TldDisabled,
+    #[error("The domain name registration has expired")]
+    NameExpired,
+    #[error("The record value is invalid")]
+    InvalidRecord,
+    #[error("The signature is invalid")]
+    InvalidSignature,
+    #[error("The domain name is being released with a premium")]
+    ReleasePremiumActive,
+    #[error("The referrer fee is too high for auctions")]
+    ReferrerFeeTooHigh,
 }
 
 impl From<crate::Error> for ProgramError {
@@ -0,0 +1,129 @@
+//! Events logged by the registrar with `sol_log_data`.
+//!
+//! Each event is logged as a single data field made of `EVENT_DISCRIMINATOR` followed by the Borsh
+//! serialization of the `Event`. Off-chain consumers base64-decode the `Program data:` log lines
+//! of the registrar and pass them to `Event::decode`.
+//!
+//! New variants must be appended to the enum to keep existing events decodable.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+use crate::state::record::Record;
+
+/// Prefix of the data logged for every event
+pub const EVENT_DISCRIMINATOR: [u8; 8] = *b"sns_evnt";
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum Event {
+    /// A domain name was created
+    Registered {
+        name_account: Pubkey,
+        owner: Pubkey,
+        root_domain: Pubkey,
+        name: String,
+        /// The mint of the payment, the default key for free registrations
+        mint: Pubkey,
+        /// Amount paid, including the referrer fees
+        amount: u64,
+        /// Expiry of the registration, `None` for names which never expire
+        expiry: Option<i64>,
+    },
+    /// The reverse lookup account of a domain name was created
+    ReverseCreated {
+        name_account: Pubkey,
+        reverse_lookup: Pubkey,
+        name: String,
+    },
+    /// The metadata URL stored in a reverse lookup account changed
+    MetadataUpdated {
+        reverse_lookup: Pubkey,
+        metadata_url: Option<String>,
+    },
+    /// A domain name and its reverse lookup account were deleted
+    Deleted {
+        name_account: Pubkey,
+        reverse_lookup: Pubkey,
+    },
+    /// A referrer received a share of a registration payment
+    ReferrerPaid {
+        name_account: Pubkey,
+        /// The token account of the referrer
+        referrer: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+    },
+    /// A wallet picked the domain name it is displayed with
+    PrimaryDomainSet {
+        wallet: Pubkey,
+        name_account: Pubkey,
+    },
+    /// A record of a domain name was created or updated
+    RecordSet {
+        domain: Pubkey,
+        record: Record,
+        content: Vec<u8>,
+    },
+    /// A record of a domain name was deleted
+    RecordDeleted { domain: Pubkey, record: Record },
+    /// The registration of a domain name was extended by its owner
+    Renewed {
+        name_account: Pubkey,
+        mint: Pubkey,
+        /// Amount paid
+        amount: u64,
+        /// New expiry of the registration
+        expiry: i64,
+    },
+    /// The owner of a domain name changed through the registrar, the owner of a tokenized name
+    /// is its NFT record
+    OwnerChanged { name_account: Pubkey, owner: Pubkey },
+    /// An auction of a domain name was started
+    AuctionStarted {
+        name_account: Pubkey,
+        quote_mint: Pubkey,
+        reserve_price: u64,
+        end_time: i64,
+    },
+    /// A bid became the highest bid of an auction
+    BidPlaced {
+        name_account: Pubkey,
+        bidder: Pubkey,
+        amount: u64,
+        /// End of the auction, extended by late bids
+        end_time: i64,
+    },
+    /// A TLD was created or its configuration updated
+    TldConfigured {
+        root_domain: Pubkey,
+        vault_owner: Pubkey,
+        enabled: bool,
+    },
+    /// The address of a record of a domain name was verified with a signature
+    RecordVerified { domain: Pubkey, record: Record },
+}
+
+impl Event {
+    pub fn emit(&self) {
+        let data = [EVENT_DISCRIMINATOR.as_ref(), &self.try_to_vec().unwrap()].concat();
+        sol_log_data(&[&data]);
+    }
+
+    /// Decodes the data of a `Program data:` log line, returns `None` for data which is not an event
+    pub fn decode(data: &[u8]) -> Option<Event> {
+        let payload = data.strip_prefix(EVENT_DISCRIMINATOR.as_ref())?;
+        Event::try_from_slice(payload).ok()
+    }
+}
+
+#[test]
+fn test_decode() {
+    let event = Event::MetadataUpdated {
+        reverse_lookup: Pubkey::new_unique(),
+        metadata_url: Some("https://example.com/alice.json".to_owned()),
+    };
+    let data = [EVENT_DISCRIMINATOR.as_ref(), &event.try_to_vec().unwrap()].concat();
+    assert_eq!(Event::decode(&data), Some(event));
+    assert_eq!(Event::decode(&data[1..]), None);
+    assert_eq!(Event::decode(&data[..data.len() - 1]), None);
+}
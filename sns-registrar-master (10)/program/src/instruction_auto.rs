@@ -1,4 +1,10 @@
-use crate::processor::{create, create_reverse, create_split_v2, create_with_nft, delete, update_metadata};
+use crate::processor::{
+    claim_name, configure_nft_campaign, configure_tld, create, create_batch, create_claim_tree,
+    create_reverse, create_split_v2, create_split_v3, create_tld, create_with_nft,
+    create_with_voucher, delete, place_bid, preview_release_premium, quote_price, reclaim_name,
+    redeem_domain, renew, resolve, set_primary_domain, settle_auction, start_auction,
+    tokenize_domain, update_metadata, update_record, verify_eth_record,
+};
 use bonfida_utils::InstructionsAccount;
 use borsh::{BorshDeserialize, BorshSerialize};
 use num_derive::FromPrimitive;
@@ -7,55 +13,90 @@ use solana_program::{instruction::Instruction, pubkey::Pubkey};
 pub enum ProgramInstruction {
     /// Create a reverse lookup registry for a name registry
     ///
-    /// | Index | Writable | Signer | Description                                     |
-    /// | --------------------------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The name service program account                |
-    /// | 1     | ❌        | ❌      | The root domain account                         |
-    /// | 2     | ✅        | ❌      | The reverse lookup account                      |
-    /// | 3     | ❌        | ❌      | The system program account                      |
-    /// | 4     | ❌        | ❌      | The central state account                       |
-    /// | 5     | ✅        | ✅      | The fee payer account                           |
-    /// | 6     | ❌        | ❌      |                                                 |
-    /// | 7     | ✅        | ❌      | The optional parent name account for subdomains |
-    /// | 8     | ✅        | ✅      | The optional parent name owner                  |
+    /// | Index | Writable | Signer | Description                                                                              |
+    /// | -------------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The name service program account                                                         |
+    /// | 1     | ❌        | ❌      | The root domain account                                                                  |
+    /// | 2     | ✅        | ❌      | The reverse lookup account                                                               |
+    /// | 3     | ❌        | ❌      | The system program account                                                               |
+    /// | 4     | ❌        | ❌      | The central state account                                                                |
+    /// | 5     | ✅        | ✅      | The fee payer account                                                                    |
+    /// | 6     | ❌        | ❌      |                                                                                          |
+    /// | 7     | ❌        | ❌      | The TLD account of the root domain, required when the root domain is not the default one |
+    /// | 8     | ✅        | ❌      | The optional parent name account for subdomains                                          |
+    /// | 9     | ✅        | ✅      | The optional parent name owner                                                           |
     CreateReverse = 12,
     /// Create a domain name and buy the ownership of a domain name
     ///
-    /// | Index | Writable | Signer | Description                                                         |
-    /// | ----------------------------------------------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The naming service program ID                                       |
-    /// | 1     | ❌        | ❌      | The root domain account                                             |
-    /// | 2     | ✅        | ❌      | The name account                                                    |
-    /// | 3     | ✅        | ❌      | The reverse look up account                                         |
-    /// | 4     | ❌        | ❌      | The system program account                                          |
-    /// | 5     | ❌        | ❌      | The central state account                                           |
-    /// | 6     | ✅        | ✅      | The buyer account                                                   |
-    /// | 7     | ✅        | ❌      | The buyer token account                                             |
-    /// | 8     | ❌        | ❌      | The Pyth mapping account                                            |
-    /// | 9     | ❌        | ❌      | The Pyth product account                                            |
-    /// | 10    | ❌        | ❌      | The Pyth price account                                              |
-    /// | 11    | ✅        | ❌      | The vault account                                                   |
-    /// | 12    | ❌        | ❌      | The SPL token program                                               |
-    /// | 13    | ❌        | ❌      | The rent sysvar account                                             |
-    /// | 14    | ❌        | ❌      | The state auction account                                           |
-    /// | 15    | ✅        | ❌      | The *optional* referrer token account to receive a portion of fees. |
+    /// | Index | Writable | Signer | Description                                                                                                                                       |
+    /// | ----------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                                                                                                     |
+    /// | 1     | ❌        | ❌      | The root domain account                                                                                                                           |
+    /// | 2     | ✅        | ❌      | The name account                                                                                                                                  |
+    /// | 3     | ✅        | ❌      | The reverse look up account                                                                                                                       |
+    /// | 4     | ❌        | ❌      | The system program account                                                                                                                        |
+    /// | 5     | ❌        | ❌      | The central state account                                                                                                                         |
+    /// | 6     | ✅        | ✅      | The buyer account                                                                                                                                 |
+    /// | 7     | ✅        | ❌      | The buyer token account                                                                                                                           |
+    /// | 8     | ❌        | ❌      | The Pyth mapping account                                                                                                                          |
+    /// | 9     | ❌        | ❌      | The Pyth product account                                                                                                                          |
+    /// | 10    | ❌        | ❌      | The Pyth price account                                                                                                                            |
+    /// | 11    | ✅        | ❌      | The vault account                                                                                                                                 |
+    /// | 12    | ❌        | ❌      | The SPL token program                                                                                                                             |
+    /// | 13    | ❌        | ❌      | The rent sysvar account                                                                                                                           |
+    /// | 14    | ✅        | ❌      | The state auction account                                                                                                                         |
+    /// | 15    | ❌        | ❌      | The TLD account of the root domain, required when registering under another root than the default one                                             |
+    /// | 16    | ✅        | ❌      | The *optional* referrer token account to receive a portion of fees.                                                                               |
+    /// | 17    | ✅        | ❌      | The registration record account, holds the expiry of the name. Passed after the optional accounts to keep the account indices of earlier clients. |
     Create = 13,
     /// Update metadata URL for an existing domain
     ///
-    /// | Index | Writable | Signer | Description                                     |
-    /// | --------------------------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The name service program account                |
-    /// | 1     | ❌        | ❌      | The root domain account                         |
-    /// | 2     | ✅        | ❌      | The reverse lookup account                      |
-    /// | 3     | ❌        | ❌      | The system program account                      |
-    /// | 4     | ❌        | ❌      | The central state account                       |
-    /// | 5     | ✅        | ✅      | The domain owner (signer)                       |
-    /// | 6     | ❌        | ❌      | The rent sysvar account                         |
+    /// | Index | Writable | Signer | Description                                                                              |
+    /// | -------------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The name service program account                                                         |
+    /// | 1     | ❌        | ❌      | The root domain account                                                                  |
+    /// | 2     | ✅        | ❌      | The reverse lookup account                                                               |
+    /// | 3     | ❌        | ❌      | The system program account                                                               |
+    /// | 4     | ❌        | ❌      | The central state account                                                                |
+    /// | 5     | ✅        | ✅      | The domain owner (signer)                                                                |
+    /// | 6     | ❌        | ❌      | The rent sysvar account                                                                  |
+    /// | 7     | ❌        | ❌      | The TLD account of the root domain, required when the root domain is not the default one |
     UpdateMetadata = 14,
-    /// Deprecated instruction
-    _Claim,
-    /// Deprecated instruction
-    _EndAuction,
+    /// Start an English auction for a domain name
+    ///
+    /// | Index | Writable | Signer | Description                                                                          |
+    /// | ---------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                                        |
+    /// | 1     | ❌        | ❌      | The root domain account                                                              |
+    /// | 2     | ✅        | ❌      | The name account                                                                     |
+    /// | 3     | ✅        | ❌      | The reverse look up account                                                          |
+    /// | 4     | ❌        | ❌      | The system program account                                                           |
+    /// | 5     | ❌        | ❌      | The central state account                                                            |
+    /// | 6     | ✅        | ❌      | The auction account                                                                  |
+    /// | 7     | ✅        | ❌      | The escrow token account of the auction                                              |
+    /// | 8     | ❌        | ❌      | The mint in which bids are denominated                                               |
+    /// | 9     | ❌        | ❌      | The SPL token program                                                                |
+    /// | 10    | ❌        | ❌      | The rent sysvar account                                                              |
+    /// | 11    | ✅        | ✅      | The fee payer account, has to be the admin unless the name has expired               |
+    /// | 12    | ✅        | ❌      | The *optional* registration record of an expired name                                |
+    /// | 13    | ✅        | ❌      | The *optional* owner of an expired name, receives the rent of the reclaimed accounts |
+    StartAuction,
+    /// Place a bid on a domain name auction
+    ///
+    /// | Index | Writable | Signer | Description                                                                                 |
+    /// | ----------------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The auction account                                                                         |
+    /// | 1     | ✅        | ❌      | The escrow token account of the auction                                                     |
+    /// | 2     | ✅        | ✅      | The bidder account, pays for the associated token account of the previous highest bidder    |
+    /// | 3     | ✅        | ❌      | The bidder token account                                                                    |
+    /// | 4     | ❌        | ❌      | The previous highest bidder, ignored for the first bid of an auction                        |
+    /// | 5     | ✅        | ❌      | The associated token account of the previous highest bidder, refunded with the previous bid |
+    /// | 6     | ❌        | ❌      | The mint in which bids are denominated                                                      |
+    /// | 7     | ❌        | ❌      | The system program account                                                                  |
+    /// | 8     | ❌        | ❌      | The SPL token program                                                                       |
+    /// | 9     | ❌        | ❌      | The SPL associated token account program                                                    |
+    /// | 10    | ❌        | ❌      | The *optional* referrer token account to receive a portion of the proceeds.                 |
+    PlaceBid,
     /// Delete a domain and clean up related accounts
     ///
     /// | Index | Writable | Signer | Description |
@@ -70,31 +111,324 @@ pub enum ProgramInstruction {
     /// | 7     | ❌        | ✅      |             |
     /// | 8     | ✅        | ❌      |             |
     Delete,
-    /// Create a domain name with a wolf
-    ///
-    /// | Index | Writable | Signer | Description                             |
-    /// | ------------------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The naming service program ID           |
-    /// | 1     | ❌        | ❌      | The root domain account                 |
-    /// | 2     | ✅        | ❌      | The name account                        |
-    /// | 3     | ✅        | ❌      | The reverse look up account             |
-    /// | 4     | ❌        | ❌      | The system program account              |
-    /// | 5     | ❌        | ❌      | The central state account               |
-    /// | 6     | ✅        | ✅      | The buyer account                       |
-    /// | 7     | ✅        | ❌      | The buyer token account                 |
-    /// | 8     | ✅        | ❌      | The NFT metadata account                |
-    /// | 9     | ✅        | ❌      | The NFT mint account                    |
-    /// | 10    | ✅        | ❌      | The NFT master edition account          |
-    /// | 11    | ✅        | ❌      | The NFT collection account              |
-    /// | 12    | ❌        | ❌      | The SPL token program                   |
-    /// | 13    | ❌        | ❌      | The rent sysvar account                 |
-    /// | 14    | ❌        | ❌      | The state auction account               |
-    /// | 15    | ❌        | ❌      | Metaplex token metadata program account |
+    /// Create a domain name with an NFT of a campaign collection
+    ///
+    /// | Index | Writable | Signer | Description                                                                       |
+    /// | ------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                                     |
+    /// | 1     | ❌        | ❌      | The root domain account                                                           |
+    /// | 2     | ✅        | ❌      | The name account                                                                  |
+    /// | 3     | ✅        | ❌      | The reverse look up account                                                       |
+    /// | 4     | ❌        | ❌      | The system program account                                                        |
+    /// | 5     | ❌        | ❌      | The central state account                                                         |
+    /// | 6     | ✅        | ✅      | The buyer account                                                                 |
+    /// | 7     | ✅        | ❌      | The buyer token account                                                           |
+    /// | 8     | ✅        | ❌      | The NFT metadata account                                                          |
+    /// | 9     | ✅        | ❌      | The NFT mint account                                                              |
+    /// | 10    | ✅        | ❌      | The NFT master edition account                                                    |
+    /// | 11    | ✅        | ❌      | The NFT collection metadata account                                               |
+    /// | 12    | ❌        | ❌      | The SPL token program                                                             |
+    /// | 13    | ❌        | ❌      | The rent sysvar account                                                           |
+    /// | 14    | ✅        | ❌      | The state auction account, closed if it holds a release whose premium has decayed |
+    /// | 15    | ❌        | ❌      | Metaplex token metadata program account                                           |
+    /// | 16    | ✅        | ❌      | The NFT campaign account of the collection                                        |
+    /// | 17    | ✅        | ❌      | The *optional* claim account of the NFT, required by campaigns locking NFTs       |
     CreateWithNft,
-    /// Deprecated instruction
-    _CloseAuctionAccount,
+    /// Settle a domain name auction and create the name for the highest bidder
+    ///
+    /// | Index | Writable | Signer | Description                                                                |
+    /// | ------------------------------------------------------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                              |
+    /// | 1     | ❌        | ❌      | The root domain account                                                    |
+    /// | 2     | ✅        | ❌      | The name account                                                           |
+    /// | 3     | ✅        | ❌      | The reverse look up account                                                |
+    /// | 4     | ❌        | ❌      | The system program account                                                 |
+    /// | 5     | ❌        | ❌      | The central state account                                                  |
+    /// | 6     | ✅        | ❌      | The auction account                                                        |
+    /// | 7     | ✅        | ❌      | The escrow token account of the auction                                    |
+    /// | 8     | ❌        | ❌      | The highest bidder, owner of the created name                              |
+    /// | 9     | ✅        | ❌      | The vault account                                                          |
+    /// | 10    | ❌        | ❌      | The SPL token program                                                      |
+    /// | 11    | ❌        | ❌      | The rent sysvar account                                                    |
+    /// | 12    | ✅        | ✅      | The fee payer account, pays for the name and reverse lookup accounts       |
+    /// | 13    | ✅        | ❌      | The account which paid for the auction accounts                            |
+    /// | 14    | ✅        | ❌      | The registration record account, auctioned names are registered for a year |
+    /// | 15    | ✅        | ❌      | The referrer token account attached to the highest bid, if any             |
+    SettleAuction,
     CreateSplit,
     CreateSplitV2,
+    /// Reclaim an expired domain name and release it with a declining premium
+    ///
+    /// | Index | Writable | Signer | Description                                                                |
+    /// | ------------------------------------------------------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                              |
+    /// | 1     | ❌        | ❌      | The root domain account                                                    |
+    /// | 2     | ✅        | ❌      | The expired name account                                                   |
+    /// | 3     | ✅        | ❌      | The reverse look up account                                                |
+    /// | 4     | ❌        | ❌      | The system program account                                                 |
+    /// | 5     | ❌        | ❌      | The central state account                                                  |
+    /// | 6     | ✅        | ❌      | The registration record of the name                                        |
+    /// | 7     | ✅        | ❌      | The owner of the expired name, receives the rent of the reclaimed accounts |
+    /// | 8     | ✅        | ❌      | The state account, holds the release premium of the name                   |
+    /// | 9     | ✅        | ✅      | The fee payer account                                                      |
+    ReclaimName,
+    /// Transfer a domain name to the registrar and mint a 1/1 NFT controlling it
+    ///
+    /// | Index | Writable | Signer | Description                                                                       |
+    /// | ------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                                     |
+    /// | 1     | ✅        | ❌      | The name account                                                                  |
+    /// | 2     | ❌        | ✅      | The owner of the name account                                                     |
+    /// | 3     | ✅        | ❌      | The NFT record account                                                            |
+    /// | 4     | ✅        | ✅      | A new keypair, the mint of the NFT                                                |
+    /// | 5     | ✅        | ❌      | The associated token account of the owner receiving the NFT                       |
+    /// | 6     | ✅        | ❌      | The NFT metadata account                                                          |
+    /// | 7     | ✅        | ❌      | The NFT master edition account, becomes the mint and freeze authority of the mint |
+    /// | 8     | ❌        | ❌      | The central state account                                                         |
+    /// | 9     | ❌        | ❌      | The system program account                                                        |
+    /// | 10    | ❌        | ❌      | The SPL token program                                                             |
+    /// | 11    | ❌        | ❌      | The SPL associated token account program                                          |
+    /// | 12    | ❌        | ❌      | Metaplex token metadata program account                                           |
+    /// | 13    | ❌        | ❌      | The rent sysvar account                                                           |
+    /// | 14    | ✅        | ✅      | The fee payer account                                                             |
+    TokenizeDomain,
+    /// Burn the NFT of a tokenized domain name and return the name to the holder
+    ///
+    /// | Index | Writable | Signer | Description                                                                            |
+    /// | ------------------------------------------------------------------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                                          |
+    /// | 1     | ✅        | ❌      | The tokenized name account                                                             |
+    /// | 2     | ✅        | ✅      | The NFT holder, becomes the owner of the name account and receives the rent of the NFT |
+    /// | 3     | ✅        | ❌      | The token account of the holder containing the NFT                                     |
+    /// | 4     | ✅        | ❌      | The NFT record account                                                                 |
+    /// | 5     | ✅        | ❌      | The NFT mint account                                                                   |
+    /// | 6     | ✅        | ❌      | The NFT metadata account                                                               |
+    /// | 7     | ✅        | ❌      | The NFT master edition account                                                         |
+    /// | 8     | ❌        | ❌      | The SPL token program                                                                  |
+    /// | 9     | ❌        | ❌      | Metaplex token metadata program account                                                |
+    RedeemDomain,
+    /// Create or update an NFT-gated registration campaign
+    ///
+    /// | Index | Writable | Signer | Description                         |
+    /// | --------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The NFT campaign account            |
+    /// | 1     | ❌        | ❌      | The mint of the verified collection |
+    /// | 2     | ❌        | ❌      | The system program account          |
+    /// | 3     | ✅        | ✅      | The admin account                   |
+    ConfigureNftCampaign,
+    /// Create a domain name with a voucher signed by the voucher authority
+    ///
+    /// | Index | Writable | Signer | Description                                                                                                                                       |
+    /// | ----------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                                                                                                     |
+    /// | 1     | ❌        | ❌      | The root domain account                                                                                                                           |
+    /// | 2     | ✅        | ❌      | The name account                                                                                                                                  |
+    /// | 3     | ✅        | ❌      | The reverse look up account                                                                                                                       |
+    /// | 4     | ❌        | ❌      | The system program account                                                                                                                        |
+    /// | 5     | ❌        | ❌      | The central state account                                                                                                                         |
+    /// | 6     | ✅        | ✅      | The buyer account                                                                                                                                 |
+    /// | 7     | ❌        | ❌      | The registered domain owner, has to be the beneficiary of the voucher                                                                             |
+    /// | 8     | ✅        | ✅      | The solana fee payer account                                                                                                                      |
+    /// | 9     | ✅        | ❌      | The buyer token account                                                                                                                           |
+    /// | 10    | ❌        | ❌      | The Pyth feed account                                                                                                                             |
+    /// | 11    | ✅        | ❌      | The vault account                                                                                                                                 |
+    /// | 12    | ❌        | ❌      | The SPL token program                                                                                                                             |
+    /// | 13    | ❌        | ❌      | The rent sysvar account                                                                                                                           |
+    /// | 14    | ✅        | ❌      | The state auction account, holds the release premium of recently expired names                                                                    |
+    /// | 15    | ❌        | ❌      | The instructions sysvar account                                                                                                                   |
+    /// | 16    | ✅        | ❌      | The account marking the voucher as redeemed                                                                                                       |
+    /// | 17    | ❌        | ❌      | The TLD account of the root domain, required when registering under another root than the default one                                             |
+    /// | 18    | ✅        | ❌      | The *optional* referrer token account to receive a portion of fees.                                                                               |
+    /// | 19    | ✅        | ❌      | The registration record account, holds the expiry of the name. Passed after the optional accounts to keep the account indices of earlier clients. |
+    CreateWithVoucher,
+    /// Publish a Merkle allowlist of domain names that can be claimed for free
+    ///
+    /// | Index | Writable | Signer | Description                |
+    /// | ------------------------------------------------------ |
+    /// | 0     | ✅        | ❌      | The claim tree account     |
+    /// | 1     | ❌        | ❌      | The system program account |
+    /// | 2     | ✅        | ✅      | The admin account          |
+    CreateClaimTree,
+    /// Claim a domain name from a Merkle allowlist
+    ///
+    /// | Index | Writable | Signer | Description                                                                          |
+    /// | ---------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                                        |
+    /// | 1     | ❌        | ❌      | The root domain account                                                              |
+    /// | 2     | ✅        | ❌      | The name account                                                                     |
+    /// | 3     | ✅        | ❌      | The reverse look up account                                                          |
+    /// | 4     | ❌        | ❌      | The system program account                                                           |
+    /// | 5     | ❌        | ❌      | The central state account                                                            |
+    /// | 6     | ✅        | ❌      | The claim tree account                                                               |
+    /// | 7     | ❌        | ❌      | The wallet of the allowlist leaf, owner of the created name                          |
+    /// | 8     | ✅        | ❌      | The state auction account, the name must not be auctioned or released with a premium |
+    /// | 9     | ❌        | ❌      | The rent sysvar account                                                              |
+    /// | 10    | ✅        | ✅      | The fee payer account                                                                |
+    /// | 11    | ✅        | ❌      | The registration record account, claimed names are registered for a year             |
+    ClaimName,
+    /// Register or update a top level domain
+    ///
+    /// | Index | Writable | Signer | Description                                                  |
+    /// | ---------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The TLD account                                              |
+    /// | 1     | ❌        | ❌      | The root name account of the TLD, owned by the central state |
+    /// | 2     | ❌        | ❌      | The system program account                                   |
+    /// | 3     | ✅        | ✅      | The admin account                                            |
+    ConfigureTld,
+    /// Create the root name account of a new top level domain and register it
+    ///
+    /// | Index | Writable | Signer | Description                                          |
+    /// | -------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                        |
+    /// | 1     | ✅        | ❌      | The root name account of the TLD                     |
+    /// | 2     | ✅        | ❌      | The reverse look up account of the root name account |
+    /// | 3     | ✅        | ❌      | The TLD account                                      |
+    /// | 4     | ❌        | ❌      | The system program account                           |
+    /// | 5     | ❌        | ❌      | The central state account                            |
+    /// | 6     | ❌        | ❌      | The rent sysvar account                              |
+    /// | 7     | ✅        | ✅      | The admin account                                    |
+    CreateTld,
+    /// Quote the price of a domain name registration without registering it
+    ///
+    /// | Index | Writable | Signer | Description                                                                                           |
+    /// | --------------------------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The root domain account, the default root domain or the root of an enabled TLD                        |
+    /// | 1     | ❌        | ❌      | The state auction account, holds the release premium of recently expired names                        |
+    /// | 2     | ❌        | ❌      | The Pyth feed account of the payment mint                                                             |
+    /// | 3     | ❌        | ❌      | The TLD account of the root domain, required when registering under another root than the default one |
+    QuotePrice,
+    /// Resolve a domain name or a subdomain to its effective owner
+    ///
+    /// | Index | Writable | Signer | Description                                                                 |
+    /// | ------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The name account to resolve                                                 |
+    /// | 1     | ❌        | ❌      | The domain name account, the same account as `name` when resolving a domain |
+    /// | 2     | ❌        | ❌      | The root domain account of the domain                                       |
+    /// | 3     | ❌        | ❌      | The registration record account of the domain                               |
+    /// | 4     | ❌        | ❌      | The NFT record account of the name, required when the name is tokenized     |
+    /// | 5     | ❌        | ❌      | The token account holding the NFT, required when the name is tokenized      |
+    /// | 6     | ❌        | ❌      | The SOL record account of the name, ignored when it does not exist          |
+    Resolve,
+    /// Register several domain names with a single oracle read and token transfer
+    ///
+    /// | Index | Writable | Signer | Description                                                                                                        |
+    /// | ---------------------------------------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                                                                      |
+    /// | 1     | ❌        | ❌      | The root domain account, the default root domain or the root of an enabled TLD                                     |
+    /// | 2     | ❌        | ❌      | The system program account                                                                                         |
+    /// | 3     | ❌        | ❌      | The central state account                                                                                          |
+    /// | 4     | ✅        | ✅      | The buyer account                                                                                                  |
+    /// | 5     | ❌        | ❌      | The registered domains owner                                                                                       |
+    /// | 6     | ✅        | ✅      | The solana fee payer account                                                                                       |
+    /// | 7     | ✅        | ❌      | The buyer token account                                                                                            |
+    /// | 8     | ❌        | ❌      | The Pyth feed account                                                                                              |
+    /// | 9     | ✅        | ❌      | The vault account                                                                                                  |
+    /// | 10    | ❌        | ❌      | The SPL token program                                                                                              |
+    /// | 11    | ❌        | ❌      | The rent sysvar account                                                                                            |
+    /// | 12    | ❌        | ❌      | The TLD account of the root domain, required when registering under another root than the default one              |
+    /// | 13    | ✅        | ❌      | The name, reverse look up, state auction and registration record accounts of each entry                            |
+    /// | 14    | ✅        | ❌      | The *optional* referrer token account to receive a portion of fees. The token account owner has to be whitelisted. |
+    CreateBatch,
+    /// Set the domain name displayed for a wallet
+    ///
+    /// | Index | Writable | Signer | Description                                         |
+    /// | ------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The primary domain account of the wallet            |
+    /// | 1     | ❌        | ❌      | The name account of the domain, owned by the wallet |
+    /// | 2     | ✅        | ✅      | The wallet                                          |
+    /// | 3     | ❌        | ❌      | The system program account                          |
+    SetPrimaryDomain,
+    /// Create an address record under a domain name
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                          |
+    /// | 1     | ❌        | ❌      | The system program account                                             |
+    /// | 2     | ❌        | ❌      | The central state account                                              |
+    /// | 3     | ❌        | ❌      | The domain name account                                                |
+    /// | 4     | ✅        | ❌      | The record account                                                     |
+    /// | 5     | ❌        | ✅      | The owner of the domain name                                           |
+    /// | 6     | ✅        | ✅      | The fee payer account                                                  |
+    /// | 7     | ❌        | ✅      | The address of a SOL record, signing to prove its right of association |
+    CreateRecord,
+    /// Update an address record of a domain name
+    ///
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                          |
+    /// | 1     | ❌        | ❌      | The central state account                                              |
+    /// | 2     | ❌        | ❌      | The domain name account                                                |
+    /// | 3     | ✅        | ❌      | The record account                                                     |
+    /// | 4     | ❌        | ✅      | The owner of the domain name                                           |
+    /// | 5     | ❌        | ✅      | The address of a SOL record, signing to prove its right of association |
+    UpdateRecord,
+    /// Delete an address record of a domain name
+    ///
+    /// | Index | Writable | Signer | Description                                          |
+    /// | -------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                        |
+    /// | 1     | ❌        | ❌      | The central state account                            |
+    /// | 2     | ❌        | ❌      | The domain name account                              |
+    /// | 3     | ✅        | ❌      | The record account                                   |
+    /// | 4     | ❌        | ✅      | The owner of the domain name                         |
+    /// | 5     | ✅        | ❌      | The account receiving the rent of the record account |
+    DeleteRecord,
+    /// Verify the right of association of an ETH record with a `personal_sign` signature
+    ///
+    /// | Index | Writable | Signer | Description                   |
+    /// | --------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID |
+    /// | 1     | ❌        | ❌      | The central state account     |
+    /// | 2     | ❌        | ❌      | The domain name account       |
+    /// | 3     | ✅        | ❌      | The ETH record account        |
+    VerifyEthRecord,
+    /// Preview the premium of a domain name released from expiry
+    ///
+    /// | Index | Writable | Signer | Description                           |
+    /// | ----------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The root domain account of the name   |
+    /// | 1     | ❌        | ❌      | The state auction account of the name |
+    PreviewReleasePremium,
+    /// Create a domain name for a registration term of several years
+    ///
+    /// | Index | Writable | Signer | Description                                                                                                                                       |
+    /// | ----------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The naming service program ID                                                                                                                     |
+    /// | 1     | ❌        | ❌      | The root domain account, the default root domain or the root of an enabled TLD                                                                    |
+    /// | 2     | ✅        | ❌      | The name account                                                                                                                                  |
+    /// | 3     | ✅        | ❌      | The reverse look up account                                                                                                                       |
+    /// | 4     | ❌        | ❌      | The system program account                                                                                                                        |
+    /// | 5     | ❌        | ❌      | The central state account                                                                                                                         |
+    /// | 6     | ✅        | ✅      | The buyer account                                                                                                                                 |
+    /// | 7     | ❌        | ❌      | The registered domain owner                                                                                                                       |
+    /// | 8     | ✅        | ✅      | The solana fee payer account                                                                                                                      |
+    /// | 9     | ✅        | ❌      | The buyer token account                                                                                                                           |
+    /// | 10    | ❌        | ❌      | The Pyth feed account                                                                                                                             |
+    /// | 11    | ✅        | ❌      | The vault account                                                                                                                                 |
+    /// | 12    | ❌        | ❌      | The SPL token program                                                                                                                             |
+    /// | 13    | ❌        | ❌      | The rent sysvar account                                                                                                                           |
+    /// | 14    | ✅        | ❌      | The state auction account, holds the release premium of recently expired names                                                                    |
+    /// | 15    | ❌        | ❌      | The TLD account of the root domain, required when registering under another root than the default one                                             |
+    /// | 16    | ✅        | ❌      | The *optional* referrer token account to receive a portion of fees. The token account owner has to be whitelisted.                                |
+    /// | 17    | ✅        | ❌      | The registration record account, holds the expiry of the name. Passed after the optional accounts to keep the account indices of earlier clients. |
+    CreateSplitV3,
+    /// Extend the registration of a domain name
+    ///
+    /// | Index | Writable | Signer | Description                                                                                        |
+    /// | ------------------------------------------------------------------------------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The root domain account, the default root domain or the root of an enabled TLD                     |
+    /// | 1     | ❌        | ❌      | The name account                                                                                   |
+    /// | 2     | ✅        | ❌      | The registration record account of the name                                                        |
+    /// | 3     | ❌        | ✅      | The owner of the name, the NFT holder when the name is tokenized                                   |
+    /// | 4     | ✅        | ✅      | The buyer account                                                                                  |
+    /// | 5     | ✅        | ❌      | The buyer token account                                                                            |
+    /// | 6     | ❌        | ❌      | The Pyth feed account                                                                              |
+    /// | 7     | ✅        | ❌      | The vault account                                                                                  |
+    /// | 8     | ❌        | ❌      | The SPL token program                                                                              |
+    /// | 9     | ❌        | ❌      | The TLD account of the root domain, required when renewing under another root than the default one |
+    /// | 10    | ❌        | ❌      | The NFT record account of the name, required when the name is tokenized                            |
+    /// | 11    | ❌        | ❌      | The token account holding the NFT, required when the name is tokenized                             |
+    Renew,
 }
 #[allow(missing_docs)]
 pub fn create(
@@ -147,3 +481,224 @@ pub fn update_metadata(
 ) -> Instruction {
     accounts.get_instruction(program_id, ProgramInstruction::UpdateMetadata as u8, params)
 }
+
+#[allow(missing_docs)]
+pub fn start_auction(
+    program_id: Pubkey,
+    accounts: start_auction::Accounts<Pubkey>,
+    params: start_auction::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::StartAuction as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn place_bid(
+    program_id: Pubkey,
+    accounts: place_bid::Accounts<Pubkey>,
+    params: place_bid::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::PlaceBid as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn settle_auction(
+    program_id: Pubkey,
+    accounts: settle_auction::Accounts<Pubkey>,
+    params: settle_auction::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::SettleAuction as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn reclaim_name(
+    program_id: Pubkey,
+    accounts: reclaim_name::Accounts<Pubkey>,
+    params: reclaim_name::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::ReclaimName as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn tokenize_domain(
+    program_id: Pubkey,
+    accounts: tokenize_domain::Accounts<Pubkey>,
+    params: tokenize_domain::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::TokenizeDomain as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn redeem_domain(
+    program_id: Pubkey,
+    accounts: redeem_domain::Accounts<Pubkey>,
+    params: redeem_domain::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::RedeemDomain as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn configure_nft_campaign(
+    program_id: Pubkey,
+    accounts: configure_nft_campaign::Accounts<Pubkey>,
+    params: configure_nft_campaign::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::ConfigureNftCampaign as u8,
+        params,
+    )
+}
+
+#[allow(missing_docs)]
+pub fn create_with_voucher(
+    program_id: Pubkey,
+    accounts: create_with_voucher::Accounts<Pubkey>,
+    params: create_with_voucher::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::CreateWithVoucher as u8,
+        params,
+    )
+}
+
+#[allow(missing_docs)]
+pub fn create_claim_tree(
+    program_id: Pubkey,
+    accounts: create_claim_tree::Accounts<Pubkey>,
+    params: create_claim_tree::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::CreateClaimTree as u8,
+        params,
+    )
+}
+
+#[allow(missing_docs)]
+pub fn claim_name(
+    program_id: Pubkey,
+    accounts: claim_name::Accounts<Pubkey>,
+    params: claim_name::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::ClaimName as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn configure_tld(
+    program_id: Pubkey,
+    accounts: configure_tld::Accounts<Pubkey>,
+    params: configure_tld::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::ConfigureTld as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn create_tld(
+    program_id: Pubkey,
+    accounts: create_tld::Accounts<Pubkey>,
+    params: create_tld::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::CreateTld as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn quote_price(
+    program_id: Pubkey,
+    accounts: quote_price::Accounts<Pubkey>,
+    params: quote_price::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::QuotePrice as u8, params)
+}
+#[allow(missing_docs)]
+pub fn resolve(
+    program_id: Pubkey,
+    accounts: resolve::Accounts<Pubkey>,
+    params: resolve::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::Resolve as u8, params)
+}
+#[allow(missing_docs)]
+pub fn create_batch(
+    program_id: Pubkey,
+    accounts: create_batch::Accounts<Pubkey>,
+    params: create_batch::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::CreateBatch as u8, params)
+}
+#[allow(missing_docs)]
+pub fn set_primary_domain(
+    program_id: Pubkey,
+    accounts: set_primary_domain::Accounts<Pubkey>,
+    params: set_primary_domain::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::SetPrimaryDomain as u8,
+        params,
+    )
+}
+#[allow(missing_docs)]
+pub fn create_record(
+    program_id: Pubkey,
+    accounts: create_record::Accounts<Pubkey>,
+    params: create_record::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::CreateRecord as u8, params)
+}
+#[allow(missing_docs)]
+pub fn update_record(
+    program_id: Pubkey,
+    accounts: update_record::Accounts<Pubkey>,
+    params: update_record::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::UpdateRecord as u8, params)
+}
+#[allow(missing_docs)]
+pub fn delete_record(
+    program_id: Pubkey,
+    accounts: delete_record::Accounts<Pubkey>,
+    params: delete_record::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::DeleteRecord as u8, params)
+}
+#[allow(missing_docs)]
+pub fn verify_eth_record(
+    program_id: Pubkey,
+    accounts: verify_eth_record::Accounts<Pubkey>,
+    params: verify_eth_record::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::VerifyEthRecord as u8,
+        params,
+    )
+}
+#[allow(missing_docs)]
+pub fn preview_release_premium(
+    program_id: Pubkey,
+    accounts: preview_release_premium::Accounts<Pubkey>,
+    params: preview_release_premium::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        ProgramInstruction::PreviewReleasePremium as u8,
+        params,
+    )
+}
+#[allow(missing_docs)]
+pub fn create_split_v3(
+    program_id: Pubkey,
+    accounts: create_split_v3::Accounts<Pubkey>,
+    params: create_split_v3::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::CreateSplitV3 as u8, params)
+}
+#[allow(missing_docs)]
+pub fn renew(
+    program_id: Pubkey,
+    accounts: renew::Accounts<Pubkey>,
+    params: renew::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, ProgramInstruction::Renew as u8, params)
+}
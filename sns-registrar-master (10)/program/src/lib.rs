@@ -1,8 +1,13 @@
 use bonfida_utils::declare_id_with_central_state;
 
+pub mod address;
+pub mod composability;
+pub mod content;
 pub mod cpi;
+pub mod dns;
 pub mod entrypoint;
 pub mod error;
+pub mod events;
 pub mod instruction_auto;
 pub mod processor;
 pub mod state;
@@ -18,6 +23,7 @@ declare_id_with_central_state!("CySCGJK9kNNqM2eQSW9hGQ1FCZ51ZHetRfGsTLY1TTe9");
 
 #[cfg(not(feature = "devnet"))]
 pub mod constants {
+    use crate::state::dutch_auction::PremiumCurve;
     use phf::phf_map;
     use solana_program::{pubkey, pubkey::Pubkey};
 
@@ -65,10 +71,22 @@ pub mod constants {
     pub const FEE_TIERS: [u64; 4] = [500_000_000, 5_000_000_000, 20_000_000_000, 40_000_000_000]; // Amount of FIDA tokens (with precision) that the discount account needs to hold
     pub const FIDA_MINT: Pubkey = pubkey!("EchesyfXePKdLtoiZSL8pBe8Myagyy8ZRqsACNCFGnvp");
     pub const ADMIN: Pubkey = pubkey!("VBx642K1hYGLU5Zm1CHW1uRXAtFgxN5mRqyMcXnLZFW");
-    pub const AUCTION_PROGRAM_ID: Pubkey = pubkey!("AVWV7vdWbLqXiLKFaP19GhYurhwxaLp2qRBSjT5tR5vT");
-    pub const WOLVES_COLLECTION: Pubkey = pubkey!("Dw74YSxTKVXsztPm3TmwbnfLK8KVaCZw69jVu4LE6uJe");
-    pub const WOLVES_COLLECTION_METADATA: Pubkey =
-        pubkey!("72aLKvXeV4aansAQtxKymeXDevT5ed6sCuz9iN62ugPT");
+    // Key signing the registration vouchers
+    pub const VOUCHER_AUTHORITY: Pubkey = ADMIN;
+
+    // Premium (USD with 6 decimals) of names released from expiry, decaying to zero over the window
+    pub const RELEASE_PREMIUM: u64 = 10_000_000_000;
+    pub const RELEASE_PREMIUM_WINDOW: i64 = 21 * 86_400;
+    pub const RELEASE_PREMIUM_CURVE: PremiumCurve = PremiumCurve::Exponential { half_life: 86_400 };
+
+    // Metadata of the NFTs minted for tokenized domains
+    pub const NFT_SYMBOL: &str = "SNS";
+    pub const NFT_METADATA_URI_PREFIX: &str = "https://sns.id/api/metadata/";
+
+    // Registrations can be bought for up to `MAX_REGISTRATION_YEARS` years at once.
+    // Discount (%) by number of years, the last entry applies to longer registrations
+    pub const MAX_REGISTRATION_YEARS: u8 = 10;
+    pub const MULTI_YEAR_DISCOUNTS_PCT: [u64; 5] = [0, 5, 10, 15, 20];
 
     // For marketing campaigns with partners
     // referrer public key -> (discount_pct, start_time, end_time, referrer_pct, start_time, end_time)
@@ -110,6 +128,7 @@ pub mod constants {
 
 #[cfg(feature = "devnet")]
 pub mod constants {
+    use crate::state::dutch_auction::PremiumCurve;
     use phf::phf_map;
     use solana_program::{pubkey, pubkey::Pubkey};
 
@@ -141,10 +160,22 @@ pub mod constants {
     pub const FEE_TIERS: [u64; 4] = [500_000_000, 5_000_000_000, 20_000_000_000, 40_000_000_000]; // Amount of FIDA tokens (with precision) that the discount account needs to hold
     pub const FIDA_MINT: Pubkey = pubkey!("fidaWCioBQjieRrUQDxxS5Uxmq1CLi2VuVRyv4dEBey");
     pub const ADMIN: Pubkey = pubkey!("SNSaTJbEv2iT3CUrCQYa9zpGjbBVWhFCPaSJHkaJX34");
-    pub const AUCTION_PROGRAM_ID: Pubkey = pubkey!("AVWV7vdWbLqXiLKFaP19GhYurhwxaLp2qRBSjT5tR5vT");
-    pub const WOLVES_COLLECTION: Pubkey = pubkey!("Dw74YSxTKVXsztPm3TmwbnfLK8KVaCZw69jVu4LE6uJe");
-    pub const WOLVES_COLLECTION_METADATA: Pubkey =
-        pubkey!("72aLKvXeV4aansAQtxKymeXDevT5ed6sCuz9iN62ugPT");
+    // Key signing the registration vouchers
+    pub const VOUCHER_AUTHORITY: Pubkey = ADMIN;
+
+    // Premium (USD with 6 decimals) of names released from expiry, decaying to zero over the window
+    pub const RELEASE_PREMIUM: u64 = 10_000_000;
+    pub const RELEASE_PREMIUM_WINDOW: i64 = 21 * 86_400;
+    pub const RELEASE_PREMIUM_CURVE: PremiumCurve = PremiumCurve::Linear;
+
+    // Metadata of the NFTs minted for tokenized domains
+    pub const NFT_SYMBOL: &str = "GORB";
+    pub const NFT_METADATA_URI_PREFIX: &str = "https://rpc.gorbchain.xyz/sns/metadata/";
+
+    // Registrations can be bought for up to `MAX_REGISTRATION_YEARS` years at once.
+    // Discount (%) by number of years, the last entry applies to longer registrations
+    pub const MAX_REGISTRATION_YEARS: u8 = 10;
+    pub const MULTI_YEAR_DISCOUNTS_PCT: [u64; 5] = [0, 5, 10, 15, 20];
 
     // For marketing campaigns with partners
     // referrer public key -> (discount_pct, start_time, end_time, referrer_pct, start_time, end_time)
@@ -6,12 +6,35 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+pub mod claim_name;
+pub mod configure_nft_campaign;
+pub mod configure_tld;
 pub mod create;
+pub mod create_batch;
+pub mod create_claim_tree;
+pub mod create_record;
 pub mod create_reverse;
 pub mod create_split_v2;
+pub mod create_split_v3;
+pub mod create_tld;
 pub mod create_with_nft;
+pub mod create_with_voucher;
 pub mod delete;
+pub mod delete_record;
+pub mod place_bid;
+pub mod preview_release_premium;
+pub mod quote_price;
+pub mod reclaim_name;
+pub mod renew;
+pub mod resolve;
+pub mod redeem_domain;
+pub mod set_primary_domain;
+pub mod settle_auction;
+pub mod start_auction;
+pub mod tokenize_domain;
 pub mod update_metadata;
+pub mod update_record;
+pub mod verify_eth_record;
 pub struct Processor {}
 
 impl Processor {
@@ -65,6 +88,148 @@ impl Processor {
                     .map_err(|_| ProgramError::InvalidInstructionData)?;
                 update_metadata::process_update_metadata(program_id, accounts, params)?
             }
+            ProgramInstruction::StartAuction => {
+                msg!("Instruction: Start auction");
+                let params = start_auction::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                start_auction::process_start_auction(program_id, accounts, params)?
+            }
+            ProgramInstruction::PlaceBid => {
+                msg!("Instruction: Place bid");
+                let params = place_bid::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                place_bid::process_place_bid(program_id, accounts, params)?
+            }
+            ProgramInstruction::SettleAuction => {
+                msg!("Instruction: Settle auction");
+                let params = settle_auction::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                settle_auction::process_settle_auction(program_id, accounts, params)?
+            }
+            ProgramInstruction::ReclaimName => {
+                msg!("Instruction: Reclaim name");
+                let params = reclaim_name::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                reclaim_name::process_reclaim_name(program_id, accounts, params)?
+            }
+            ProgramInstruction::TokenizeDomain => {
+                msg!("Instruction: Tokenize domain");
+                let params = tokenize_domain::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                tokenize_domain::process_tokenize_domain(program_id, accounts, params)?
+            }
+            ProgramInstruction::RedeemDomain => {
+                msg!("Instruction: Redeem domain");
+                let params = redeem_domain::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                redeem_domain::process_redeem_domain(program_id, accounts, params)?
+            }
+            ProgramInstruction::ConfigureNftCampaign => {
+                msg!("Instruction: Configure NFT campaign");
+                let params = configure_nft_campaign::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                configure_nft_campaign::process_configure_nft_campaign(
+                    program_id, accounts, params,
+                )?
+            }
+            ProgramInstruction::CreateWithVoucher => {
+                msg!("Instruction: Create with voucher");
+                let params = create_with_voucher::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                create_with_voucher::process_create_with_voucher(program_id, accounts, params)?
+            }
+            ProgramInstruction::CreateClaimTree => {
+                msg!("Instruction: Create claim tree");
+                let params = create_claim_tree::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                create_claim_tree::process_create_claim_tree(program_id, accounts, params)?
+            }
+            ProgramInstruction::ClaimName => {
+                msg!("Instruction: Claim name");
+                let params = claim_name::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                claim_name::process_claim_name(program_id, accounts, params)?
+            }
+            ProgramInstruction::ConfigureTld => {
+                msg!("Instruction: Configure TLD");
+                let params = configure_tld::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                configure_tld::process_configure_tld(program_id, accounts, params)?
+            }
+            ProgramInstruction::CreateTld => {
+                msg!("Instruction: Create TLD");
+                let params = create_tld::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                create_tld::process_create_tld(program_id, accounts, params)?
+            }
+            ProgramInstruction::QuotePrice => {
+                msg!("Instruction: Quote price");
+                let params = quote_price::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                quote_price::process_quote_price(program_id, accounts, params)?
+            }
+            ProgramInstruction::Resolve => {
+                msg!("Instruction: Resolve");
+                let params = resolve::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                resolve::process_resolve(program_id, accounts, params)?
+            }
+            ProgramInstruction::CreateBatch => {
+                msg!("Instruction: Create batch");
+                let params = create_batch::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                create_batch::process_create_batch(program_id, accounts, params)?
+            }
+            ProgramInstruction::SetPrimaryDomain => {
+                msg!("Instruction: Set primary domain");
+                let params = set_primary_domain::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                set_primary_domain::process_set_primary_domain(program_id, accounts, params)?
+            }
+            ProgramInstruction::CreateRecord => {
+                msg!("Instruction: Create record");
+                let params = create_record::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                create_record::process_create_record(program_id, accounts, params)?
+            }
+            ProgramInstruction::UpdateRecord => {
+                msg!("Instruction: Update record");
+                let params = update_record::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                update_record::process_update_record(program_id, accounts, params)?
+            }
+            ProgramInstruction::DeleteRecord => {
+                msg!("Instruction: Delete record");
+                let params = delete_record::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                delete_record::process_delete_record(program_id, accounts, params)?
+            }
+            ProgramInstruction::VerifyEthRecord => {
+                msg!("Instruction: Verify ETH record");
+                let params = verify_eth_record::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                verify_eth_record::process_verify_eth_record(program_id, accounts, params)?
+            }
+            ProgramInstruction::PreviewReleasePremium => {
+                msg!("Instruction: Preview release premium");
+                let params = preview_release_premium::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                preview_release_premium::process_preview_release_premium(
+                    program_id, accounts, params,
+                )?
+            }
+            ProgramInstruction::CreateSplitV3 => {
+                msg!("Instruction: Create with split V3");
+                let params = create_split_v3::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                create_split_v3::process_create(program_id, accounts, params)?
+            }
+            ProgramInstruction::Renew => {
+                msg!("Instruction: Renew");
+                let params = renew::Params::try_from_slice(instruction_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                renew::process_renew(program_id, accounts, params)?
+            }
             _ => {
                 msg!("Instruction: Deprecated");
                 return Err(ProgramError::InvalidInstructionData);
@@ -0,0 +1,213 @@
+//! Claim a domain name from a Merkle allowlist
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program, sysvar,
+    sysvar::Sysvar,
+};
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    central_state,
+    constants::ROOT_DOMAIN_ACCOUNT,
+    cpi::Cpi,
+    error::Error,
+    events::Event,
+    state::claim_tree::ClaimTree,
+    utils::{
+        check_no_release_premium, close_release_state, get_hashed_name, get_name_key,
+        get_reverse_key, write_registration_record,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `claim_name` instruction
+pub struct Params {
+    pub name: String,
+    pub space: u32,
+    pub metadata_url: Option<String>,
+    /// Position of the (name, wallet) leaf in the tree
+    pub index: u32,
+    /// The Merkle proof of the leaf, from the leaf level up to the root
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `claim_name` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The root domain account
+    pub root_domain: &'a T,
+    /// The name account
+    #[cons(writable)]
+    pub name: &'a T,
+    /// The reverse look up account
+    #[cons(writable)]
+    pub reverse_lookup: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The claim tree account
+    #[cons(writable)]
+    pub claim_tree: &'a T,
+    /// The wallet of the allowlist leaf, owner of the created name. Signs so that the space and
+    /// metadata URL of the name are its own choice.
+    #[cons(signer)]
+    pub owner: &'a T,
+    /// The state auction account, the name must not be auctioned or released with a premium
+    #[cons(writable)]
+    pub state: &'a T,
+    /// The rent sysvar account
+    pub rent_sysvar: &'a T,
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+    /// The registration record account, claimed names are registered for a year
+    #[cons(writable)]
+    pub registration_record: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            naming_service_program: next_account_info(accounts_iter)?,
+            root_domain: next_account_info(accounts_iter)?,
+            name: next_account_info(accounts_iter)?,
+            reverse_lookup: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            claim_tree: next_account_info(accounts_iter)?,
+            owner: next_account_info(accounts_iter)?,
+            state: next_account_info(accounts_iter)?,
+            rent_sysvar: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            registration_record: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.root_domain, &ROOT_DOMAIN_ACCOUNT)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+        check_account_key(accounts.rent_sysvar, &sysvar::rent::ID)?;
+
+        // Check ownership
+        check_account_owner(accounts.name, &system_program::ID)
+            .map_err(|_| Error::AlreadyRegistered)?;
+        check_account_owner(accounts.claim_tree, program_id)?;
+
+        // Check signer
+        check_signer(accounts.owner)?;
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_claim_name(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let name_account_key = get_name_key(&params.name, None)?;
+    check_account_key(accounts.name, &name_account_key)?;
+    check_account_key(
+        accounts.reverse_lookup,
+        &get_reverse_key(&name_account_key, None)?,
+    )?;
+
+    let (state_key, _) = Pubkey::find_program_address(&[&name_account_key.to_bytes()], program_id);
+    check_account_key(accounts.state, &state_key)?;
+    check_no_release_premium(accounts.state)?;
+
+    let mut claim_tree = ClaimTree::from_account_info(accounts.claim_tree)?;
+    let leaf = ClaimTree::get_leaf(&params.name, accounts.owner.key);
+    if !claim_tree.verify_proof(params.index, leaf, &params.proof) {
+        return Err(Error::InvalidMerkleProof.into());
+    }
+    if claim_tree.is_claimed(params.index) {
+        return Err(Error::AlreadyClaimed.into());
+    }
+    claim_tree.set_claimed(params.index);
+    claim_tree.save(&mut accounts.claim_tree.data.borrow_mut());
+
+    msg!("[+] Claiming {}", params.name);
+    let central_state_signer_seeds: &[&[u8]] = &[&program_id.to_bytes(), &[central_state::NONCE]];
+    let rent = Rent::get()?;
+    Cpi::create_name_account(
+        accounts.naming_service_program,
+        accounts.system_program,
+        accounts.name,
+        accounts.fee_payer,
+        accounts.owner,
+        Some(accounts.root_domain),
+        accounts.central_state,
+        get_hashed_name(&params.name),
+        rent.minimum_balance(NameRecordHeader::LEN + params.space as usize),
+        params.space,
+        central_state_signer_seeds,
+    )?;
+    close_release_state(accounts.state, accounts.fee_payer);
+    let expiry = write_registration_record(
+        program_id,
+        accounts.system_program,
+        accounts.fee_payer,
+        accounts.registration_record,
+        &name_account_key,
+        1,
+    )?;
+    Event::Registered {
+        name_account: name_account_key,
+        owner: *accounts.owner.key,
+        root_domain: *accounts.root_domain.key,
+        name: params.name.clone(),
+        mint: Pubkey::default(),
+        amount: 0,
+        expiry: Some(expiry),
+    }
+    .emit();
+
+    // Reverse look up
+    if accounts.reverse_lookup.data_len() == 0 {
+        Cpi::create_reverse_lookup_account(
+            accounts.naming_service_program,
+            accounts.system_program,
+            accounts.reverse_lookup,
+            accounts.fee_payer,
+            params.name.clone(),
+            get_hashed_name(&name_account_key.to_string()),
+            accounts.central_state,
+            accounts.rent_sysvar,
+            central_state_signer_seeds,
+            None,
+            None,
+            params.metadata_url,
+        )?;
+        Event::ReverseCreated {
+            name_account: name_account_key,
+            reverse_lookup: *accounts.reverse_lookup.key,
+            name: params.name,
+        }
+        .emit();
+    }
+
+    Ok(())
+}
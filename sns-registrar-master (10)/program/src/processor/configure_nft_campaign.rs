@@ -0,0 +1,126 @@
+//! Create or update an NFT-gated registration campaign
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::{
+    constants::ADMIN,
+    cpi::Cpi,
+    state::{
+        nft_campaign::{NftCampaign, NftCampaignMode},
+        Tag,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `configure_nft_campaign` instruction
+pub struct Params {
+    pub mode: NftCampaignMode,
+    /// Maximum length (in graphemes) of the names that can be claimed
+    pub max_name_length: u32,
+    /// Maximum number of names that can be claimed through the campaign
+    pub max_claims: u64,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `configure_nft_campaign` instruction
+pub struct Accounts<'a, T> {
+    /// The NFT campaign account
+    #[cons(writable)]
+    pub nft_campaign: &'a T,
+    /// The mint of the verified collection
+    pub collection_mint: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The admin account
+    #[cons(writable, signer)]
+    pub admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            nft_campaign: next_account_info(accounts_iter)?,
+            collection_mint: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.admin, &ADMIN)?;
+
+        // Check ownership
+        check_account_owner(accounts.collection_mint, &spl_token::ID)?;
+
+        // Check signer
+        check_signer(accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_configure_nft_campaign(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let (campaign_key, campaign_nonce) =
+        NftCampaign::find_key(accounts.collection_mint.key, program_id);
+    check_account_key(accounts.nft_campaign, &campaign_key)?;
+
+    let campaign = if accounts.nft_campaign.data_is_empty() {
+        msg!("[+] Creating NFT campaign");
+        let campaign = NftCampaign {
+            tag: Tag::NftCampaign,
+            nonce: campaign_nonce,
+            collection: *accounts.collection_mint.key,
+            mode: params.mode,
+            max_name_length: params.max_name_length,
+            max_claims: params.max_claims,
+            claims: 0,
+        };
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.admin,
+            accounts.nft_campaign,
+            &[
+                NftCampaign::SEED,
+                &accounts.collection_mint.key.to_bytes(),
+                &[campaign_nonce],
+            ],
+            campaign.borsh_len(),
+        )?;
+        campaign
+    } else {
+        msg!("[+] Updating NFT campaign");
+        check_account_owner(accounts.nft_campaign, program_id)?;
+        let mut campaign = NftCampaign::from_account_info(accounts.nft_campaign)?;
+        campaign.mode = params.mode;
+        campaign.max_name_length = params.max_name_length;
+        campaign.max_claims = params.max_claims;
+        campaign
+    };
+
+    campaign.save(&mut accounts.nft_campaign.data.borrow_mut());
+
+    Ok(())
+}
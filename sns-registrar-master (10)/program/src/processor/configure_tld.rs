@@ -0,0 +1,148 @@
+//! Register or update a top level domain
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    central_state,
+    constants::ADMIN,
+    cpi::Cpi,
+    events::Event,
+    state::{tld::Tld, Tag},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `configure_tld` instruction
+pub struct Params {
+    /// Owner of the token accounts receiving the registration payments
+    pub vault_owner: Pubkey,
+    pub enabled: bool,
+    /// USD price (6 decimals) of names of 1 to 10 graphemes, the last entry applies to longer names
+    pub usd_prices: [u64; 10],
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `configure_tld` instruction
+pub struct Accounts<'a, T> {
+    /// The TLD account
+    #[cons(writable)]
+    pub tld: &'a T,
+    /// The root name account of the TLD, owned by the central state
+    pub root_domain: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The admin account
+    #[cons(writable, signer)]
+    pub admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            tld: next_account_info(accounts_iter)?,
+            root_domain: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.admin, &ADMIN)?;
+
+        // Check ownership
+        check_account_owner(accounts.root_domain, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_configure_tld(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    configure_tld(program_id, &accounts, params)
+}
+
+/// Creates the TLD account of `root_domain` or updates its configuration
+pub fn configure_tld<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &Accounts<'a, AccountInfo<'b>>,
+    params: Params,
+) -> ProgramResult {
+    let (tld_key, tld_nonce) = Tld::find_key(accounts.root_domain.key, program_id);
+    check_account_key(accounts.tld, &tld_key)?;
+
+    // Only root names held by the registrar can be sold as a TLD
+    let root_header = NameRecordHeader::unpack_from_slice(&accounts.root_domain.data.borrow())?;
+    if root_header.parent_name != Pubkey::default() {
+        msg!("The TLD has to be a root name");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if root_header.owner != central_state::KEY {
+        msg!("The root name of the TLD has to be owned by the central state");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let tld = Tld {
+        tag: Tag::Tld,
+        nonce: tld_nonce,
+        root_domain: *accounts.root_domain.key,
+        vault_owner: params.vault_owner,
+        enabled: params.enabled,
+        usd_prices: params.usd_prices,
+    };
+
+    if accounts.tld.data_is_empty() {
+        msg!("[+] Registering TLD");
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.admin,
+            accounts.tld,
+            &[
+                Tld::SEED,
+                &accounts.root_domain.key.to_bytes(),
+                &[tld_nonce],
+            ],
+            tld.borsh_len(),
+        )?;
+    } else {
+        msg!("[+] Updating TLD");
+        check_account_owner(accounts.tld, program_id)?;
+        Tld::from_account_info(accounts.tld)?;
+    }
+
+    tld.save(&mut accounts.tld.data.borrow_mut());
+
+    Event::TldConfigured {
+        root_domain: tld.root_domain,
+        vault_owner: tld.vault_owner,
+        enabled: tld.enabled,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -10,6 +10,7 @@ use solana_program::{
 };
 
 use super::create_split_v2;
+use crate::utils::next_tld_account;
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
 /// The required parameters for the `create` instruction
@@ -68,7 +69,11 @@ pub struct Accounts<'a, T> {
     /// The rent sysvar account
     pub rent_sysvar: &'a T,
     /// The state auction account
+    #[cons(writable)]
     pub state: &'a T,
+    /// The TLD account of the root domain, required when registering under another root than the
+    /// default one
+    pub tld_opt: Option<&'a T>,
     /// The *optional* referrer token account to receive a portion of fees.
     /// The token account owner has to be whitelisted.
     #[cons(writable)]
@@ -96,6 +101,7 @@ impl<'a, T> From<Accounts<'a, T>> for create_split_v2::Accounts<'a, T> {
             spl_token_program: value.spl_token_program,
             rent_sysvar: value.rent_sysvar,
             state: value.state,
+            tld_opt: value.tld_opt,
             referrer_account_opt: value.referrer_account_opt,
         }
     }
@@ -107,9 +113,11 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         _program_id: &Pubkey,
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
+        let naming_service_program = next_account_info(accounts_iter)?;
+        let root_domain = next_account_info(accounts_iter)?;
         Ok(Accounts {
-            naming_service_program: next_account_info(accounts_iter)?,
-            root_domain: next_account_info(accounts_iter)?,
+            naming_service_program,
+            root_domain,
             name: next_account_info(accounts_iter)?,
             reverse_lookup: next_account_info(accounts_iter)?,
             system_program: next_account_info(accounts_iter)?,
@@ -123,6 +131,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             spl_token_program: next_account_info(accounts_iter)?,
             rent_sysvar: next_account_info(accounts_iter)?,
             state: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
             referrer_account_opt: next_account_info(accounts_iter).ok(),
         })
     }
@@ -134,5 +143,6 @@ pub fn process_create(
     params: Params,
 ) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
-    create_split_v2::create(program_id, accounts.into(), params.into())
+    let params = create_split_v2::Params::from(params);
+    create_split_v2::create(program_id, accounts.into(), None, params.into())
 }
@@ -0,0 +1,370 @@
+//! Register several domain names with a single oracle read and token transfer
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    fp_math::fp32_div,
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program, sysvar,
+    sysvar::Sysvar,
+};
+use spl_name_service::state::NameRecordHeader;
+use spl_token::instruction::transfer;
+
+use crate::{
+    central_state,
+    cpi::Cpi,
+    events::Event,
+    utils::{
+        check_referrer_token_account, check_registration_accounts, check_root_domain, check_vault,
+        close_release_state, get_domain_usd_price, get_hashed_name, get_price_quote,
+        get_token_usd_price_checked_v2, next_tld_account, write_registration_record, PriceQuote,
+    },
+    Error,
+};
+
+/// Number of accounts passed for each name of the batch
+pub const ACCOUNTS_PER_NAME: usize = 4;
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// A name of the batch
+pub struct BatchEntry {
+    pub name: String,
+    pub space: u32,
+    pub metadata_url: Option<String>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `create_batch` instruction
+pub struct Params {
+    pub entries: Vec<BatchEntry>,
+    pub referrer_idx_opt: Option<u16>,
+    /// Number of years of the registrations
+    pub years: u8,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `create_batch` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The root domain account, the default root domain or the root of an enabled TLD
+    pub root_domain: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The buyer account
+    #[cons(writable, signer)]
+    pub buyer: &'a T,
+    /// The registered domains owner
+    pub domain_owner: &'a T,
+    /// The solana fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+    /// The buyer token account
+    #[cons(writable)]
+    pub buyer_token_source: &'a T,
+    /// The Pyth feed account
+    pub pyth_feed_account: &'a T,
+    /// The vault account
+    #[cons(writable)]
+    pub vault: &'a T,
+    /// The SPL token program
+    pub spl_token_program: &'a T,
+    /// The rent sysvar account
+    pub rent_sysvar: &'a T,
+    /// The TLD account of the root domain, required when registering under another root than the
+    /// default one
+    pub tld_opt: Option<&'a T>,
+    /// The name, reverse look up, state auction and registration record accounts of each entry
+    #[cons(writable)]
+    pub names: &'a [T],
+    /// The *optional* referrer token account to receive a portion of fees.
+    /// The token account owner has to be whitelisted.
+    #[cons(writable)]
+    pub referrer_account_opt: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+        num_entries: usize,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let naming_service_program = next_account_info(accounts_iter)?;
+        let root_domain = next_account_info(accounts_iter)?;
+        let mut accounts = Accounts {
+            naming_service_program,
+            root_domain,
+            system_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            buyer: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            buyer_token_source: next_account_info(accounts_iter)?,
+            pyth_feed_account: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            rent_sysvar: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
+            names: &[],
+            referrer_account_opt: None,
+        };
+        let remaining_accounts = accounts_iter.as_slice();
+        let num_name_accounts = num_entries * ACCOUNTS_PER_NAME;
+        if remaining_accounts.len() < num_name_accounts {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (names, remaining_accounts) = remaining_accounts.split_at(num_name_accounts);
+        accounts.names = names;
+        accounts.referrer_account_opt = remaining_accounts.first();
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+        check_account_key(accounts.rent_sysvar, &sysvar::rent::ID)?;
+
+        // Check ownership
+        check_account_owner(accounts.vault, &spl_token::ID)?;
+
+        // Check signer
+        check_signer(accounts.buyer)?;
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+/// The accounts of an entry of the batch
+struct NameAccounts<'a, 'b> {
+    name: &'a AccountInfo<'b>,
+    reverse_lookup: &'a AccountInfo<'b>,
+    state: &'a AccountInfo<'b>,
+    registration_record: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> NameAccounts<'a, 'b> {
+    fn new(accounts: &'a [AccountInfo<'b>]) -> Self {
+        Self {
+            name: &accounts[0],
+            reverse_lookup: &accounts[1],
+            state: &accounts[2],
+            registration_record: &accounts[3],
+        }
+    }
+
+    /// Checks the accounts of the entry and returns its name account key
+    fn check(
+        &self,
+        program_id: &Pubkey,
+        root_domain: &Pubkey,
+        entry: &BatchEntry,
+    ) -> Result<Pubkey, ProgramError> {
+        check_account_owner(self.name, &system_program::ID)
+            .map_err(|_| Error::AlreadyRegistered)?;
+        check_registration_accounts(
+            program_id,
+            root_domain,
+            &entry.name,
+            self.name,
+            self.reverse_lookup,
+            self.state,
+            Some(self.registration_record),
+        )
+    }
+}
+
+pub fn process_create_batch(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id, params.entries.len())?;
+    if params.entries.is_empty() {
+        msg!("The batch is empty");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let tld = check_root_domain(accounts.root_domain, accounts.tld_opt)?;
+    check_vault(accounts.vault, tld.as_ref())?;
+
+    let token_acc = spl_token::state::Account::unpack(&accounts.buyer_token_source.data.borrow())?;
+    let token_price = get_token_usd_price_checked_v2(accounts.pyth_feed_account, &token_acc.mint)?;
+    let referrer_opt = accounts
+        .referrer_account_opt
+        .map(|referrer_account| {
+            check_referrer_token_account(referrer_account, params.referrer_idx_opt)
+        })
+        .transpose()?;
+
+    // Price every name with the same oracle price
+    let mut quotes = Vec::with_capacity(params.entries.len());
+    let mut name_account_keys = Vec::with_capacity(params.entries.len());
+    for (entry, name_accounts) in params
+        .entries
+        .iter()
+        .zip(accounts.names.chunks_exact(ACCOUNTS_PER_NAME))
+    {
+        let name_accounts = NameAccounts::new(name_accounts);
+        name_account_keys.push(name_accounts.check(program_id, accounts.root_domain.key, entry)?);
+        let usd_price =
+            get_domain_usd_price(&entry.name, name_accounts.state, tld.as_ref(), params.years)?;
+        let domain_price = fp32_div(usd_price, token_price).ok_or(Error::Overflow)?;
+        quotes.push(get_price_quote(
+            domain_price,
+            &token_acc.mint,
+            0,
+            referrer_opt.as_ref(),
+        )?);
+    }
+    let total = PriceQuote::sum(&quotes)?;
+    msg!(
+        "Registering {} domains for {}",
+        quotes.len(),
+        total.total()?
+    );
+
+    // Transfer fees to the referrer
+    if let Some(referrer_account) = accounts.referrer_account_opt {
+        let transfer_ix = transfer(
+            &spl_token::ID,
+            accounts.buyer_token_source.key,
+            referrer_account.key,
+            accounts.buyer.key,
+            &[],
+            total.referrer_fee,
+        )?;
+
+        invoke(
+            &transfer_ix,
+            &[
+                accounts.spl_token_program.clone(),
+                accounts.buyer_token_source.clone(),
+                referrer_account.clone(),
+                accounts.buyer.clone(),
+            ],
+        )?;
+
+        for (name_account_key, quote) in name_account_keys.iter().zip(&quotes) {
+            Event::ReferrerPaid {
+                name_account: *name_account_key,
+                referrer: *referrer_account.key,
+                mint: token_acc.mint,
+                amount: quote.referrer_fee,
+            }
+            .emit();
+        }
+    }
+
+    // Transfer tokens to vault
+    let transfer_ix = transfer(
+        &spl_token::ID,
+        accounts.buyer_token_source.key,
+        accounts.vault.key,
+        accounts.buyer.key,
+        &[],
+        total.vault_amount,
+    )?;
+
+    invoke(
+        &transfer_ix,
+        &[
+            accounts.spl_token_program.clone(),
+            accounts.buyer_token_source.clone(),
+            accounts.vault.clone(),
+            accounts.buyer.clone(),
+        ],
+    )?;
+
+    let central_state_signer_seeds: &[&[u8]] = &[&program_id.to_bytes(), &[central_state::NONCE]];
+    let rent = Rent::get()?;
+
+    for (((entry, name_accounts), name_account_key), quote) in params
+        .entries
+        .into_iter()
+        .zip(accounts.names.chunks_exact(ACCOUNTS_PER_NAME))
+        .zip(name_account_keys)
+        .zip(quotes)
+    {
+        let name_accounts = NameAccounts::new(name_accounts);
+
+        // Create domain name
+        Cpi::create_name_account(
+            accounts.naming_service_program,
+            accounts.system_program,
+            name_accounts.name,
+            accounts.fee_payer,
+            accounts.domain_owner,
+            Some(accounts.root_domain),
+            accounts.central_state,
+            get_hashed_name(&entry.name),
+            rent.minimum_balance(NameRecordHeader::LEN + entry.space as usize),
+            entry.space,
+            central_state_signer_seeds,
+        )?;
+
+        // Close the release premium
+        close_release_state(name_accounts.state, accounts.fee_payer);
+
+        // Registration term
+        let expiry = write_registration_record(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            name_accounts.registration_record,
+            &name_account_key,
+            params.years,
+        )?;
+
+        Event::Registered {
+            name_account: name_account_key,
+            owner: *accounts.domain_owner.key,
+            root_domain: *accounts.root_domain.key,
+            name: entry.name.clone(),
+            mint: token_acc.mint,
+            amount: quote.total()?,
+            expiry: Some(expiry),
+        }
+        .emit();
+
+        // Reverse look up
+        if name_accounts.reverse_lookup.data_len() == 0 {
+            Cpi::create_reverse_lookup_account(
+                accounts.naming_service_program,
+                accounts.system_program,
+                name_accounts.reverse_lookup,
+                accounts.fee_payer,
+                entry.name.clone(),
+                get_hashed_name(&name_account_key.to_string()),
+                accounts.central_state,
+                accounts.rent_sysvar,
+                central_state_signer_seeds,
+                None,
+                None,
+                entry.metadata_url,
+            )?;
+            Event::ReverseCreated {
+                name_account: name_account_key,
+                reverse_lookup: *name_accounts.reverse_lookup.key,
+                name: entry.name,
+            }
+            .emit();
+        }
+    }
+
+    Ok(())
+}
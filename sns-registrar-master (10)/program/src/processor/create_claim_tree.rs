@@ -0,0 +1,103 @@
+//! Publish a Merkle allowlist of domain names that can be claimed for free
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::{constants::ADMIN, cpi::Cpi, state::claim_tree::ClaimTree};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `create_claim_tree` instruction
+pub struct Params {
+    /// Identifier of the tree, used to derive its address
+    pub id: u64,
+    /// The Merkle root of the allowlist
+    pub root: [u8; 32],
+    /// Number of (name, wallet) pairs of the allowlist, at most `ClaimTree::MAX_LEAVES`
+    pub num_leaves: u32,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `create_claim_tree` instruction
+pub struct Accounts<'a, T> {
+    /// The claim tree account
+    #[cons(writable)]
+    pub claim_tree: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The admin account
+    #[cons(writable, signer)]
+    pub admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            claim_tree: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.admin, &ADMIN)?;
+
+        // Check ownership
+        check_account_owner(accounts.claim_tree, &system_program::ID)?;
+
+        // Check signer
+        check_signer(accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_create_claim_tree(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let (claim_tree_key, claim_tree_nonce) = ClaimTree::find_key(params.id, program_id);
+    check_account_key(accounts.claim_tree, &claim_tree_key)?;
+
+    if params.num_leaves == 0 || params.num_leaves > ClaimTree::MAX_LEAVES {
+        msg!(
+            "Claim trees have from 1 to {} leaves, split larger allowlists",
+            ClaimTree::MAX_LEAVES
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let claim_tree = ClaimTree::new(claim_tree_nonce, params.id, params.root, params.num_leaves);
+    Cpi::create_account(
+        program_id,
+        accounts.system_program,
+        accounts.admin,
+        accounts.claim_tree,
+        &[
+            ClaimTree::SEED,
+            &params.id.to_le_bytes(),
+            &[claim_tree_nonce],
+        ],
+        claim_tree.borsh_len(),
+    )?;
+    claim_tree.save(&mut accounts.claim_tree.data.borrow_mut());
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! Create an address record under a domain name
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::{
+    central_state,
+    cpi::Cpi,
+    events::Event,
+    state::record::{Record, RecordData},
+    utils::{check_domain_owner, get_hashed_name, get_record_key},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `create_record` instruction
+pub struct Params {
+    pub record: Record,
+    /// The value of the record, in the format accepted by `Record::parse_value`
+    pub value: String,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `create_record` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The domain name account
+    pub domain: &'a T,
+    /// The record account
+    #[cons(writable)]
+    pub record: &'a T,
+    /// The owner of the domain name
+    #[cons(signer)]
+    pub domain_owner: &'a T,
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+    /// The address of a SOL record, signing to prove its right of association
+    #[cons(signer)]
+    pub roa_signer_opt: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            naming_service_program: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            roa_signer_opt: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+
+        // Check ownership
+        check_account_owner(accounts.record, &system_program::ID)
+            .map_err(|_| crate::Error::AlreadyRegistered)?;
+
+        // Check signer
+        check_signer(accounts.domain_owner)?;
+        check_signer(accounts.fee_payer)?;
+        if let Some(roa_signer) = accounts.roa_signer_opt {
+            check_signer(roa_signer)?;
+        }
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_create_record(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner)?;
+    check_account_key(
+        accounts.record,
+        &get_record_key(accounts.domain.key, params.record),
+    )?;
+
+    let mut record_data =
+        RecordData::new(params.record, &params.value, *accounts.domain_owner.key)?;
+    if let Some(roa_signer) = accounts.roa_signer_opt {
+        record_data.set_sol_right_of_association(roa_signer.key)?;
+    }
+
+    let central_state_signer_seeds: &[&[u8]] = &[&program_id.to_bytes(), &[central_state::NONCE]];
+    Cpi::create_child_name_account(
+        accounts.naming_service_program,
+        accounts.system_program,
+        accounts.record,
+        accounts.fee_payer,
+        accounts.domain,
+        accounts.domain_owner,
+        accounts.central_state,
+        get_hashed_name(&params.record.name()),
+        record_data.to_padded_vec(),
+        central_state_signer_seeds,
+    )?;
+
+    Event::RecordSet {
+        domain: *accounts.domain.key,
+        record: params.record,
+        content: record_data.content,
+    }
+    .emit();
+
+    Ok(())
+}
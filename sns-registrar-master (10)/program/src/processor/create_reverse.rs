@@ -2,11 +2,12 @@
 
 use crate::{
     central_state,
+    composability::RegistrationResult,
     cpi::Cpi,
-    utils::{get_hashed_name, get_name_key},
+    events::Event,
+    utils::{check_root_domain, get_hashed_name, get_name_key, next_tld_account},
 };
 
-use crate::constants::ROOT_DOMAIN_ACCOUNT;
 use bonfida_utils::{
     checks::{check_account_key, check_account_owner, check_signer},
     BorshSize, InstructionsAccount,
@@ -36,7 +37,7 @@ pub struct Accounts<'a, T> {
     /// The name service program account
     pub naming_service_program: &'a T,
 
-    /// The root domain account
+    /// The root domain account, the default root domain or the root of an enabled TLD
     pub root_domain: &'a T,
 
     /// The reverse lookup account
@@ -55,6 +56,9 @@ pub struct Accounts<'a, T> {
 
     pub rent_sysvar: &'a T,
 
+    /// The TLD account of the root domain, required when the root domain is not the default one
+    pub tld_opt: Option<&'a T>,
+
     /// The optional parent name account for subdomains
     #[cons(writable)]
     pub parent_name: Option<&'a T>,
@@ -70,21 +74,23 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         _program_id: &Pubkey,
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
+        let naming_service_program = next_account_info(accounts_iter)?;
+        let root_domain = next_account_info(accounts_iter)?;
         let accounts = Accounts {
-            naming_service_program: next_account_info(accounts_iter)?,
-            root_domain: next_account_info(accounts_iter)?,
+            naming_service_program,
+            root_domain,
             reverse_lookup: next_account_info(accounts_iter)?,
             system_program: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
             fee_payer: next_account_info(accounts_iter)?,
             rent_sysvar: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
             parent_name: next_account_info(accounts_iter).ok(),
             parent_name_owner: next_account_info(accounts_iter).ok(),
         };
 
         // Check keys
         check_account_key(accounts.naming_service_program, &spl_name_service::id()).unwrap();
-        check_account_key(accounts.root_domain, &ROOT_DOMAIN_ACCOUNT).unwrap();
         check_account_key(accounts.system_program, &system_program::id()).unwrap();
         check_account_key(accounts.central_state, &central_state::KEY).unwrap();
         check_account_key(accounts.rent_sysvar, &sysvar::rent::id()).unwrap();
@@ -105,6 +111,7 @@ pub fn process_create_reverse(
     params: Params,
 ) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
+    check_root_domain(accounts.root_domain, accounts.tld_opt)?;
 
     let mut parent = None;
     match (accounts.parent_name, accounts.parent_name_owner) {
@@ -114,7 +121,7 @@ pub fn process_create_reverse(
             check_signer(parent_name_owner).unwrap();
             let parent_hd =
                 NameRecordHeader::unpack_from_slice(&parent_name.data.borrow()).unwrap();
-            if parent_hd.parent_name != ROOT_DOMAIN_ACCOUNT {
+            if &parent_hd.parent_name != accounts.root_domain.key {
                 msg!("Invalid parent name");
                 return Err(ProgramError::InvalidArgument);
             }
@@ -123,7 +130,8 @@ pub fn process_create_reverse(
         _ => (),
     }
 
-    let name_account_key = get_name_key(&params.name, parent).unwrap();
+    let name_account_key =
+        get_name_key(&params.name, parent.or(Some(accounts.root_domain.key))).unwrap();
 
     let hashed_reverse_lookup = get_hashed_name(&name_account_key.to_string());
 
@@ -147,7 +155,7 @@ pub fn process_create_reverse(
             accounts.system_program,
             accounts.reverse_lookup,
             accounts.fee_payer,
-            params.name,
+            params.name.clone(),
             hashed_reverse_lookup,
             accounts.central_state,
             accounts.rent_sysvar,
@@ -156,10 +164,23 @@ pub fn process_create_reverse(
             accounts.parent_name_owner,
             params.metadata_url, // Pass the metadata URL
         )?;
+        Event::ReverseCreated {
+            name_account: name_account_key,
+            reverse_lookup: reverse_lookup_account_key,
+            name: params.name,
+        }
+        .emit();
     } else {
         msg!("Reverse lookup already exists. No-op");
         return Err(ProgramError::AccountAlreadyInitialized);
     }
 
+    RegistrationResult {
+        name_account: name_account_key,
+        reverse_lookup: reverse_lookup_account_key,
+        amount: 0,
+    }
+    .set_return_data();
+
     Ok(())
 }
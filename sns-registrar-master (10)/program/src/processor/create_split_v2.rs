@@ -17,18 +17,21 @@ use solana_program::{
     system_program, sysvar,
     sysvar::Sysvar,
 };
-use spl_name_service::state::{get_seeds_and_key, NameRecordHeader};
+use spl_name_service::state::NameRecordHeader;
 use spl_token::instruction::transfer;
 
+use super::create_split_v3;
+
 use crate::{
     central_state,
-    constants::{FIDA_MINT, REFERRER_FEES_PCT, REFERRER_WHITELIST, ROOT_DOMAIN_ACCOUNT},
+    composability::RegistrationResult,
     cpi::Cpi,
+    events::Event,
     utils::{
-        check_vault_token_account_owner, get_domain_price_checked, get_hashed_name, get_name_key,
-        get_special_discount_and_fee,
+        check_referrer_token_account, check_registration_accounts, check_root_domain, check_vault,
+        close_release_state, get_domain_price_checked, get_hashed_name, get_price_quote,
+        next_tld_account, write_registration_record,
     },
-    Error,
 };
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
@@ -40,12 +43,24 @@ pub struct Params {
     pub metadata_url: Option<String>, // New field for metadata URL
 }
 
+impl From<Params> for create_split_v3::Params {
+    fn from(value: Params) -> Self {
+        create_split_v3::Params {
+            name: value.name,
+            space: value.space,
+            referrer_idx_opt: value.referrer_idx_opt,
+            metadata_url: value.metadata_url,
+            years: 1,
+        }
+    }
+}
+
 #[derive(InstructionsAccount)]
 /// The required accounts for the `create` instruction
 pub struct Accounts<'a, T> {
     /// The naming service program ID
     pub naming_service_program: &'a T,
-    /// The root domain account       
+    /// The root domain account, the default root domain or the root of an enabled TLD
     pub root_domain: &'a T,
     /// The name account
     #[cons(writable)]
@@ -77,8 +92,12 @@ pub struct Accounts<'a, T> {
     pub spl_token_program: &'a T,
     /// The rent sysvar account
     pub rent_sysvar: &'a T,
-    /// The state auction account
+    /// The state auction account, holds the release premium of recently expired names
+    #[cons(writable)]
     pub state: &'a T,
+    /// The TLD account of the root domain, required when registering under another root than the
+    /// default one
+    pub tld_opt: Option<&'a T>,
     /// The *optional* referrer token account to receive a portion of fees.
     /// The token account owner has to be whitelisted.
     #[cons(writable)]
@@ -91,9 +110,11 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         _program_id: &Pubkey,
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
+        let naming_service_program = next_account_info(accounts_iter)?;
+        let root_domain = next_account_info(accounts_iter)?;
         Ok(Accounts {
-            naming_service_program: next_account_info(accounts_iter)?,
-            root_domain: next_account_info(accounts_iter)?,
+            naming_service_program,
+            root_domain,
             name: next_account_info(accounts_iter)?,
             reverse_lookup: next_account_info(accounts_iter)?,
             system_program: next_account_info(accounts_iter)?,
@@ -107,6 +128,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             spl_token_program: next_account_info(accounts_iter)?,
             rent_sysvar: next_account_info(accounts_iter)?,
             state: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
             referrer_account_opt: next_account_info(accounts_iter).ok(),
         })
     }
@@ -114,7 +136,6 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     pub fn check(&self) -> Result<(), ProgramError> {
         // Check keys
         check_account_key(self.naming_service_program, &spl_name_service::ID).unwrap();
-        check_account_key(self.root_domain, &ROOT_DOMAIN_ACCOUNT).unwrap();
         check_account_key(self.system_program, &system_program::ID).unwrap();
         check_account_key(self.central_state, &central_state::KEY).unwrap();
         check_account_key(self.spl_token_program, &spl_token::ID).unwrap();
@@ -124,7 +145,8 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         check_account_owner(self.name, &system_program::ID)
             .map_err(|_| crate::Error::AlreadyRegistered)?;
         check_account_owner(self.vault, &spl_token::ID).unwrap();
-        check_account_owner(self.state, &system_program::ID).unwrap();
+        check_account_owner(self.state, &system_program::ID)
+            .or_else(|_| check_account_owner(self.state, &crate::ID))?;
 
         // Check signer
         check_signer(self.buyer).unwrap();
@@ -140,106 +162,73 @@ pub fn process_create(
     params: Params,
 ) -> ProgramResult {
     let accounts = Accounts::parse(accounts, program_id)?;
-    create(program_id, accounts, params)
+    create(program_id, accounts, None, params.into())
 }
 
+/// Registers a domain name, names registered without `registration_record_opt` never expire
 pub fn create<'a, 'b: 'a>(
     program_id: &Pubkey,
     accounts: Accounts<'a, AccountInfo<'b>>,
-    params: Params,
+    registration_record_opt: Option<&'a AccountInfo<'b>>,
+    params: create_split_v3::Params,
 ) -> ProgramResult {
-    accounts.check()?;
-    check_vault_token_account_owner(accounts.vault).unwrap();
-
-    if params.name != params.name.trim().to_lowercase() {
-        msg!("Domain names must be lower case and have no space");
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    if params.name.contains('.') {
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    let name_account_key = get_name_key(&params.name, None).unwrap();
-
-    if &name_account_key != accounts.name.key {
-        msg!("Provided wrong name account");
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    let (state_key, _) = Pubkey::find_program_address(&[&name_account_key.to_bytes()], program_id);
-    if &state_key != accounts.state.key {
-        msg!("An invalid name auctioning state account was provided");
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    if !accounts.state.data_is_empty() {
-        msg!("The name auctioning state account is not empty.");
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    let hashed_reverse_lookup = get_hashed_name(&name_account_key.to_string());
-
-    let (reverse_lookup_account_key, _) = get_seeds_and_key(
-        accounts.naming_service_program.key,
-        hashed_reverse_lookup.clone(),
-        Some(&central_state::KEY),
-        None,
-    );
+    create_with_discount(program_id, accounts, registration_record_opt, params, 0)
+}
 
-    if &reverse_lookup_account_key != accounts.reverse_lookup.key {
-        msg!("Provided wrong reverse lookup account");
-        return Err(ProgramError::InvalidArgument);
-    }
+/// Registers a domain name, `discount_pct` is applied to the domain price before any other discount
+pub fn create_with_discount<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: Accounts<'a, AccountInfo<'b>>,
+    registration_record_opt: Option<&'a AccountInfo<'b>>,
+    params: create_split_v3::Params,
+    discount_pct: u8,
+) -> ProgramResult {
+    accounts.check()?;
+    let tld = check_root_domain(accounts.root_domain, accounts.tld_opt)?;
+    check_vault(accounts.vault, tld.as_ref())?;
+    let name_account_key = check_registration_accounts(
+        program_id,
+        accounts.root_domain.key,
+        &params.name,
+        accounts.name,
+        accounts.reverse_lookup,
+        accounts.state,
+        registration_record_opt,
+    )?;
+    let reverse_lookup_account_key = *accounts.reverse_lookup.key;
 
     let central_state_signer_seeds: &[&[u8]] = &[&program_id.to_bytes(), &[central_state::NONCE]];
 
-    let mut domain_token_price = get_domain_price_checked(&params.name, &accounts)?;
-
-    // 5% discount if paid in FIDA
     let token_acc = spl_token::state::Account::unpack(&accounts.buyer_token_source.data.borrow())?;
-    if token_acc.mint == FIDA_MINT {
-        domain_token_price = domain_token_price.checked_mul(95).ok_or(Error::Overflow)? / 100;
-    }
-
-    //Check referrer token account owner and transfer fees
-    let referrer_fees = if let Some(referrer_account) = accounts.referrer_account_opt {
-        check_account_owner(referrer_account, &spl_token::ID)?;
-        let mut referrer_fee_pct = REFERRER_FEES_PCT;
-        let referrer_token_acc =
-            spl_token::state::Account::unpack(&referrer_account.data.borrow()).unwrap();
-
-        #[cfg(not(feature = "no-referrer-check"))]
-        if *REFERRER_WHITELIST
-            .get(params.referrer_idx_opt.unwrap() as usize)
-            .unwrap()
-            != referrer_token_acc.owner
-        {
-            msg!("Referrer token account owner is not whitelisted.");
-            return Err(ProgramError::IllegalOwner);
-        }
-
-        let (discount, special_fee) = get_special_discount_and_fee(&referrer_token_acc.owner);
-        if let Some(discount) = discount {
-            domain_token_price = 100u64
-                .checked_sub(discount as u64)
-                .ok_or(Error::Overflow)?
-                .checked_mul(domain_token_price)
-                .ok_or(Error::Overflow)?
-                / 100;
-        }
-        if let Some(special_fee) = special_fee {
-            referrer_fee_pct = special_fee as u64
-        }
+    let domain_token_price = get_domain_price_checked(
+        &params.name,
+        accounts.state,
+        &(&accounts).into(),
+        tld.as_ref(),
+        params.years,
+    )?;
+    let referrer_opt = accounts
+        .referrer_account_opt
+        .map(|referrer_account| {
+            check_referrer_token_account(referrer_account, params.referrer_idx_opt)
+        })
+        .transpose()?;
+    let quote = get_price_quote(
+        domain_token_price,
+        &token_acc.mint,
+        discount_pct,
+        referrer_opt.as_ref(),
+    )?;
 
-        let referrer_fees_amount = domain_token_price.checked_mul(referrer_fee_pct).unwrap() / 100;
+    // Transfer fees to the referrer
+    if let Some(referrer_account) = accounts.referrer_account_opt {
         let transfer_ix = transfer(
             &spl_token::ID,
             accounts.buyer_token_source.key,
             referrer_account.key,
             accounts.buyer.key,
             &[],
-            referrer_fees_amount,
+            quote.referrer_fee,
         )?;
 
         invoke(
@@ -252,10 +241,14 @@ pub fn create<'a, 'b: 'a>(
             ],
         )?;
 
-        referrer_fees_amount
-    } else {
-        0
-    };
+        Event::ReferrerPaid {
+            name_account: name_account_key,
+            referrer: *referrer_account.key,
+            mint: token_acc.mint,
+            amount: quote.referrer_fee,
+        }
+        .emit();
+    }
 
     // Transfer tokens to vault
     let transfer_ix = transfer(
@@ -264,7 +257,7 @@ pub fn create<'a, 'b: 'a>(
         accounts.vault.key,
         accounts.buyer.key,
         &[],
-        domain_token_price.checked_sub(referrer_fees).unwrap(),
+        quote.vault_amount,
     )?;
 
     invoke(
@@ -286,7 +279,7 @@ pub fn create<'a, 'b: 'a>(
         accounts.name,
         accounts.fee_payer,
         accounts.domain_owner,
-        accounts.root_domain,
+        Some(accounts.root_domain),
         accounts.central_state,
         hashed_name,
         rent.minimum_balance(NameRecordHeader::LEN + params.space as usize),
@@ -294,6 +287,34 @@ pub fn create<'a, 'b: 'a>(
         central_state_signer_seeds,
     )?;
 
+    // Close the release premium
+    close_release_state(accounts.state, accounts.fee_payer);
+
+    // Registration term
+    let expiry = registration_record_opt
+        .map(|registration_record| {
+            write_registration_record(
+                program_id,
+                accounts.system_program,
+                accounts.fee_payer,
+                registration_record,
+                &name_account_key,
+                params.years,
+            )
+        })
+        .transpose()?;
+
+    Event::Registered {
+        name_account: name_account_key,
+        owner: *accounts.domain_owner.key,
+        root_domain: *accounts.root_domain.key,
+        name: params.name.clone(),
+        mint: token_acc.mint,
+        amount: quote.total()?,
+        expiry,
+    }
+    .emit();
+
     // Reverse look up
     if accounts.reverse_lookup.data_len() == 0 {
         Cpi::create_reverse_lookup_account(
@@ -301,8 +322,8 @@ pub fn create<'a, 'b: 'a>(
             accounts.system_program,
             accounts.reverse_lookup,
             accounts.fee_payer,
-            params.name,
-            hashed_reverse_lookup,
+            params.name.clone(),
+            get_hashed_name(&name_account_key.to_string()),
             accounts.central_state,
             accounts.rent_sysvar,
             central_state_signer_seeds,
@@ -310,6 +331,19 @@ pub fn create<'a, 'b: 'a>(
             None,
             params.metadata_url, // Pass the metadata URL
         )?;
+        Event::ReverseCreated {
+            name_account: name_account_key,
+            reverse_lookup: reverse_lookup_account_key,
+            name: params.name,
+        }
+        .emit();
+    }
+    RegistrationResult {
+        name_account: name_account_key,
+        reverse_lookup: reverse_lookup_account_key,
+        amount: quote.total()?,
     }
+    .set_return_data();
+
     Ok(())
 }
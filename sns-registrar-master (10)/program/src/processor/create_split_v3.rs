@@ -0,0 +1,146 @@
+//! Create a domain name for a registration term of several years
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use super::create_split_v2;
+use crate::utils::next_tld_account;
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `create_split_v3` instruction
+pub struct Params {
+    pub name: String,
+    pub space: u32,
+    pub referrer_idx_opt: Option<u16>,
+    pub metadata_url: Option<String>,
+    /// Number of years of the registration, from 1 to `MAX_REGISTRATION_YEARS`
+    pub years: u8,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `create_split_v3` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The root domain account, the default root domain or the root of an enabled TLD
+    pub root_domain: &'a T,
+    /// The name account
+    #[cons(writable)]
+    pub name: &'a T,
+    /// The reverse look up account
+    #[cons(writable)]
+    pub reverse_lookup: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The buyer account
+    #[cons(writable, signer)]
+    pub buyer: &'a T,
+    /// The registered domain owner
+    pub domain_owner: &'a T,
+    /// The solana fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+    /// The buyer token account
+    #[cons(writable)]
+    pub buyer_token_source: &'a T,
+    /// The Pyth feed account
+    pub pyth_feed_account: &'a T,
+    /// The vault account
+    #[cons(writable)]
+    pub vault: &'a T,
+    /// The SPL token program
+    pub spl_token_program: &'a T,
+    /// The rent sysvar account
+    pub rent_sysvar: &'a T,
+    /// The state auction account, holds the release premium of recently expired names
+    #[cons(writable)]
+    pub state: &'a T,
+    /// The registration record account, holds the expiry of the name
+    #[cons(writable)]
+    pub registration_record: &'a T,
+    /// The TLD account of the root domain, required when registering under another root than the
+    /// default one
+    pub tld_opt: Option<&'a T>,
+    /// The *optional* referrer token account to receive a portion of fees.
+    /// The token account owner has to be whitelisted.
+    #[cons(writable)]
+    pub referrer_account_opt: Option<&'a T>,
+}
+
+impl<'a, T> From<Accounts<'a, T>> for create_split_v2::Accounts<'a, T> {
+    fn from(value: Accounts<'a, T>) -> Self {
+        Self {
+            naming_service_program: value.naming_service_program,
+            root_domain: value.root_domain,
+            name: value.name,
+            reverse_lookup: value.reverse_lookup,
+            system_program: value.system_program,
+            central_state: value.central_state,
+            buyer: value.buyer,
+            domain_owner: value.domain_owner,
+            fee_payer: value.fee_payer,
+            buyer_token_source: value.buyer_token_source,
+            pyth_feed_account: value.pyth_feed_account,
+            vault: value.vault,
+            spl_token_program: value.spl_token_program,
+            rent_sysvar: value.rent_sysvar,
+            state: value.state,
+            tld_opt: value.tld_opt,
+            referrer_account_opt: value.referrer_account_opt,
+        }
+    }
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let naming_service_program = next_account_info(accounts_iter)?;
+        let root_domain = next_account_info(accounts_iter)?;
+        Ok(Accounts {
+            naming_service_program,
+            root_domain,
+            name: next_account_info(accounts_iter)?,
+            reverse_lookup: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            buyer: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            buyer_token_source: next_account_info(accounts_iter)?,
+            pyth_feed_account: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            rent_sysvar: next_account_info(accounts_iter)?,
+            state: next_account_info(accounts_iter)?,
+            registration_record: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
+            referrer_account_opt: next_account_info(accounts_iter).ok(),
+        })
+    }
+}
+
+pub fn process_create(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let registration_record = accounts.registration_record;
+    create_split_v2::create(
+        program_id,
+        accounts.into(),
+        Some(registration_record),
+        params,
+    )
+}
@@ -0,0 +1,166 @@
+//! Create the root name account of a new top level domain and register it
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program, sysvar,
+    sysvar::Sysvar,
+};
+use spl_name_service::state::{get_seeds_and_key, NameRecordHeader};
+
+use super::configure_tld;
+use crate::{
+    central_state,
+    constants::ADMIN,
+    cpi::Cpi,
+    utils::{get_hashed_name, get_reverse_key},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `create_tld` instruction
+pub struct Params {
+    /// The top level domain, without the leading dot
+    pub tld: String,
+    pub config: configure_tld::Params,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `create_tld` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The root name account of the TLD
+    #[cons(writable)]
+    pub root_domain: &'a T,
+    /// The reverse look up account of the root name account
+    #[cons(writable)]
+    pub reverse_lookup: &'a T,
+    /// The TLD account
+    #[cons(writable)]
+    pub tld: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The rent sysvar account
+    pub rent_sysvar: &'a T,
+    /// The admin account
+    #[cons(writable, signer)]
+    pub admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            naming_service_program: next_account_info(accounts_iter)?,
+            root_domain: next_account_info(accounts_iter)?,
+            reverse_lookup: next_account_info(accounts_iter)?,
+            tld: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            rent_sysvar: next_account_info(accounts_iter)?,
+            admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+        check_account_key(accounts.rent_sysvar, &sysvar::rent::ID)?;
+        check_account_key(accounts.admin, &ADMIN)?;
+
+        // Check ownership
+        // Root names have no parent to sign their creation, anyone can create the root name of a TLD
+        // before this instruction. Such a root has to be transferred to the central state and
+        // registered with `ConfigureTld` instead.
+        check_account_owner(accounts.root_domain, &system_program::ID)
+            .map_err(|_| crate::Error::AlreadyRegistered)?;
+        check_account_owner(accounts.tld, &system_program::ID)?;
+
+        // Check signer
+        check_signer(accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_create_tld(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    if params.tld.is_empty()
+        || params.tld != params.tld.trim().to_lowercase()
+        || params.tld.contains('.')
+    {
+        msg!("TLDs must be lower case and have no space or dot");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let hashed_name = get_hashed_name(&params.tld);
+    let (root_domain_key, _) =
+        get_seeds_and_key(&spl_name_service::ID, hashed_name.clone(), None, None);
+    check_account_key(accounts.root_domain, &root_domain_key)?;
+    check_account_key(
+        accounts.reverse_lookup,
+        &get_reverse_key(&root_domain_key, None)?,
+    )?;
+
+    msg!("[+] Creating root name account of .{}", params.tld);
+    let central_state_signer_seeds: &[&[u8]] = &[&program_id.to_bytes(), &[central_state::NONCE]];
+    Cpi::create_name_account(
+        accounts.naming_service_program,
+        accounts.system_program,
+        accounts.root_domain,
+        accounts.admin,
+        accounts.central_state,
+        None,
+        accounts.central_state,
+        hashed_name,
+        Rent::get()?.minimum_balance(NameRecordHeader::LEN),
+        0,
+        central_state_signer_seeds,
+    )?;
+    if accounts.reverse_lookup.data_len() == 0 {
+        Cpi::create_reverse_lookup_account(
+            accounts.naming_service_program,
+            accounts.system_program,
+            accounts.reverse_lookup,
+            accounts.admin,
+            params.tld,
+            get_hashed_name(&root_domain_key.to_string()),
+            accounts.central_state,
+            accounts.rent_sysvar,
+            central_state_signer_seeds,
+            None,
+            None,
+            None,
+        )?;
+    }
+
+    configure_tld::configure_tld(
+        program_id,
+        &configure_tld::Accounts {
+            tld: accounts.tld,
+            root_domain: accounts.root_domain,
+            system_program: accounts.system_program,
+            admin: accounts.admin,
+        },
+        params.config,
+    )
+}
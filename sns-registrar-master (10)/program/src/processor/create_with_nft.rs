@@ -1,14 +1,23 @@
-//! Create a domain name with a wolf
+//! Create a domain name with an NFT of a campaign collection
 
 use std::convert::TryFrom;
 
 use crate::{
     central_state,
-    constants::{ROOT_DOMAIN_ACCOUNT, WOLVES_COLLECTION, WOLVES_COLLECTION_METADATA},
+    composability::RegistrationResult,
+    constants::ROOT_DOMAIN_ACCOUNT,
     cpi::Cpi,
     error::Error,
+    events::Event,
+    state::{
+        nft_campaign::{NftCampaign, NftCampaignMode, NftClaim},
+        Tag,
+    },
     utils::get_hashed_name,
-    utils::get_name_key,
+    utils::{
+        check_no_release_premium, close_release_state, get_grapheme_len, get_name_key,
+        write_registration_record,
+    },
 };
 use bonfida_utils::{
     checks::{check_account_key, check_account_owner, check_signer},
@@ -79,16 +88,26 @@ pub struct Accounts<'a, T> {
     pub spl_token_program: &'a T,
     /// The rent sysvar account
     pub rent_sysvar: &'a T,
-    /// The state auction account
+    /// The state auction account, closed if it holds a release whose premium has decayed
+    #[cons(writable)]
     pub state: &'a T,
     /// Metaplex token metadata program account
     pub mpl_token_metadata: &'a T,
+    /// The NFT campaign account of the collection
+    #[cons(writable)]
+    pub nft_campaign: &'a T,
+    /// The registration record account, names created with an NFT are registered for a year
+    #[cons(writable)]
+    pub registration_record: &'a T,
+    /// The *optional* claim account of the NFT, required by campaigns locking NFTs
+    #[cons(writable)]
+    pub nft_claim_opt: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     pub fn parse(
         accounts: &'a [AccountInfo<'b>],
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Accounts {
@@ -108,6 +127,9 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             rent_sysvar: next_account_info(accounts_iter)?,
             state: next_account_info(accounts_iter)?,
             mpl_token_metadata: next_account_info(accounts_iter)?,
+            nft_campaign: next_account_info(accounts_iter)?,
+            registration_record: next_account_info(accounts_iter)?,
+            nft_claim_opt: next_account_info(accounts_iter).ok(),
         };
 
         // Check keys
@@ -115,7 +137,6 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         check_account_key(accounts.root_domain, &ROOT_DOMAIN_ACCOUNT).unwrap();
         check_account_key(accounts.system_program, &system_program::ID).unwrap();
         check_account_key(accounts.central_state, &central_state::KEY).unwrap();
-        check_account_key(accounts.collection_metadata, &WOLVES_COLLECTION_METADATA).unwrap();
         check_account_key(accounts.spl_token_program, &spl_token::ID).unwrap();
         check_account_key(accounts.rent_sysvar, &sysvar::rent::ID).unwrap();
         check_account_key(accounts.mpl_token_metadata, &mpl_token_metadata::ID).unwrap();
@@ -123,12 +144,14 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         // Check ownership
         check_account_owner(accounts.name, &system_program::ID)
             .map_err(|_| crate::Error::AlreadyRegistered)?;
-        check_account_owner(accounts.state, &system_program::ID).unwrap();
+        check_account_owner(accounts.state, &system_program::ID)
+            .or_else(|_| check_account_owner(accounts.state, program_id))?;
         check_account_owner(accounts.nft_source, &spl_token::ID).unwrap();
         check_account_owner(accounts.nft_metadata, &mpl_token_metadata::ID).unwrap();
         check_account_owner(accounts.nft_mint, &spl_token::ID).unwrap();
         check_account_owner(accounts.master_edition, &mpl_token_metadata::ID).unwrap();
         check_account_owner(accounts.collection_metadata, &mpl_token_metadata::ID).unwrap();
+        check_account_owner(accounts.nft_campaign, program_id)?;
 
         // Check signer
         check_signer(accounts.buyer).unwrap();
@@ -164,10 +187,8 @@ pub fn process_create_with_nft(
         return Err(ProgramError::InvalidArgument);
     }
 
-    if !accounts.state.data_is_empty() {
-        msg!("The name auctioning state account is not empty.");
-        return Err(ProgramError::InvalidArgument);
-    }
+    // Released names can only be claimed for free once their premium has decayed
+    check_no_release_premium(accounts.state)?;
 
     let hashed_reverse_lookup = get_hashed_name(&name_account_key.to_string());
 
@@ -193,29 +214,87 @@ pub fn process_create_with_nft(
     check_account_key(accounts.master_edition, &master_edition_key)?;
     check_account_key(accounts.nft_metadata, &metadata_key)?;
 
+    let mut campaign = NftCampaign::from_account_info(accounts.nft_campaign)?;
+    check_account_key(
+        accounts.collection_metadata,
+        &Metadata::find_pda(&campaign.collection).0,
+    )?;
+
     let metadata = Metadata::try_from(accounts.nft_metadata)?;
 
     if let Some(collection) = metadata.collection {
-        if !collection.verified || collection.key != WOLVES_COLLECTION {
+        if !collection.verified || collection.key != campaign.collection {
             return Err(Error::WrongCollection.into());
         }
     } else {
         return Err(Error::WrongCollection.into());
     }
 
-    BurnNftCpi::new(
-        accounts.mpl_token_metadata,
-        BurnNftCpiAccounts {
-            spl_token_program: accounts.spl_token_program,
-            metadata: accounts.nft_metadata,
-            owner: accounts.buyer,
-            master_edition_account: accounts.master_edition,
-            mint: accounts.nft_mint,
-            token_account: accounts.nft_source,
-            collection_metadata: Some(accounts.collection_metadata),
-        },
-    )
-    .invoke()?;
+    let nft_token_acc = spl_token::state::Account::unpack(&accounts.nft_source.data.borrow())?;
+    if nft_token_acc.mint != *accounts.nft_mint.key
+        || nft_token_acc.owner != *accounts.buyer.key
+        || nft_token_acc.amount != 1
+    {
+        msg!("The buyer does not hold the NFT");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if get_grapheme_len(&params.name) > campaign.max_name_length as usize {
+        return Err(Error::NameTooLong.into());
+    }
+    if campaign.claims >= campaign.max_claims {
+        return Err(Error::CampaignExhausted.into());
+    }
+    campaign.claims += 1;
+    campaign.save(&mut accounts.nft_campaign.data.borrow_mut());
+
+    match campaign.mode {
+        NftCampaignMode::Burn => {
+            BurnNftCpi::new(
+                accounts.mpl_token_metadata,
+                BurnNftCpiAccounts {
+                    spl_token_program: accounts.spl_token_program,
+                    metadata: accounts.nft_metadata,
+                    owner: accounts.buyer,
+                    master_edition_account: accounts.master_edition,
+                    mint: accounts.nft_mint,
+                    token_account: accounts.nft_source,
+                    collection_metadata: Some(accounts.collection_metadata),
+                },
+            )
+            .invoke()?;
+        }
+        NftCampaignMode::Lock => {
+            let nft_claim_account = accounts
+                .nft_claim_opt
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let (nft_claim_key, nft_claim_nonce) =
+                NftClaim::find_key(accounts.nft_campaign.key, accounts.nft_mint.key, program_id);
+            check_account_key(nft_claim_account, &nft_claim_key)?;
+            if !nft_claim_account.data_is_empty() {
+                return Err(Error::NftAlreadyClaimed.into());
+            }
+            let nft_claim = NftClaim {
+                tag: Tag::NftClaim,
+                name_account: name_account_key,
+            };
+            Cpi::create_account(
+                program_id,
+                accounts.system_program,
+                accounts.buyer,
+                nft_claim_account,
+                &[
+                    NftClaim::SEED,
+                    &accounts.nft_campaign.key.to_bytes(),
+                    &accounts.nft_mint.key.to_bytes(),
+                    &[nft_claim_nonce],
+                ],
+                nft_claim.borsh_len(),
+            )?;
+            nft_claim.save(&mut nft_claim_account.data.borrow_mut());
+        }
+        NftCampaignMode::Check => (),
+    }
 
     // Create domain name
     let rent = Rent::get()?;
@@ -226,13 +305,32 @@ pub fn process_create_with_nft(
         accounts.name,
         accounts.buyer,
         accounts.buyer,
-        accounts.root_domain,
+        Some(accounts.root_domain),
         accounts.central_state,
         hashed_name,
         rent.minimum_balance(NameRecordHeader::LEN + params.space as usize),
         params.space,
         central_state_signer_seeds,
     )?;
+    close_release_state(accounts.state, accounts.buyer);
+    let expiry = write_registration_record(
+        program_id,
+        accounts.system_program,
+        accounts.buyer,
+        accounts.registration_record,
+        &name_account_key,
+        1,
+    )?;
+    Event::Registered {
+        name_account: name_account_key,
+        owner: *accounts.buyer.key,
+        root_domain: *accounts.root_domain.key,
+        name: params.name.clone(),
+        mint: Pubkey::default(),
+        amount: 0,
+        expiry: Some(expiry),
+    }
+    .emit();
 
     // Reverse look up
     if accounts.reverse_lookup.data_len() == 0 {
@@ -241,7 +339,7 @@ pub fn process_create_with_nft(
             accounts.system_program,
             accounts.reverse_lookup,
             accounts.buyer,
-            params.name,
+            params.name.clone(),
             hashed_reverse_lookup,
             accounts.central_state,
             accounts.rent_sysvar,
@@ -250,6 +348,19 @@ pub fn process_create_with_nft(
             None,
             params.metadata_url, // Add the missing metadata_url parameter
         )?;
+        Event::ReverseCreated {
+            name_account: name_account_key,
+            reverse_lookup: reverse_lookup_account_key,
+            name: params.name,
+        }
+        .emit();
+    }
+    RegistrationResult {
+        name_account: name_account_key,
+        reverse_lookup: reverse_lookup_account_key,
+        amount: 0,
     }
+    .set_return_data();
+
     Ok(())
 }
@@ -0,0 +1,240 @@
+//! Create a domain name with a voucher signed by the voucher authority
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program, sysvar,
+    sysvar::Sysvar,
+};
+
+use super::{create_split_v2, create_split_v3};
+use crate::{
+    constants::VOUCHER_AUTHORITY,
+    cpi::Cpi,
+    error::Error,
+    state::{
+        voucher::{Voucher, VoucherNonce},
+        Tag,
+    },
+    utils::{check_ed25519_signature, get_name_key, next_tld_account},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `create_with_voucher` instruction
+pub struct Params {
+    pub name: String,
+    pub space: u32,
+    pub referrer_idx_opt: Option<u16>,
+    pub metadata_url: Option<String>,
+    /// The voucher, its signature is verified by the preceding Ed25519 program instruction
+    pub voucher: Voucher,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `create_with_voucher` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The root domain account, the default root domain or the root of an enabled TLD
+    pub root_domain: &'a T,
+    /// The name account
+    #[cons(writable)]
+    pub name: &'a T,
+    /// The reverse look up account
+    #[cons(writable)]
+    pub reverse_lookup: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The buyer account
+    #[cons(writable, signer)]
+    pub buyer: &'a T,
+    /// The registered domain owner, has to be the beneficiary of the voucher
+    pub domain_owner: &'a T,
+    /// The solana fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+    /// The buyer token account
+    #[cons(writable)]
+    pub buyer_token_source: &'a T,
+    /// The Pyth feed account
+    pub pyth_feed_account: &'a T,
+    /// The vault account
+    #[cons(writable)]
+    pub vault: &'a T,
+    /// The SPL token program
+    pub spl_token_program: &'a T,
+    /// The rent sysvar account
+    pub rent_sysvar: &'a T,
+    /// The state auction account, holds the release premium of recently expired names
+    #[cons(writable)]
+    pub state: &'a T,
+    /// The instructions sysvar account
+    pub instructions_sysvar: &'a T,
+    /// The account marking the voucher as redeemed
+    #[cons(writable)]
+    pub voucher_nonce: &'a T,
+    /// The TLD account of the root domain, required when registering under another root than the
+    /// default one
+    pub tld_opt: Option<&'a T>,
+    /// The *optional* referrer token account to receive a portion of fees.
+    /// The token account owner has to be whitelisted.
+    #[cons(writable)]
+    pub referrer_account_opt: Option<&'a T>,
+    /// The registration record account, holds the expiry of the name. Passed after the optional
+    /// accounts to keep the account indices of earlier clients.
+    #[cons(writable)]
+    pub registration_record: &'a T,
+}
+
+impl<'a, T> From<Accounts<'a, T>> for create_split_v2::Accounts<'a, T> {
+    fn from(value: Accounts<'a, T>) -> Self {
+        Self {
+            naming_service_program: value.naming_service_program,
+            root_domain: value.root_domain,
+            name: value.name,
+            reverse_lookup: value.reverse_lookup,
+            system_program: value.system_program,
+            central_state: value.central_state,
+            buyer: value.buyer,
+            domain_owner: value.domain_owner,
+            fee_payer: value.fee_payer,
+            buyer_token_source: value.buyer_token_source,
+            pyth_feed_account: value.pyth_feed_account,
+            vault: value.vault,
+            spl_token_program: value.spl_token_program,
+            rent_sysvar: value.rent_sysvar,
+            state: value.state,
+            tld_opt: value.tld_opt,
+            referrer_account_opt: value.referrer_account_opt,
+        }
+    }
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let naming_service_program = next_account_info(accounts_iter)?;
+        let root_domain = next_account_info(accounts_iter)?;
+        let mut accounts = Accounts {
+            naming_service_program,
+            root_domain,
+            name: next_account_info(accounts_iter)?,
+            reverse_lookup: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            buyer: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            buyer_token_source: next_account_info(accounts_iter)?,
+            pyth_feed_account: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            rent_sysvar: next_account_info(accounts_iter)?,
+            state: next_account_info(accounts_iter)?,
+            instructions_sysvar: next_account_info(accounts_iter)?,
+            voucher_nonce: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
+            referrer_account_opt: None,
+            registration_record: accounts.last().ok_or(ProgramError::NotEnoughAccountKeys)?,
+        };
+        // The registration record is the last account, the optional accounts come before it
+        let (_, optional_accounts) = accounts_iter
+            .as_slice()
+            .split_last()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        accounts.referrer_account_opt = optional_accounts.first();
+
+        // Check keys
+        check_account_key(accounts.instructions_sysvar, &sysvar::instructions::ID)?;
+
+        // Check ownership
+        check_account_owner(accounts.voucher_nonce, &system_program::ID)
+            .map_err(|_| Error::VoucherAlreadyRedeemed)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_create_with_voucher(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let Params {
+        name,
+        space,
+        referrer_idx_opt,
+        metadata_url,
+        voucher,
+    } = params;
+
+    check_ed25519_signature(
+        accounts.instructions_sysvar,
+        &VOUCHER_AUTHORITY,
+        &voucher.message(program_id),
+    )?;
+
+    if voucher.expiry < Clock::get()?.unix_timestamp {
+        return Err(Error::VoucherExpired.into());
+    }
+    if !voucher.applies_to(&name) {
+        msg!("The voucher cannot be redeemed for this domain name");
+        return Err(Error::InvalidVoucher.into());
+    }
+    if voucher.discount_pct > 100 {
+        return Err(Error::InvalidVoucher.into());
+    }
+    check_account_key(accounts.domain_owner, &voucher.beneficiary)?;
+
+    let (voucher_nonce_key, voucher_nonce_bump) = VoucherNonce::find_key(voucher.nonce, program_id);
+    check_account_key(accounts.voucher_nonce, &voucher_nonce_key)?;
+
+    msg!("[+] Redeeming voucher {}", voucher.nonce);
+    let voucher_nonce = VoucherNonce {
+        tag: Tag::VoucherNonce,
+        name_account: get_name_key(&name, Some(accounts.root_domain.key))?,
+    };
+    Cpi::create_account(
+        program_id,
+        accounts.system_program,
+        accounts.fee_payer,
+        accounts.voucher_nonce,
+        &[
+            VoucherNonce::SEED,
+            &voucher.nonce.to_le_bytes(),
+            &[voucher_nonce_bump],
+        ],
+        voucher_nonce.borsh_len(),
+    )?;
+    voucher_nonce.save(&mut accounts.voucher_nonce.data.borrow_mut());
+
+    let registration_record = accounts.registration_record;
+    create_split_v2::create_with_discount(
+        program_id,
+        accounts.into(),
+        Some(registration_record),
+        create_split_v3::Params {
+            name,
+            space,
+            referrer_idx_opt,
+            metadata_url,
+            years: 1,
+        },
+        voucher.discount_pct,
+    )
+}
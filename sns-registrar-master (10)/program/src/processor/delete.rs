@@ -2,7 +2,7 @@
 
 use solana_program::program::{invoke, invoke_signed};
 
-use crate::{central_state, utils::get_reverse_key};
+use crate::{central_state, events::Event, utils::get_reverse_key};
 use {
     bonfida_utils::{
         checks::{check_account_key, check_account_owner, check_signer},
@@ -155,5 +155,11 @@ pub fn process_delete(
         **source = 0;
     }
 
+    Event::Deleted {
+        name_account: *accounts.domain.key,
+        reverse_lookup: reverse_key,
+    }
+    .emit();
+
     Ok(())
 }
@@ -0,0 +1,107 @@
+//! Delete an address record of a domain name
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    central_state,
+    cpi::Cpi,
+    events::Event,
+    state::record::Record,
+    utils::{check_domain_owner, get_record_key},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `delete_record` instruction
+pub struct Params {
+    pub record: Record,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `delete_record` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The domain name account
+    pub domain: &'a T,
+    /// The record account
+    #[cons(writable)]
+    pub record: &'a T,
+    /// The owner of the domain name
+    #[cons(signer)]
+    pub domain_owner: &'a T,
+    /// The account receiving the rent of the record account
+    #[cons(writable)]
+    pub refund_target: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            naming_service_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            refund_target: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+
+        // Check ownership
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.domain_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_delete_record(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    check_domain_owner(accounts.domain, accounts.domain_owner)?;
+    check_account_key(
+        accounts.record,
+        &get_record_key(accounts.domain.key, params.record),
+    )?;
+
+    let central_state_signer_seeds: &[&[u8]] = &[&program_id.to_bytes(), &[central_state::NONCE]];
+    Cpi::delete_name_account(
+        accounts.naming_service_program,
+        accounts.record,
+        accounts.central_state,
+        accounts.refund_target,
+        central_state_signer_seeds,
+    )?;
+
+    Event::RecordDeleted {
+        domain: *accounts.domain.key,
+        record: params.record,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,224 @@
+//! Place a bid on a domain name auction
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use spl_token::instruction::transfer;
+
+use crate::{
+    constants::REFERRER_FEES_PCT,
+    cpi::Cpi,
+    events::Event,
+    state::auction::Auction,
+    utils::{check_referrer_token_account, get_referrer_fee_pct},
+    Error,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `place_bid` instruction
+pub struct Params {
+    /// Amount of the bid, in the quote token of the auction
+    pub amount: u64,
+    pub referrer_idx_opt: Option<u16>,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `place_bid` instruction
+pub struct Accounts<'a, T> {
+    /// The auction account
+    #[cons(writable)]
+    pub auction: &'a T,
+    /// The escrow token account of the auction
+    #[cons(writable)]
+    pub escrow: &'a T,
+    /// The bidder account, pays for the associated token account of the previous highest bidder
+    #[cons(writable, signer)]
+    pub bidder: &'a T,
+    /// The bidder token account
+    #[cons(writable)]
+    pub bidder_token_source: &'a T,
+    /// The previous highest bidder, ignored for the first bid of an auction
+    pub previous_bidder: &'a T,
+    /// The associated token account of the previous highest bidder, refunded with the previous
+    /// bid and created if it does not exist
+    #[cons(writable)]
+    pub previous_bidder_ata: &'a T,
+    /// The mint in which bids are denominated
+    pub quote_mint: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The SPL token program
+    pub spl_token_program: &'a T,
+    /// The SPL associated token account program
+    pub associated_token_program: &'a T,
+    /// The *optional* referrer token account to receive a portion of the proceeds.
+    /// The token account owner has to be whitelisted, with a fee of at most the default one.
+    pub referrer_account_opt: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            auction: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            bidder: next_account_info(accounts_iter)?,
+            bidder_token_source: next_account_info(accounts_iter)?,
+            previous_bidder: next_account_info(accounts_iter)?,
+            previous_bidder_ata: next_account_info(accounts_iter)?,
+            quote_mint: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            associated_token_program: next_account_info(accounts_iter)?,
+            referrer_account_opt: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+        check_account_key(
+            accounts.associated_token_program,
+            &spl_associated_token_account::ID,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.auction, program_id)?;
+        check_account_owner(accounts.escrow, &spl_token::ID)?;
+
+        // Check signer
+        check_signer(accounts.bidder)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_place_bid(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let mut auction = Auction::from_account_info(accounts.auction)?;
+    check_account_key(accounts.escrow, &auction.escrow)?;
+    check_account_key(accounts.quote_mint, &auction.quote_mint)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    if now >= auction.end_time {
+        return Err(Error::AuctionEnded.into());
+    }
+    if params.amount < auction.min_next_bid()? {
+        msg!("The bid has to be at least {}", auction.min_next_bid()?);
+        return Err(Error::BidTooLow.into());
+    }
+
+    // Referrers with a special fee above the default one are excluded from auctions
+    let (referrer, referrer_owner, referrer_fee_pct) =
+        if let Some(referrer_account) = accounts.referrer_account_opt {
+            let referrer_owner =
+                check_referrer_token_account(referrer_account, params.referrer_idx_opt)?;
+            let referrer_fee_pct = get_referrer_fee_pct(&referrer_owner);
+            if referrer_fee_pct > REFERRER_FEES_PCT {
+                return Err(Error::ReferrerFeeTooHigh.into());
+            }
+            (*referrer_account.key, referrer_owner, referrer_fee_pct)
+        } else {
+            (Pubkey::default(), Pubkey::default(), 0)
+        };
+
+    // Refund the outbid bidder to an associated token account they cannot close or redirect
+    if auction.highest_bid != 0 {
+        check_account_key(accounts.previous_bidder, &auction.highest_bidder)?;
+        check_account_key(
+            accounts.previous_bidder_ata,
+            &get_associated_token_address(&auction.highest_bidder, &auction.quote_mint),
+        )?;
+        let create_ata_ix = create_associated_token_account_idempotent(
+            accounts.bidder.key,
+            &auction.highest_bidder,
+            &auction.quote_mint,
+            &spl_token::ID,
+        );
+        invoke(
+            &create_ata_ix,
+            &[
+                accounts.associated_token_program.clone(),
+                accounts.bidder.clone(),
+                accounts.previous_bidder_ata.clone(),
+                accounts.previous_bidder.clone(),
+                accounts.quote_mint.clone(),
+                accounts.system_program.clone(),
+                accounts.spl_token_program.clone(),
+            ],
+        )?;
+        let auction_signer_seeds: &[&[u8]] = &[&auction.name_account.to_bytes(), &[auction.nonce]];
+        Cpi::transfer_tokens_signed(
+            accounts.spl_token_program,
+            accounts.escrow,
+            accounts.previous_bidder_ata,
+            accounts.auction,
+            auction.highest_bid,
+            auction_signer_seeds,
+        )?;
+    }
+
+    // Escrow the new bid
+    let transfer_ix = transfer(
+        &spl_token::ID,
+        accounts.bidder_token_source.key,
+        accounts.escrow.key,
+        accounts.bidder.key,
+        &[],
+        params.amount,
+    )?;
+    invoke(
+        &transfer_ix,
+        &[
+            accounts.spl_token_program.clone(),
+            accounts.bidder_token_source.clone(),
+            accounts.escrow.clone(),
+            accounts.bidder.clone(),
+        ],
+    )?;
+
+    auction.highest_bid = params.amount;
+    auction.highest_bidder = *accounts.bidder.key;
+    auction.referrer = referrer;
+    auction.referrer_owner = referrer_owner;
+    auction.referrer_fee_pct = referrer_fee_pct;
+
+    // Prevent sniping by extending the auction when a bid comes in at the last minute
+    if auction.end_time - now < Auction::TIME_EXTENSION {
+        auction.end_time = now + Auction::TIME_EXTENSION;
+    }
+
+    auction.save(&mut accounts.auction.data.borrow_mut());
+
+    Event::BidPlaced {
+        name_account: auction.name_account,
+        bidder: auction.highest_bidder,
+        amount: auction.highest_bid,
+        end_time: auction.end_time,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! Preview the premium of a domain name released from expiry
+
+use bonfida_utils::{checks::check_account_key, BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    state::dutch_auction::DutchAuction,
+    utils::{get_name_key, get_release_premium},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `preview_release_premium` instruction
+pub struct Params {
+    pub name: String,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `preview_release_premium` instruction
+pub struct Accounts<'a, T> {
+    /// The root domain account of the name
+    pub root_domain: &'a T,
+    /// The state auction account of the name
+    pub state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            root_domain: next_account_info(accounts_iter)?,
+            state: next_account_info(accounts_iter)?,
+        };
+
+        Ok(accounts)
+    }
+}
+
+/// Sets the current release premium of the name (USD with 6 decimals, `u64`) as return data, 0
+/// when the name is not being released
+pub fn process_preview_release_premium(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let name_account_key = get_name_key(&params.name, Some(accounts.root_domain.key))?;
+    check_account_key(
+        accounts.state,
+        &DutchAuction::find_key(&name_account_key, program_id).0,
+    )?;
+
+    let premium = get_release_premium(accounts.state)?;
+    set_return_data(&premium.try_to_vec()?);
+
+    Ok(())
+}
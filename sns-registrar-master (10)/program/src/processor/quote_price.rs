@@ -0,0 +1,107 @@
+//! Quote the price of a domain name registration without registering it
+
+use bonfida_utils::{checks::check_account_key, BorshSize, InstructionsAccount};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    constants::REFERRER_WHITELIST,
+    utils::{
+        check_domain_name, check_root_domain, get_domain_price_checked, get_name_key,
+        get_price_quote, get_state_key, next_tld_account, PythAccounts,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `quote_price` instruction
+pub struct Params {
+    pub name: String,
+    /// Number of years of the registration
+    pub years: u8,
+    /// The mint of the payment
+    pub mint: Pubkey,
+    /// Index of the referrer in the referrer whitelist
+    pub referrer_idx_opt: Option<u16>,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `quote_price` instruction
+pub struct Accounts<'a, T> {
+    /// The root domain account, the default root domain or the root of an enabled TLD
+    pub root_domain: &'a T,
+    /// The state auction account, holds the release premium of recently expired names
+    pub state: &'a T,
+    /// The Pyth feed account of the payment mint
+    pub pyth_feed_account: &'a T,
+    /// The TLD account of the root domain, required when registering under another root than the
+    /// default one
+    pub tld_opt: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let root_domain = next_account_info(accounts_iter)?;
+        let accounts = Accounts {
+            root_domain,
+            state: next_account_info(accounts_iter)?,
+            pyth_feed_account: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
+        };
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_quote_price(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let tld = check_root_domain(accounts.root_domain, accounts.tld_opt)?;
+
+    // Only names which can be registered are quoted
+    check_domain_name(&params.name)?;
+    let name_account_key = get_name_key(&params.name, Some(accounts.root_domain.key))?;
+    check_account_key(
+        accounts.state,
+        &get_state_key(&name_account_key, program_id),
+    )?;
+
+    let referrer_opt = params
+        .referrer_idx_opt
+        .map(|idx| {
+            REFERRER_WHITELIST.get(idx as usize).ok_or_else(|| {
+                msg!("Referrer is not whitelisted.");
+                ProgramError::InvalidArgument
+            })
+        })
+        .transpose()?;
+
+    let domain_price = get_domain_price_checked(
+        &params.name,
+        accounts.state,
+        &PythAccounts {
+            pyth_mapping_acc_or_feed: accounts.pyth_feed_account,
+            buyer_token_mint: params.mint,
+        },
+        tld.as_ref(),
+        params.years,
+    )?;
+    let quote = get_price_quote(domain_price, &params.mint, 0, referrer_opt)?;
+
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
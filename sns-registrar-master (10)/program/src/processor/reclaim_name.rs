@@ -0,0 +1,235 @@
+//! Reclaim an expired domain name and release it with a declining premium
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    central_state,
+    constants::{
+        RELEASE_PREMIUM, RELEASE_PREMIUM_CURVE, RELEASE_PREMIUM_WINDOW, ROOT_DOMAIN_ACCOUNT,
+    },
+    cpi::Cpi,
+    events::Event,
+    state::{
+        dutch_auction::DutchAuction, nft_record::NftRecord, registration::RegistrationRecord, Tag,
+    },
+    utils::get_reverse_key,
+    Error,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `reclaim_name` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `reclaim_name` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The root domain account
+    pub root_domain: &'a T,
+    /// The expired name account
+    #[cons(writable)]
+    pub name: &'a T,
+    /// The reverse look up account
+    #[cons(writable)]
+    pub reverse_lookup: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The registration record of the name
+    #[cons(writable)]
+    pub registration_record: &'a T,
+    /// The owner of the expired name, receives the rent of the reclaimed accounts
+    #[cons(writable)]
+    pub previous_owner: &'a T,
+    /// The state account, holds the release premium of the name
+    #[cons(writable)]
+    pub state: &'a T,
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            naming_service_program: next_account_info(accounts_iter)?,
+            root_domain: next_account_info(accounts_iter)?,
+            name: next_account_info(accounts_iter)?,
+            reverse_lookup: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            registration_record: next_account_info(accounts_iter)?,
+            previous_owner: next_account_info(accounts_iter)?,
+            state: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.root_domain, &ROOT_DOMAIN_ACCOUNT)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+
+        // Check ownership
+        check_account_owner(accounts.name, &spl_name_service::ID)?;
+        check_account_owner(accounts.registration_record, program_id)?;
+        check_account_owner(accounts.state, &system_program::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_reclaim_name(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let (state_key, state_nonce) = DutchAuction::find_key(accounts.name.key, program_id);
+    check_account_key(accounts.state, &state_key)?;
+    if !accounts.state.data_is_empty() {
+        msg!("The name auctioning state account is not empty.");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    reclaim(
+        program_id,
+        accounts.naming_service_program,
+        accounts.root_domain,
+        accounts.name,
+        accounts.reverse_lookup,
+        accounts.central_state,
+        accounts.registration_record,
+        accounts.previous_owner,
+        now,
+    )?;
+
+    let dutch_auction = DutchAuction {
+        tag: Tag::DutchAuction,
+        nonce: state_nonce,
+        name_account: *accounts.name.key,
+        released_at: now,
+        start_premium: RELEASE_PREMIUM,
+        window: RELEASE_PREMIUM_WINDOW,
+        curve: RELEASE_PREMIUM_CURVE,
+    };
+    Cpi::create_account(
+        program_id,
+        accounts.system_program,
+        accounts.fee_payer,
+        accounts.state,
+        &[&accounts.name.key.to_bytes(), &[state_nonce]],
+        dutch_auction.borsh_len(),
+    )?;
+    dutch_auction.save(&mut accounts.state.data.borrow_mut());
+
+    Ok(())
+}
+
+/// Takes an expired name back from its owner and deletes it along with its reverse lookup and
+/// registration record, refunding their rent to the previous owner
+#[allow(clippy::too_many_arguments)]
+pub fn reclaim<'a>(
+    program_id: &Pubkey,
+    naming_service_program: &AccountInfo<'a>,
+    root_domain: &AccountInfo<'a>,
+    name: &AccountInfo<'a>,
+    reverse_lookup: &AccountInfo<'a>,
+    central_state: &AccountInfo<'a>,
+    registration_record: &AccountInfo<'a>,
+    previous_owner: &AccountInfo<'a>,
+    now: i64,
+) -> ProgramResult {
+    check_account_owner(name, &spl_name_service::ID)?;
+    check_account_owner(registration_record, program_id)?;
+    check_account_key(
+        registration_record,
+        &RegistrationRecord::find_key(name.key, program_id).0,
+    )?;
+
+    let record = RegistrationRecord::from_account_info(registration_record)?;
+    if !record.is_reclaimable(now) {
+        return Err(Error::NameNotExpired.into());
+    }
+
+    let header = NameRecordHeader::unpack_from_slice(&name.data.borrow())?;
+    check_account_key(previous_owner, &header.owner)?;
+
+    // The NFT of a tokenized name no longer controls it, the record can be tokenized again
+    if header.owner == NftRecord::find_key(name.key, program_id).0 {
+        check_account_owner(previous_owner, program_id)?;
+        let mut nft_record = NftRecord::from_account_info(previous_owner, Tag::ActiveNftRecord)?;
+        nft_record.tag = Tag::InactiveNftRecord;
+        nft_record.save(&mut previous_owner.data.borrow_mut());
+    }
+
+    msg!("[+] Reclaiming expired domain");
+    let central_state_signer_seeds: &[&[u8]] = &[&program_id.to_bytes(), &[central_state::NONCE]];
+    Cpi::transfer_name_account_as_parent_owner(
+        naming_service_program,
+        name,
+        root_domain,
+        central_state,
+        &central_state::KEY,
+        central_state_signer_seeds,
+    )?;
+    Cpi::delete_name_account(
+        naming_service_program,
+        name,
+        central_state,
+        previous_owner,
+        central_state_signer_seeds,
+    )?;
+
+    let reverse_lookup_key = get_reverse_key(name.key, None)?;
+    if !reverse_lookup.data_is_empty() {
+        check_account_key(reverse_lookup, &reverse_lookup_key)?;
+        Cpi::delete_name_account(
+            naming_service_program,
+            reverse_lookup,
+            central_state,
+            previous_owner,
+            central_state_signer_seeds,
+        )?;
+    }
+
+    let mut source = registration_record.lamports.borrow_mut();
+    let mut target = previous_owner.lamports.borrow_mut();
+    **target += **source;
+    **source = 0;
+
+    Event::Deleted {
+        name_account: *name.key,
+        reverse_lookup: reverse_lookup_key,
+    }
+    .emit();
+
+    Ok(())
+}
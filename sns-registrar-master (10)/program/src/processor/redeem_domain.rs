@@ -0,0 +1,166 @@
+//! Burn the NFT of a tokenized domain name and return the name to the holder
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata},
+    instructions::{BurnNftCpi, BurnNftCpiAccounts},
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    cpi::Cpi,
+    events::Event,
+    state::{nft_record::NftRecord, Tag},
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `redeem_domain` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `redeem_domain` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The tokenized name account
+    #[cons(writable)]
+    pub name: &'a T,
+    /// The NFT holder, becomes the owner of the name account and receives the rent of the NFT
+    /// accounts
+    #[cons(writable, signer)]
+    pub holder: &'a T,
+    /// The token account of the holder containing the NFT
+    #[cons(writable)]
+    pub nft_source: &'a T,
+    /// The NFT record account
+    #[cons(writable)]
+    pub nft_record: &'a T,
+    /// The NFT mint account
+    #[cons(writable)]
+    pub mint: &'a T,
+    /// The NFT metadata account
+    #[cons(writable)]
+    pub nft_metadata: &'a T,
+    /// The NFT master edition account
+    #[cons(writable)]
+    pub master_edition: &'a T,
+    /// The SPL token program
+    pub spl_token_program: &'a T,
+    /// Metaplex token metadata program account
+    pub mpl_token_metadata: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            naming_service_program: next_account_info(accounts_iter)?,
+            name: next_account_info(accounts_iter)?,
+            holder: next_account_info(accounts_iter)?,
+            nft_source: next_account_info(accounts_iter)?,
+            nft_record: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            nft_metadata: next_account_info(accounts_iter)?,
+            master_edition: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            mpl_token_metadata: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+        check_account_key(accounts.mpl_token_metadata, &mpl_token_metadata::ID)?;
+
+        // Check ownership
+        check_account_owner(accounts.name, &spl_name_service::ID)?;
+        check_account_owner(accounts.nft_source, &spl_token::ID)?;
+        check_account_owner(accounts.nft_record, program_id)?;
+        check_account_owner(accounts.mint, &spl_token::ID)?;
+
+        // Check signer
+        check_signer(accounts.holder)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_redeem_domain(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let mut nft_record = NftRecord::from_account_info(accounts.nft_record, Tag::ActiveNftRecord)?;
+    check_account_key(accounts.name, &nft_record.name_account)?;
+    check_account_key(accounts.mint, &nft_record.nft_mint)?;
+    check_account_key(
+        accounts.nft_metadata,
+        &Metadata::find_pda(&nft_record.nft_mint).0,
+    )?;
+    check_account_key(
+        accounts.master_edition,
+        &MasterEdition::find_pda(&nft_record.nft_mint).0,
+    )?;
+
+    let token_acc = spl_token::state::Account::unpack(&accounts.nft_source.data.borrow())?;
+    if token_acc.mint != nft_record.nft_mint || token_acc.amount != 1 {
+        msg!("The token account does not hold the NFT");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    msg!("[+] Burning NFT");
+    BurnNftCpi::new(
+        accounts.mpl_token_metadata,
+        BurnNftCpiAccounts {
+            metadata: accounts.nft_metadata,
+            owner: accounts.holder,
+            mint: accounts.mint,
+            token_account: accounts.nft_source,
+            master_edition_account: accounts.master_edition,
+            spl_token_program: accounts.spl_token_program,
+            collection_metadata: None,
+        },
+    )
+    .invoke()?;
+
+    msg!("[+] Returning domain to the holder");
+    let nft_record_signer_seeds: &[&[u8]] = &[
+        NftRecord::SEED,
+        &nft_record.name_account.to_bytes(),
+        &[nft_record.nonce],
+    ];
+    Cpi::transfer_name_account(
+        accounts.naming_service_program,
+        accounts.nft_record,
+        accounts.name,
+        accounts.holder.key,
+        Some(nft_record_signer_seeds),
+    )?;
+
+    nft_record.tag = Tag::InactiveNftRecord;
+    nft_record.owner = *accounts.holder.key;
+    nft_record.save(&mut accounts.nft_record.data.borrow_mut());
+
+    Event::OwnerChanged {
+        name_account: nft_record.name_account,
+        owner: *accounts.holder.key,
+    }
+    .emit();
+
+    Ok(())
+}
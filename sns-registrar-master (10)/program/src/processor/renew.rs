@@ -0,0 +1,226 @@
+//! Extend the registration of a domain name
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    fp_math::fp32_div,
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use spl_name_service::state::NameRecordHeader;
+use spl_token::instruction::transfer;
+
+use crate::{
+    constants::MAX_REGISTRATION_YEARS,
+    events::Event,
+    state::{nft_record::NftRecord, registration::RegistrationRecord, Tag},
+    utils::{
+        check_root_domain, check_vault, get_grapheme_len, get_multi_year_usd_price, get_name_key,
+        get_price_quote, get_token_usd_price_checked_v2, get_usd_price, next_tld_account,
+    },
+    Error,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `renew` instruction
+pub struct Params {
+    pub name: String,
+    /// Number of years added to the registration
+    pub years: u8,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `renew` instruction
+pub struct Accounts<'a, T> {
+    /// The root domain account, the default root domain or the root of an enabled TLD
+    pub root_domain: &'a T,
+    /// The name account
+    pub name: &'a T,
+    /// The registration record account of the name
+    #[cons(writable)]
+    pub registration_record: &'a T,
+    /// The owner of the name, the NFT holder when the name is tokenized
+    #[cons(signer)]
+    pub domain_owner: &'a T,
+    /// The buyer account
+    #[cons(writable, signer)]
+    pub buyer: &'a T,
+    /// The buyer token account
+    #[cons(writable)]
+    pub buyer_token_source: &'a T,
+    /// The Pyth feed account
+    pub pyth_feed_account: &'a T,
+    /// The vault account
+    #[cons(writable)]
+    pub vault: &'a T,
+    /// The SPL token program
+    pub spl_token_program: &'a T,
+    /// The TLD account of the root domain, required when renewing under another root than the
+    /// default one
+    pub tld_opt: Option<&'a T>,
+    /// The NFT record account of the name, required when the name is tokenized
+    pub nft_record_opt: Option<&'a T>,
+    /// The token account holding the NFT, required when the name is tokenized
+    pub nft_token_account_opt: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let root_domain = next_account_info(accounts_iter)?;
+        let accounts = Accounts {
+            root_domain,
+            name: next_account_info(accounts_iter)?,
+            registration_record: next_account_info(accounts_iter)?,
+            domain_owner: next_account_info(accounts_iter)?,
+            buyer: next_account_info(accounts_iter)?,
+            buyer_token_source: next_account_info(accounts_iter)?,
+            pyth_feed_account: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
+            nft_record_opt: next_account_info(accounts_iter).ok(),
+            nft_token_account_opt: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.registration_record,
+            &RegistrationRecord::find_key(accounts.name.key, program_id).0,
+        )?;
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+
+        // Check ownership
+        check_account_owner(accounts.name, &spl_name_service::ID)?;
+        check_account_owner(accounts.vault, &spl_token::ID)?;
+
+        // Check signer
+        check_signer(accounts.domain_owner)?;
+        check_signer(accounts.buyer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_renew(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let tld = check_root_domain(accounts.root_domain, accounts.tld_opt)?;
+    check_vault(accounts.vault, tld.as_ref())?;
+
+    let name_account_key = get_name_key(&params.name, Some(accounts.root_domain.key))?;
+    check_account_key(accounts.name, &name_account_key)?;
+
+    // Only the owner of the name can renew it
+    let header = NameRecordHeader::unpack_from_slice(&accounts.name.data.borrow())?;
+    let (nft_record_key, _) = NftRecord::find_key(accounts.name.key, program_id);
+    let owner = if header.owner == nft_record_key {
+        let (nft_record, nft_token_account) =
+            match (accounts.nft_record_opt, accounts.nft_token_account_opt) {
+                (Some(nft_record), Some(nft_token_account)) => (nft_record, nft_token_account),
+                _ => {
+                    msg!("The name is tokenized, the NFT accounts are required");
+                    return Err(ProgramError::NotEnoughAccountKeys);
+                }
+            };
+        check_account_key(nft_record, &nft_record_key)?;
+        check_account_owner(nft_token_account, &spl_token::ID)?;
+        let nft_record = NftRecord::from_account_info(nft_record, Tag::ActiveNftRecord)?;
+        let token_acc = spl_token::state::Account::unpack(&nft_token_account.data.borrow())?;
+        if token_acc.mint != nft_record.nft_mint || token_acc.amount != 1 {
+            msg!("The token account does not hold the NFT");
+            return Err(ProgramError::InvalidArgument);
+        }
+        token_acc.owner
+    } else {
+        header.owner
+    };
+    if &owner != accounts.domain_owner.key {
+        msg!("Only the owner of the name can renew it");
+        return Err(Error::PermissionDenied.into());
+    }
+
+    // Names can be renewed until the end of their grace period
+    if accounts.registration_record.data_is_empty() {
+        msg!("The name has no registration term to renew");
+        return Err(ProgramError::InvalidArgument);
+    }
+    check_account_owner(accounts.registration_record, program_id)?;
+    let mut registration_record =
+        RegistrationRecord::from_account_info(accounts.registration_record)?;
+    let now = Clock::get()?.unix_timestamp;
+    if registration_record.is_reclaimable(now) {
+        return Err(Error::NameExpired.into());
+    }
+    let expiry = registration_record
+        .expiry
+        .checked_add(RegistrationRecord::YEAR * params.years as i64)
+        .ok_or(Error::Overflow)?;
+    if expiry - now > RegistrationRecord::YEAR * MAX_REGISTRATION_YEARS as i64 {
+        msg!(
+            "Registrations cannot last more than {} years",
+            MAX_REGISTRATION_YEARS
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Payment
+    let len = get_grapheme_len(&params.name);
+    let yearly_usd_price = tld
+        .as_ref()
+        .map_or_else(|| get_usd_price(len), |tld| tld.get_usd_price(len));
+    let usd_price = get_multi_year_usd_price(yearly_usd_price, params.years)?;
+    let token_acc = spl_token::state::Account::unpack(&accounts.buyer_token_source.data.borrow())?;
+    let token_price = get_token_usd_price_checked_v2(accounts.pyth_feed_account, &token_acc.mint)?;
+    let domain_price = fp32_div(usd_price, token_price).ok_or(Error::Overflow)?;
+    let quote = get_price_quote(domain_price, &token_acc.mint, 0, None)?;
+
+    let transfer_ix = transfer(
+        &spl_token::ID,
+        accounts.buyer_token_source.key,
+        accounts.vault.key,
+        accounts.buyer.key,
+        &[],
+        quote.vault_amount,
+    )?;
+
+    invoke(
+        &transfer_ix,
+        &[
+            accounts.spl_token_program.clone(),
+            accounts.buyer_token_source.clone(),
+            accounts.vault.clone(),
+            accounts.buyer.clone(),
+        ],
+    )?;
+
+    msg!("[+] Renewing until {}", expiry);
+    registration_record.expiry = expiry;
+    registration_record.save(&mut accounts.registration_record.data.borrow_mut());
+
+    Event::Renewed {
+        name_account: name_account_key,
+        mint: token_acc.mint,
+        amount: quote.vault_amount,
+        expiry,
+    }
+    .emit();
+
+    Ok(())
+}
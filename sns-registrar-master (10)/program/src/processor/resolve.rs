@@ -0,0 +1,203 @@
+//! Resolve a domain name or a subdomain to its effective owner
+
+use std::convert::TryInto;
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::set_return_data,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    state::{
+        nft_record::NftRecord,
+        record::{Record, RecordData},
+        registration::RegistrationRecord,
+        Tag,
+    },
+    utils::{check_root_domain, get_record_key, next_tld_account},
+    Error,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `resolve` instruction
+pub struct Params {}
+
+/// Resolution of a name, set as return data by the `resolve` instruction
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Resolution {
+    /// The resolved name account
+    pub name_account: Pubkey,
+    /// The owner of the name, the NFT holder when the name is tokenized
+    pub owner: Pubkey,
+    /// The address payments to the name should be sent to, the address of the SOL record when it
+    /// is current and has a right of association, the owner otherwise
+    pub destination: Pubkey,
+    /// Expiry of the registration of the domain, 0 for names which never expire
+    pub expiry: i64,
+    pub tokenized: bool,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `resolve` instruction
+pub struct Accounts<'a, T> {
+    /// The name account to resolve
+    pub name: &'a T,
+    /// The domain name account, the same account as `name` when resolving a domain
+    pub domain: &'a T,
+    /// The root domain account of the domain, the default root domain or the root of an enabled TLD
+    pub root_domain: &'a T,
+    /// The registration record account of the domain
+    pub registration_record: &'a T,
+    /// The TLD account of the root domain, required when resolving under another root than the
+    /// default one
+    pub tld_opt: Option<&'a T>,
+    /// The NFT record account of the name, required when the name is tokenized
+    pub nft_record_opt: Option<&'a T>,
+    /// The token account holding the NFT, required when the name is tokenized
+    pub nft_token_account_opt: Option<&'a T>,
+    /// The SOL record account of the name, ignored when it does not exist
+    pub sol_record_opt: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let name = next_account_info(accounts_iter)?;
+        let domain = next_account_info(accounts_iter)?;
+        let root_domain = next_account_info(accounts_iter)?;
+        let mut accounts = Accounts {
+            name,
+            domain,
+            root_domain,
+            registration_record: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
+            nft_record_opt: None,
+            nft_token_account_opt: None,
+            sol_record_opt: None,
+        };
+        // The other optional accounts are told apart by their owner
+        for account in accounts_iter {
+            if account.owner == program_id {
+                accounts.nft_record_opt = Some(account);
+            } else if account.owner == &spl_token::ID {
+                accounts.nft_token_account_opt = Some(account);
+            } else {
+                accounts.sol_record_opt = Some(account);
+            }
+        }
+
+        // Check keys
+        check_account_key(
+            accounts.registration_record,
+            &RegistrationRecord::find_key(accounts.domain.key, program_id).0,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.name, &spl_name_service::ID)?;
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+        check_account_owner(accounts.root_domain, &spl_name_service::ID)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_resolve(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    // Name chain
+    check_root_domain(accounts.root_domain, accounts.tld_opt)?;
+    let header = NameRecordHeader::unpack_from_slice(&accounts.name.data.borrow())?;
+    let domain_header = NameRecordHeader::unpack_from_slice(&accounts.domain.data.borrow())?;
+    if (accounts.name.key != accounts.domain.key && header.parent_name != *accounts.domain.key)
+        || domain_header.parent_name != *accounts.root_domain.key
+    {
+        msg!("The name is not a domain or a subdomain of the root domain");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Expiry
+    let expiry = if accounts.registration_record.data_is_empty() {
+        0
+    } else {
+        let record = RegistrationRecord::from_account_info(accounts.registration_record)?;
+        if record.expiry < Clock::get()?.unix_timestamp {
+            msg!("The registration of the domain expired");
+            return Err(Error::NameExpired.into());
+        }
+        record.expiry
+    };
+
+    // Tokenization
+    let (nft_record_key, _) = NftRecord::find_key(accounts.name.key, program_id);
+    let tokenized = header.owner == nft_record_key;
+    let owner = if tokenized {
+        let (nft_record, nft_token_account) =
+            match (accounts.nft_record_opt, accounts.nft_token_account_opt) {
+                (Some(nft_record), Some(nft_token_account)) => (nft_record, nft_token_account),
+                _ => {
+                    msg!("The name is tokenized, the NFT accounts are required");
+                    return Err(ProgramError::NotEnoughAccountKeys);
+                }
+            };
+        check_account_key(nft_record, &nft_record_key)?;
+        let nft_record = NftRecord::from_account_info(nft_record, Tag::ActiveNftRecord)?;
+        let token_acc = spl_token::state::Account::unpack(&nft_token_account.data.borrow())?;
+        if token_acc.mint != nft_record.nft_mint || token_acc.amount != 1 {
+            msg!("The token account does not hold the NFT");
+            return Err(ProgramError::InvalidArgument);
+        }
+        token_acc.owner
+    } else {
+        header.owner
+    };
+
+    // Destination
+    let mut destination = owner;
+    if let Some(sol_record) = accounts.sol_record_opt.filter(|a| !a.data_is_empty()) {
+        check_account_key(sol_record, &get_record_key(accounts.name.key, Record::Sol))?;
+        check_account_owner(sol_record, &spl_name_service::ID)?;
+        let record = RecordData::from_buffer(&sol_record.data.borrow()[NameRecordHeader::LEN..])?;
+        if record.is_stale(&header.owner) || !record.right_of_association {
+            msg!("Ignoring the SOL record, stale or without right of association");
+        } else {
+            destination = Pubkey::new_from_array(
+                record
+                    .content
+                    .try_into()
+                    .map_err(|_| ProgramError::InvalidAccountData)?,
+            );
+        }
+    }
+
+    let resolution = Resolution {
+        name_account: *accounts.name.key,
+        owner,
+        destination,
+        expiry,
+        tokenized,
+    };
+
+    set_return_data(&resolution.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Set the domain name displayed for a wallet
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program,
+};
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{cpi::Cpi, events::Event, state::primary_domain::PrimaryDomain, Error};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `set_primary_domain` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `set_primary_domain` instruction
+pub struct Accounts<'a, T> {
+    /// The primary domain account of the wallet
+    #[cons(writable)]
+    pub primary_domain: &'a T,
+    /// The name account of the domain, owned by the wallet
+    pub name: &'a T,
+    /// The wallet
+    #[cons(writable, signer)]
+    pub wallet: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            primary_domain: next_account_info(accounts_iter)?,
+            name: next_account_info(accounts_iter)?,
+            wallet: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.primary_domain,
+            &PrimaryDomain::find_key(accounts.wallet.key, program_id).0,
+        )?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+
+        // Check ownership
+        check_account_owner(accounts.name, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.wallet)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_set_primary_domain(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let header = NameRecordHeader::unpack_from_slice(&accounts.name.data.borrow())?;
+    if header.owner != *accounts.wallet.key {
+        msg!("The wallet does not own the domain name");
+        return Err(Error::PermissionDenied.into());
+    }
+
+    let (_, nonce) = PrimaryDomain::find_key(accounts.wallet.key, program_id);
+    let primary_domain = PrimaryDomain::new(nonce, *accounts.wallet.key, *accounts.name.key);
+
+    if accounts.primary_domain.data_is_empty() {
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.wallet,
+            accounts.primary_domain,
+            &[
+                PrimaryDomain::SEED,
+                &accounts.wallet.key.to_bytes(),
+                &[nonce],
+            ],
+            primary_domain.borsh_len(),
+        )?;
+    } else {
+        check_account_owner(accounts.primary_domain, program_id)?;
+        PrimaryDomain::from_account_info(accounts.primary_domain)?;
+    }
+    primary_domain.save(&mut accounts.primary_domain.data.borrow_mut());
+
+    Event::PrimaryDomainSet {
+        wallet: *accounts.wallet.key,
+        name_account: *accounts.name.key,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,309 @@
+//! Settle a domain name auction and create the name for the highest bidder
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program, sysvar,
+    sysvar::Sysvar,
+};
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    central_state,
+    constants::ROOT_DOMAIN_ACCOUNT,
+    cpi::Cpi,
+    events::Event,
+    state::auction::Auction,
+    utils::{
+        check_vault_token_account_owner, get_hashed_name, get_referrer_fee_pct, get_reverse_key,
+        write_registration_record,
+    },
+    Error,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `settle_auction` instruction
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `settle_auction` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The root domain account
+    pub root_domain: &'a T,
+    /// The name account
+    #[cons(writable)]
+    pub name: &'a T,
+    /// The reverse look up account
+    #[cons(writable)]
+    pub reverse_lookup: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The auction account
+    #[cons(writable)]
+    pub auction: &'a T,
+    /// The escrow token account of the auction
+    #[cons(writable)]
+    pub escrow: &'a T,
+    /// The highest bidder, owner of the created name
+    pub winner: &'a T,
+    /// The vault account
+    #[cons(writable)]
+    pub vault: &'a T,
+    /// The SPL token program
+    pub spl_token_program: &'a T,
+    /// The rent sysvar account
+    pub rent_sysvar: &'a T,
+    /// The fee payer account, pays for the name and reverse lookup accounts
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+    /// The account which paid for the auction accounts
+    #[cons(writable)]
+    pub rent_payer: &'a T,
+    /// The registration record account, auctioned names are registered for a year
+    #[cons(writable)]
+    pub registration_record: &'a T,
+    /// The referrer token account attached to the highest bid, if any
+    #[cons(writable)]
+    pub referrer_account_opt: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            naming_service_program: next_account_info(accounts_iter)?,
+            root_domain: next_account_info(accounts_iter)?,
+            name: next_account_info(accounts_iter)?,
+            reverse_lookup: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            auction: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            winner: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            rent_sysvar: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            rent_payer: next_account_info(accounts_iter)?,
+            registration_record: next_account_info(accounts_iter)?,
+            referrer_account_opt: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.root_domain, &ROOT_DOMAIN_ACCOUNT)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+        check_account_key(accounts.rent_sysvar, &sysvar::rent::ID)?;
+
+        // Check ownership
+        check_account_owner(accounts.name, &system_program::ID)
+            .map_err(|_| Error::AlreadyRegistered)?;
+        check_account_owner(accounts.auction, program_id)?;
+        check_account_owner(accounts.escrow, &spl_token::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_settle_auction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+    let auction = Auction::from_account_info(accounts.auction)?;
+
+    check_account_key(accounts.name, &auction.name_account)?;
+    check_account_key(accounts.escrow, &auction.escrow)?;
+    check_account_key(accounts.rent_payer, &auction.rent_payer)?;
+
+    if Clock::get()?.unix_timestamp < auction.end_time {
+        return Err(Error::AuctionInProgress.into());
+    }
+
+    let auction_signer_seeds: &[&[u8]] = &[&auction.name_account.to_bytes(), &[auction.nonce]];
+
+    if auction.highest_bid != 0 {
+        check_account_key(accounts.winner, &auction.highest_bidder)?;
+        let vault = check_vault_token_account_owner(accounts.vault)?;
+        if vault.mint != auction.quote_mint {
+            msg!("The vault mint does not match the auction mint");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Transfer proceeds to the referrer and the vault. The referrer validated at bid time is
+        // paid the fee fixed then, unless its token account changed hands or its fee went up.
+        let referrer_account = if auction.referrer != Pubkey::default() {
+            let referrer_account = accounts
+                .referrer_account_opt
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            check_account_key(referrer_account, &auction.referrer)?;
+            let referrer_token_acc =
+                spl_token::state::Account::unpack(&referrer_account.data.borrow())?;
+            if referrer_token_acc.owner == auction.referrer_owner
+                && get_referrer_fee_pct(&referrer_token_acc.owner) <= auction.referrer_fee_pct
+            {
+                Some(referrer_account)
+            } else {
+                msg!("The referrer changed since the bid, the proceeds go to the vault");
+                None
+            }
+        } else {
+            None
+        };
+        let referrer_fees = if let Some(referrer_account) = referrer_account {
+            let referrer_fees_amount = auction
+                .highest_bid
+                .checked_mul(auction.referrer_fee_pct)
+                .ok_or(Error::Overflow)?
+                / 100;
+            Cpi::transfer_tokens_signed(
+                accounts.spl_token_program,
+                accounts.escrow,
+                referrer_account,
+                accounts.auction,
+                referrer_fees_amount,
+                auction_signer_seeds,
+            )?;
+            Event::ReferrerPaid {
+                name_account: auction.name_account,
+                referrer: auction.referrer,
+                mint: auction.quote_mint,
+                amount: referrer_fees_amount,
+            }
+            .emit();
+            referrer_fees_amount
+        } else {
+            0
+        };
+
+        Cpi::transfer_tokens_signed(
+            accounts.spl_token_program,
+            accounts.escrow,
+            accounts.vault,
+            accounts.auction,
+            auction
+                .highest_bid
+                .checked_sub(referrer_fees)
+                .ok_or(Error::Overflow)?,
+            auction_signer_seeds,
+        )?;
+
+        // Create domain name
+        let central_state_signer_seeds: &[&[u8]] =
+            &[&program_id.to_bytes(), &[central_state::NONCE]];
+        let rent = Rent::get()?;
+        Cpi::create_name_account(
+            accounts.naming_service_program,
+            accounts.system_program,
+            accounts.name,
+            accounts.fee_payer,
+            accounts.winner,
+            Some(accounts.root_domain),
+            accounts.central_state,
+            get_hashed_name(&auction.name),
+            rent.minimum_balance(NameRecordHeader::LEN + auction.space as usize),
+            auction.space,
+            central_state_signer_seeds,
+        )?;
+        let expiry = write_registration_record(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.registration_record,
+            &auction.name_account,
+            1,
+        )?;
+        Event::Registered {
+            name_account: auction.name_account,
+            owner: auction.highest_bidder,
+            root_domain: *accounts.root_domain.key,
+            name: auction.name.clone(),
+            mint: auction.quote_mint,
+            amount: auction.highest_bid,
+            expiry: Some(expiry),
+        }
+        .emit();
+
+        // Reverse look up
+        if accounts.reverse_lookup.data_len() == 0 {
+            check_account_key(
+                accounts.reverse_lookup,
+                &get_reverse_key(&auction.name_account, None)?,
+            )?;
+            Cpi::create_reverse_lookup_account(
+                accounts.naming_service_program,
+                accounts.system_program,
+                accounts.reverse_lookup,
+                accounts.fee_payer,
+                auction.name.clone(),
+                get_hashed_name(&auction.name_account.to_string()),
+                accounts.central_state,
+                accounts.rent_sysvar,
+                central_state_signer_seeds,
+                None,
+                None,
+                None,
+            )?;
+            Event::ReverseCreated {
+                name_account: auction.name_account,
+                reverse_lookup: *accounts.reverse_lookup.key,
+                name: auction.name.clone(),
+            }
+            .emit();
+        }
+    } else {
+        msg!("The auction ended without bids");
+    }
+
+    // Close the escrow and the auction accounts
+    let close_escrow_ix = spl_token::instruction::close_account(
+        &spl_token::ID,
+        accounts.escrow.key,
+        accounts.rent_payer.key,
+        accounts.auction.key,
+        &[],
+    )?;
+    invoke_signed(
+        &close_escrow_ix,
+        &[
+            accounts.spl_token_program.clone(),
+            accounts.escrow.clone(),
+            accounts.rent_payer.clone(),
+            accounts.auction.clone(),
+        ],
+        &[auction_signer_seeds],
+    )?;
+
+    let mut source = accounts.auction.lamports.borrow_mut();
+    let mut target = accounts.rent_payer.lamports.borrow_mut();
+    **target += **source;
+    **source = 0;
+    accounts.auction.data.borrow_mut().fill(0);
+
+    Ok(())
+}
@@ -0,0 +1,262 @@
+//! Start an English auction for a domain name
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    tokens::SupportedToken,
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program, sysvar,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    central_state,
+    constants::{ADMIN, ROOT_DOMAIN_ACCOUNT},
+    cpi::Cpi,
+    events::Event,
+    processor::reclaim_name::reclaim,
+    state::{auction::Auction, Tag},
+    utils::get_name_key,
+    Error,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `start_auction` instruction
+pub struct Params {
+    pub name: String,
+    pub space: u32,
+    /// Duration of the auction in seconds
+    pub duration: i64,
+    /// Minimum amount of the first bid
+    pub reserve_price: u64,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `start_auction` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The root domain account
+    pub root_domain: &'a T,
+    /// The name account
+    #[cons(writable)]
+    pub name: &'a T,
+    /// The reverse look up account
+    #[cons(writable)]
+    pub reverse_lookup: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The auction account
+    #[cons(writable)]
+    pub auction: &'a T,
+    /// The escrow token account of the auction
+    #[cons(writable)]
+    pub escrow: &'a T,
+    /// The mint in which bids are denominated
+    pub quote_mint: &'a T,
+    /// The SPL token program
+    pub spl_token_program: &'a T,
+    /// The rent sysvar account
+    pub rent_sysvar: &'a T,
+    /// The fee payer account, has to be the admin
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+    /// The *optional* registration record of an expired name
+    #[cons(writable)]
+    pub registration_record_opt: Option<&'a T>,
+    /// The *optional* owner of an expired name, receives the rent of the reclaimed accounts
+    #[cons(writable)]
+    pub previous_owner_opt: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            naming_service_program: next_account_info(accounts_iter)?,
+            root_domain: next_account_info(accounts_iter)?,
+            name: next_account_info(accounts_iter)?,
+            reverse_lookup: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            auction: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            quote_mint: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            rent_sysvar: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            registration_record_opt: next_account_info(accounts_iter).ok(),
+            previous_owner_opt: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.root_domain, &ROOT_DOMAIN_ACCOUNT)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+        check_account_key(accounts.rent_sysvar, &sysvar::rent::ID)?;
+
+        // Check ownership
+        check_account_owner(accounts.auction, &system_program::ID)?;
+        check_account_owner(accounts.escrow, &system_program::ID)?;
+        check_account_owner(accounts.quote_mint, &spl_token::ID)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_start_auction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    if params.name != params.name.trim().to_lowercase() {
+        msg!("Domain names must be lower case and have no space");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if params.name.contains('.') {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let name_account_key = get_name_key(&params.name, None)?;
+    check_account_key(accounts.name, &name_account_key)?;
+
+    let (auction_key, auction_nonce) = Auction::find_key(&name_account_key, program_id);
+    check_account_key(accounts.auction, &auction_key)?;
+    if !accounts.auction.data_is_empty() {
+        msg!("An auction is already running for this name");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let (escrow_key, escrow_nonce) = Auction::find_escrow_key(&auction_key, program_id);
+    check_account_key(accounts.escrow, &escrow_key)?;
+
+    // Bids have to be placed in a token supported by the registrar
+    SupportedToken::from_mint(accounts.quote_mint.key)?;
+
+    // Expired names are released by anyone through `ReclaimName`, which charges the release premium
+    if accounts.fee_payer.key != &ADMIN {
+        msg!("Only the admin can start auctions");
+        return Err(Error::PermissionDenied.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if !accounts.name.data_is_empty() {
+        // The name has to be reclaimed from its owner
+        let (registration_record, previous_owner) = match (
+            accounts.registration_record_opt,
+            accounts.previous_owner_opt,
+        ) {
+            (Some(r), Some(o)) => (r, o),
+            _ => {
+                msg!("Auctioning a registered name requires its registration record");
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+        };
+        reclaim(
+            program_id,
+            accounts.naming_service_program,
+            accounts.root_domain,
+            accounts.name,
+            accounts.reverse_lookup,
+            accounts.central_state,
+            registration_record,
+            previous_owner,
+            now,
+        )?;
+    }
+
+    let duration = params.duration;
+    if !(Auction::MIN_DURATION..=Auction::MAX_DURATION).contains(&duration) {
+        msg!("Invalid auction duration");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let auction = Auction {
+        tag: Tag::Auction,
+        nonce: auction_nonce,
+        name_account: name_account_key,
+        quote_mint: *accounts.quote_mint.key,
+        escrow: escrow_key,
+        rent_payer: *accounts.fee_payer.key,
+        start_time: now,
+        end_time: now.checked_add(duration).ok_or(Error::Overflow)?,
+        reserve_price: params.reserve_price,
+        highest_bid: 0,
+        highest_bidder: Pubkey::default(),
+        referrer: Pubkey::default(),
+        referrer_owner: Pubkey::default(),
+        referrer_fee_pct: 0,
+        space: params.space,
+        name: params.name,
+    };
+
+    Cpi::create_account(
+        program_id,
+        accounts.system_program,
+        accounts.fee_payer,
+        accounts.auction,
+        &[&name_account_key.to_bytes(), &[auction_nonce]],
+        auction.borsh_len(),
+    )?;
+    auction.save(&mut accounts.auction.data.borrow_mut());
+
+    Cpi::create_account(
+        &spl_token::ID,
+        accounts.system_program,
+        accounts.fee_payer,
+        accounts.escrow,
+        &[
+            &auction_key.to_bytes(),
+            Auction::ESCROW_SEED,
+            &[escrow_nonce],
+        ],
+        spl_token::state::Account::LEN,
+    )?;
+    let init_escrow_ix = spl_token::instruction::initialize_account3(
+        &spl_token::ID,
+        &escrow_key,
+        accounts.quote_mint.key,
+        &auction_key,
+    )?;
+    invoke(
+        &init_escrow_ix,
+        &[
+            accounts.spl_token_program.clone(),
+            accounts.escrow.clone(),
+            accounts.quote_mint.clone(),
+        ],
+    )?;
+
+    Event::AuctionStarted {
+        name_account: name_account_key,
+        quote_mint: auction.quote_mint,
+        reserve_price: auction.reserve_price,
+        end_time: auction.end_time,
+    }
+    .emit();
+
+    Ok(())
+}
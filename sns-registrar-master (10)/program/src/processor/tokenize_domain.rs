@@ -0,0 +1,364 @@
+//! Transfer a domain name to the registrar and mint a 1/1 NFT controlling it
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata},
+    instructions::{
+        CreateMasterEditionV3Cpi, CreateMasterEditionV3CpiAccounts,
+        CreateMasterEditionV3InstructionArgs, CreateMetadataAccountV3Cpi,
+        CreateMetadataAccountV3CpiAccounts, CreateMetadataAccountV3InstructionArgs,
+    },
+    types::DataV2,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program, sysvar,
+    sysvar::Sysvar,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    central_state,
+    constants::{NFT_METADATA_URI_PREFIX, NFT_SYMBOL},
+    cpi::Cpi,
+    events::Event,
+    state::{nft_record::NftRecord, registration::RegistrationRecord, Tag},
+    utils::{check_root_domain, get_name_key, next_tld_account},
+    Error,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `tokenize_domain` instruction
+pub struct Params {
+    pub name: String,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `tokenize_domain` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The root domain account, the default root domain or the root of an enabled TLD
+    pub root_domain: &'a T,
+    /// The name account
+    #[cons(writable)]
+    pub name: &'a T,
+    /// The registration record account of the name, expired names cannot be tokenized
+    pub registration_record: &'a T,
+    /// The owner of the name account
+    #[cons(signer)]
+    pub owner: &'a T,
+    /// The NFT record account
+    #[cons(writable)]
+    pub nft_record: &'a T,
+    /// A new keypair, the mint of the NFT
+    #[cons(writable, signer)]
+    pub mint: &'a T,
+    /// The associated token account of the owner receiving the NFT
+    #[cons(writable)]
+    pub nft_destination: &'a T,
+    /// The NFT metadata account
+    #[cons(writable)]
+    pub nft_metadata: &'a T,
+    /// The NFT master edition account, becomes the mint and freeze authority of the mint
+    #[cons(writable)]
+    pub master_edition: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The system program account
+    pub system_program: &'a T,
+    /// The SPL token program
+    pub spl_token_program: &'a T,
+    /// The SPL associated token account program
+    pub associated_token_program: &'a T,
+    /// Metaplex token metadata program account
+    pub mpl_token_metadata: &'a T,
+    /// The rent sysvar account
+    pub rent_sysvar: &'a T,
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+    /// The TLD account of the root domain, required when tokenizing a name under another root than
+    /// the default one
+    pub tld_opt: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let naming_service_program = next_account_info(accounts_iter)?;
+        let root_domain = next_account_info(accounts_iter)?;
+        let accounts = Accounts {
+            naming_service_program,
+            root_domain,
+            name: next_account_info(accounts_iter)?,
+            registration_record: next_account_info(accounts_iter)?,
+            owner: next_account_info(accounts_iter)?,
+            nft_record: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            nft_destination: next_account_info(accounts_iter)?,
+            nft_metadata: next_account_info(accounts_iter)?,
+            master_edition: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            associated_token_program: next_account_info(accounts_iter)?,
+            mpl_token_metadata: next_account_info(accounts_iter)?,
+            rent_sysvar: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.spl_token_program, &spl_token::ID)?;
+        check_account_key(
+            accounts.associated_token_program,
+            &spl_associated_token_account::ID,
+        )?;
+        check_account_key(accounts.mpl_token_metadata, &mpl_token_metadata::ID)?;
+        check_account_key(accounts.rent_sysvar, &sysvar::rent::ID)?;
+
+        // Check ownership
+        check_account_owner(accounts.name, &spl_name_service::ID)?;
+        check_account_owner(accounts.mint, &system_program::ID)?;
+
+        // Check signer
+        check_signer(accounts.owner)?;
+        check_signer(accounts.mint)?;
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_tokenize_domain(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    check_root_domain(accounts.root_domain, accounts.tld_opt)?;
+    let name_account_key = get_name_key(&params.name, Some(accounts.root_domain.key))?;
+    check_account_key(accounts.name, &name_account_key)?;
+
+    // Names registered before expiries were recorded have no registration record
+    check_account_key(
+        accounts.registration_record,
+        &RegistrationRecord::find_key(&name_account_key, program_id).0,
+    )?;
+    if !accounts.registration_record.data_is_empty() {
+        check_account_owner(accounts.registration_record, program_id)?;
+        let record = RegistrationRecord::from_account_info(accounts.registration_record)?;
+        if record.is_expired(Clock::get()?.unix_timestamp) {
+            msg!("The registration of the name expired");
+            return Err(Error::NameExpired.into());
+        }
+    }
+
+    let header = NameRecordHeader::unpack_from_slice(&accounts.name.data.borrow())?;
+    check_account_key(accounts.owner, &header.owner)?;
+
+    let (nft_record_key, nft_record_nonce) = NftRecord::find_key(&name_account_key, program_id);
+    check_account_key(accounts.nft_record, &nft_record_key)?;
+
+    let mint_key = *accounts.mint.key;
+    check_account_key(accounts.nft_metadata, &Metadata::find_pda(&mint_key).0)?;
+    check_account_key(
+        accounts.master_edition,
+        &MasterEdition::find_pda(&mint_key).0,
+    )?;
+    check_account_key(
+        accounts.nft_destination,
+        &get_associated_token_address(accounts.owner.key, &mint_key),
+    )?;
+
+    let central_state_signer_seeds: &[&[u8]] = &[&program_id.to_bytes(), &[central_state::NONCE]];
+
+    // The record is reused when a redeemed domain is tokenized again
+    let nft_record = NftRecord::new(
+        nft_record_nonce,
+        name_account_key,
+        *accounts.owner.key,
+        mint_key,
+    );
+    if accounts.nft_record.data_is_empty() {
+        Cpi::create_account(
+            program_id,
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.nft_record,
+            &[
+                NftRecord::SEED,
+                &name_account_key.to_bytes(),
+                &[nft_record_nonce],
+            ],
+            nft_record.borsh_len(),
+        )?;
+    } else {
+        check_account_owner(accounts.nft_record, program_id)?;
+        NftRecord::from_account_info(accounts.nft_record, Tag::InactiveNftRecord)?;
+    }
+
+    // Every tokenization mints a new NFT, the mints of redeemed domains are left without supply
+    msg!("[+] Creating NFT mint");
+    let create_mint_ix = system_instruction::create_account(
+        accounts.fee_payer.key,
+        &mint_key,
+        Rent::get()?.minimum_balance(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::ID,
+    );
+    invoke(
+        &create_mint_ix,
+        &[
+            accounts.system_program.clone(),
+            accounts.fee_payer.clone(),
+            accounts.mint.clone(),
+        ],
+    )?;
+    let init_mint_ix = spl_token::instruction::initialize_mint2(
+        &spl_token::ID,
+        &mint_key,
+        &central_state::KEY,
+        Some(&central_state::KEY),
+        0,
+    )?;
+    invoke(
+        &init_mint_ix,
+        &[accounts.spl_token_program.clone(), accounts.mint.clone()],
+    )?;
+
+    let create_ata_ix = create_associated_token_account_idempotent(
+        accounts.fee_payer.key,
+        accounts.owner.key,
+        &mint_key,
+        &spl_token::ID,
+    );
+    invoke(
+        &create_ata_ix,
+        &[
+            accounts.associated_token_program.clone(),
+            accounts.fee_payer.clone(),
+            accounts.nft_destination.clone(),
+            accounts.owner.clone(),
+            accounts.mint.clone(),
+            accounts.system_program.clone(),
+            accounts.spl_token_program.clone(),
+        ],
+    )?;
+
+    msg!("[+] Minting NFT");
+    let mint_to_ix = spl_token::instruction::mint_to(
+        &spl_token::ID,
+        &mint_key,
+        accounts.nft_destination.key,
+        &central_state::KEY,
+        &[],
+        1,
+    )?;
+    invoke_signed(
+        &mint_to_ix,
+        &[
+            accounts.spl_token_program.clone(),
+            accounts.mint.clone(),
+            accounts.nft_destination.clone(),
+            accounts.central_state.clone(),
+        ],
+        &[central_state_signer_seeds],
+    )?;
+
+    // Metaplex limits the length of NFT names
+    let mut nft_name = params.name;
+    while nft_name.len() > mpl_token_metadata::MAX_NAME_LENGTH {
+        nft_name.pop();
+    }
+    CreateMetadataAccountV3Cpi::new(
+        accounts.mpl_token_metadata,
+        CreateMetadataAccountV3CpiAccounts {
+            metadata: accounts.nft_metadata,
+            mint: accounts.mint,
+            mint_authority: accounts.central_state,
+            payer: accounts.fee_payer,
+            update_authority: (accounts.central_state, true),
+            system_program: accounts.system_program,
+            rent: Some(accounts.rent_sysvar),
+        },
+        CreateMetadataAccountV3InstructionArgs {
+            data: DataV2 {
+                name: nft_name,
+                symbol: NFT_SYMBOL.to_owned(),
+                uri: format!("{}{}", NFT_METADATA_URI_PREFIX, name_account_key),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            is_mutable: true,
+            collection_details: None,
+        },
+    )
+    .invoke_signed(&[central_state_signer_seeds])?;
+
+    // A master edition without prints makes the token a 1/1 NFT and takes over the mint and
+    // freeze authorities, no other token can be minted
+    msg!("[+] Creating master edition");
+    CreateMasterEditionV3Cpi::new(
+        accounts.mpl_token_metadata,
+        CreateMasterEditionV3CpiAccounts {
+            edition: accounts.master_edition,
+            mint: accounts.mint,
+            update_authority: accounts.central_state,
+            mint_authority: accounts.central_state,
+            payer: accounts.fee_payer,
+            metadata: accounts.nft_metadata,
+            token_program: accounts.spl_token_program,
+            system_program: accounts.system_program,
+            rent: Some(accounts.rent_sysvar),
+        },
+        CreateMasterEditionV3InstructionArgs {
+            max_supply: Some(0),
+        },
+    )
+    .invoke_signed(&[central_state_signer_seeds])?;
+
+    msg!("[+] Transferring domain to the NFT record");
+    Cpi::transfer_name_account(
+        accounts.naming_service_program,
+        accounts.owner,
+        accounts.name,
+        &nft_record_key,
+        None,
+    )?;
+
+    nft_record.save(&mut accounts.nft_record.data.borrow_mut());
+
+    Event::OwnerChanged {
+        name_account: name_account_key,
+        owner: nft_record_key,
+    }
+    .emit();
+
+    Ok(())
+}
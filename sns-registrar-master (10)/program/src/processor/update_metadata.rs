@@ -3,11 +3,11 @@
 use crate::{
     central_state,
     cpi::Cpi,
+    events::Event,
     state::ReverseLookup,
-    utils::get_hashed_name,
+    utils::{check_root_domain, get_hashed_name, next_tld_account},
 };
 
-use crate::constants::ROOT_DOMAIN_ACCOUNT;
 use bonfida_utils::{
     checks::{check_account_key, check_account_owner, check_signer},
     BorshSize, InstructionsAccount,
@@ -34,7 +34,7 @@ pub struct Accounts<'a, T> {
     /// The name service program account
     pub naming_service_program: &'a T,
 
-    /// The root domain account
+    /// The root domain account, the default root domain or the root of an enabled TLD
     pub root_domain: &'a T,
 
     /// The reverse lookup account
@@ -53,6 +53,9 @@ pub struct Accounts<'a, T> {
 
     /// The rent sysvar account
     pub rent_sysvar: &'a T,
+
+    /// The TLD account, required when the root domain is not the default one
+    pub tld_opt: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -61,19 +64,22 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         _program_id: &Pubkey,
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
+        let naming_service_program = next_account_info(accounts_iter)?;
+        let root_domain = next_account_info(accounts_iter)?;
         let accounts = Accounts {
-            naming_service_program: next_account_info(accounts_iter)?,
-            root_domain: next_account_info(accounts_iter)?,
+            naming_service_program,
+            root_domain,
             reverse_lookup: next_account_info(accounts_iter)?,
             system_program: next_account_info(accounts_iter)?,
             central_state: next_account_info(accounts_iter)?,
             domain_owner: next_account_info(accounts_iter)?,
             rent_sysvar: next_account_info(accounts_iter)?,
+            tld_opt: next_tld_account(accounts_iter, root_domain)?,
         };
 
         // Check keys
         check_account_key(accounts.naming_service_program, &spl_name_service::id()).unwrap();
-        check_account_key(accounts.root_domain, &ROOT_DOMAIN_ACCOUNT).unwrap();
+        check_root_domain(accounts.root_domain, accounts.tld_opt)?;
         check_account_key(accounts.system_program, &system_program::id()).unwrap();
         check_account_key(accounts.central_state, &central_state::KEY).unwrap();
         check_account_key(accounts.rent_sysvar, &sysvar::rent::id()).unwrap();
@@ -129,7 +135,7 @@ pub fn update_metadata<'a, 'b: 'a>(
         .map_err(|_| ProgramError::InvalidAccountData)?;
 
     // Update the metadata URL
-    reverse_lookup.metadata_url = params.metadata_url;
+    reverse_lookup.metadata_url = params.metadata_url.clone();
 
     // Serialize the updated data
     let updated_data = borsh::BorshSerialize::try_to_vec(&reverse_lookup)
@@ -150,6 +156,12 @@ pub fn update_metadata<'a, 'b: 'a>(
         central_state_signer_seeds,
     )?;
 
+    Event::MetadataUpdated {
+        reverse_lookup: *accounts.reverse_lookup.key,
+        metadata_url: params.metadata_url,
+    }
+    .emit();
+
     msg!("Metadata URL updated successfully");
     Ok(())
 } 
\ No newline at end of file
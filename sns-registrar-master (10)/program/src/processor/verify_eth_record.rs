@@ -0,0 +1,136 @@
+//! Verify the right of association of an ETH record with a `personal_sign` signature
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner},
+    BorshSize, InstructionsAccount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    secp256k1_recover::secp256k1_recover,
+};
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    address::{eth_address_from_pubkey, eth_personal_message_hash, is_low_s},
+    central_state,
+    cpi::Cpi,
+    events::Event,
+    state::record::{Record, RecordData},
+    utils::get_record_key,
+    Error,
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize, Debug)]
+/// The required parameters for the `verify_eth_record` instruction
+pub struct Params {
+    /// The `r` and `s` values of the signature of `RecordData::eth_verification_message`
+    pub signature: [u8; 64],
+    /// The recovery id of the signature, either 0 and 1 or 27 and 28
+    pub recovery_id: u8,
+}
+
+#[derive(InstructionsAccount)]
+/// The required accounts for the `verify_eth_record` instruction
+pub struct Accounts<'a, T> {
+    /// The naming service program ID
+    pub naming_service_program: &'a T,
+    /// The central state account
+    pub central_state: &'a T,
+    /// The domain name account
+    pub domain: &'a T,
+    /// The ETH record account
+    #[cons(writable)]
+    pub record: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        accounts: &'a [AccountInfo<'b>],
+        _program_id: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            naming_service_program: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            domain: next_account_info(accounts_iter)?,
+            record: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.naming_service_program, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+        check_account_key(
+            accounts.record,
+            &get_record_key(accounts.domain.key, Record::Eth),
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.domain, &spl_name_service::ID)?;
+        check_account_owner(accounts.record, &spl_name_service::ID)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process_verify_eth_record(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(accounts, program_id)?;
+
+    let header = NameRecordHeader::unpack_from_slice(&accounts.domain.data.borrow())?;
+    let mut record_data =
+        RecordData::from_buffer(&accounts.record.data.borrow()[NameRecordHeader::LEN..])?;
+    if record_data.is_stale(&header.owner) {
+        msg!("The record was written by a previous owner of the domain");
+        return Err(Error::InvalidRecord.into());
+    }
+
+    let message = record_data
+        .eth_verification_message(accounts.domain.key)
+        .ok_or(Error::InvalidRecord)?;
+    let recovery_id = match params.recovery_id {
+        27 | 28 => params.recovery_id - 27,
+        id => id,
+    };
+    if !is_low_s(&params.signature) {
+        msg!("The signature is not canonical, s must be in the lower half of the curve order");
+        return Err(Error::InvalidSignature.into());
+    }
+    let signer = secp256k1_recover(
+        &eth_personal_message_hash(message.as_bytes()),
+        recovery_id,
+        &params.signature,
+    )
+    .map_err(|_| Error::InvalidSignature)?;
+    if record_data.content != eth_address_from_pubkey(&signer.to_bytes()) {
+        msg!("The signature was not made by the address of the record");
+        return Err(Error::InvalidSignature.into());
+    }
+
+    record_data.right_of_association = true;
+    let central_state_signer_seeds: &[&[u8]] = &[&program_id.to_bytes(), &[central_state::NONCE]];
+    Cpi::write_name_account(
+        accounts.naming_service_program,
+        accounts.record,
+        accounts.central_state,
+        record_data.to_padded_vec(),
+        central_state_signer_seeds,
+    )?;
+
+    msg!("ETH record verified");
+    Event::RecordVerified {
+        domain: *accounts.domain.key,
+        record: Record::Eth,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -1,7 +1,37 @@
+use bonfida_utils::BorshSize;
 use borsh::{BorshDeserialize, BorshSerialize};
 
-#[derive(BorshDeserialize, BorshSerialize)]
+pub mod auction;
+pub mod claim_tree;
+pub mod dutch_auction;
+pub mod nft_campaign;
+pub mod nft_record;
+pub mod primary_domain;
+pub mod record;
+pub mod registration;
+pub mod tld;
+pub mod voucher;
+
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq, Eq)]
 pub struct ReverseLookup {
     pub name: String,
     pub metadata_url: Option<String>, // New field for metadata URL
 }
+
+/// Discriminator of the accounts owned by the registrar program
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Eq, Debug, Clone, Copy)]
+#[allow(missing_docs)]
+pub enum Tag {
+    Uninitialized,
+    Auction,
+    RegistrationRecord,
+    DutchAuction,
+    ActiveNftRecord,
+    InactiveNftRecord,
+    NftCampaign,
+    NftClaim,
+    VoucherNonce,
+    ClaimTree,
+    Tld,
+    PrimaryDomain,
+}
@@ -0,0 +1,92 @@
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use super::Tag;
+use crate::error::Error;
+
+/// English auction of a domain name.
+///
+/// The account lives at the legacy name auctioning state address (seeds: `[name_account]`),
+/// which prevents the name from being bought through the regular create instructions while
+/// the auction is running.
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+pub struct Auction {
+    pub tag: Tag,
+    pub nonce: u8,
+    /// The auctioned name account
+    pub name_account: Pubkey,
+    /// The mint in which bids are denominated
+    pub quote_mint: Pubkey,
+    /// The token account escrowing the highest bid
+    pub escrow: Pubkey,
+    /// The account which paid for the auction accounts and receives the rent back on settlement
+    pub rent_payer: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Minimum amount of the first bid
+    pub reserve_price: u64,
+    /// Amount of the highest bid, zero if no bid has been placed
+    pub highest_bid: u64,
+    /// The highest bidder, becomes the owner of the name on settlement and is refunded to its
+    /// associated token account when outbid
+    pub highest_bidder: Pubkey,
+    /// The whitelisted referrer token account attached to the highest bid, default if none
+    pub referrer: Pubkey,
+    /// Owner of the referrer token account when the highest bid was placed, the referrer is only
+    /// paid if it still owns the account on settlement
+    pub referrer_owner: Pubkey,
+    /// Fee (in percent) of the referrer, fixed when the highest bid was placed
+    pub referrer_fee_pct: u64,
+    /// Space allocated to the name account on settlement
+    pub space: u32,
+    pub name: String,
+}
+
+impl Auction {
+    pub const ESCROW_SEED: &'static [u8; 6] = b"escrow";
+    pub const MIN_DURATION: i64 = 86_400;
+    pub const MAX_DURATION: i64 = 30 * 86_400;
+    /// A bid placed within this window of the end extends the auction by the same amount
+    pub const TIME_EXTENSION: i64 = 15 * 60;
+    /// Minimum increase (in percent) of a bid over the current highest bid
+    pub const MIN_INCREMENT_PCT: u64 = 5;
+
+    pub fn find_key(name_account: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[&name_account.to_bytes()], program_id)
+    }
+
+    pub fn find_escrow_key(auction: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[&auction.to_bytes(), Auction::ESCROW_SEED];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<Auction, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data.is_empty() || data[0] != Tag::Auction as u8 {
+            return Err(Error::DataTypeMismatch.into());
+        }
+        let result = Auction::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Smallest amount accepted for the next bid
+    pub fn min_next_bid(&self) -> Result<u64, ProgramError> {
+        if self.highest_bid == 0 {
+            return Ok(self.reserve_price.max(1));
+        }
+        let increment = self
+            .highest_bid
+            .checked_mul(Self::MIN_INCREMENT_PCT)
+            .ok_or(Error::Overflow)?
+            / 100;
+        Ok(self
+            .highest_bid
+            .checked_add(increment.max(1))
+            .ok_or(Error::Overflow)?)
+    }
+}
@@ -0,0 +1,137 @@
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::MAX_PERMITTED_DATA_INCREASE, hash::hashv,
+    program_error::ProgramError, pubkey::Pubkey,
+};
+
+use super::Tag;
+use crate::error::Error;
+
+/// Allowlist of (name, wallet) pairs that can be claimed for free.
+///
+/// The leaves of the Merkle tree are `hash(0x00 || wallet || name)` and the nodes
+/// `hash(0x01 || left || right)`, the position of a leaf in the tree is its claim index.
+///
+/// The account is created through CPI, which caps its size to `MAX_PERMITTED_DATA_INCREASE`
+/// bytes, so a tree has at most `MAX_LEAVES` leaves. Larger allowlists are split over several trees.
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+pub struct ClaimTree {
+    pub tag: Tag,
+    pub nonce: u8,
+    /// Identifier chosen by the admin when publishing the tree
+    pub id: u64,
+    /// The Merkle root of the allowlist
+    pub root: [u8; 32],
+    /// Number of leaves of the tree
+    pub num_leaves: u32,
+    /// Bitmap of the claimed leaves
+    pub claimed: Vec<u8>,
+}
+
+impl ClaimTree {
+    pub const SEED: &'static [u8; 10] = b"claim_tree";
+    /// Size of the account without the claimed bitmap
+    pub const HEADER_LEN: usize = 1 + 1 + 8 + 32 + 4 + 4;
+    /// Number of leaves whose claimed bitmap fits in an account created through CPI
+    pub const MAX_LEAVES: u32 = ((MAX_PERMITTED_DATA_INCREASE - Self::HEADER_LEN) * 8) as u32;
+
+    pub fn new(nonce: u8, id: u64, root: [u8; 32], num_leaves: u32) -> Self {
+        Self {
+            tag: Tag::ClaimTree,
+            nonce,
+            id,
+            root,
+            num_leaves,
+            claimed: vec![0; (num_leaves as usize + 7) / 8],
+        }
+    }
+
+    pub fn find_key(id: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[ClaimTree::SEED, &id.to_le_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<ClaimTree, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data.is_empty() || data[0] != Tag::ClaimTree as u8 {
+            return Err(Error::DataTypeMismatch.into());
+        }
+        let result = ClaimTree::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.claimed[index as usize / 8] |= 1 << (index % 8)
+    }
+
+    pub fn get_leaf(name: &str, wallet: &Pubkey) -> [u8; 32] {
+        hashv(&[&[0], &wallet.to_bytes(), name.as_bytes()]).to_bytes()
+    }
+
+    /// Checks that `leaf` is at position `index` of the tree
+    pub fn verify_proof(&self, index: u32, leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+        if index >= self.num_leaves || proof.len() >= 32 || index >> proof.len() != 0 {
+            return false;
+        }
+        let node = proof
+            .iter()
+            .enumerate()
+            .fold(leaf, |node, (depth, sibling)| {
+                if (index >> depth) & 1 == 0 {
+                    hashv(&[&[1], &node, sibling]).to_bytes()
+                } else {
+                    hashv(&[&[1], sibling, &node]).to_bytes()
+                }
+            });
+        node == self.root
+    }
+}
+
+#[test]
+fn test_claim_tree() {
+    let node = |l: &[u8; 32], r: &[u8; 32]| hashv(&[&[1], l, r]).to_bytes();
+    let wallets = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let leaves = [
+        ClaimTree::get_leaf("alice", &wallets[0]),
+        ClaimTree::get_leaf("bob", &wallets[1]),
+        ClaimTree::get_leaf("carol", &wallets[2]),
+        [0; 32],
+    ];
+    let (n01, n23) = (node(&leaves[0], &leaves[1]), node(&leaves[2], &leaves[3]));
+    let mut tree = ClaimTree::new(0, 0, node(&n01, &n23), 3);
+
+    assert!(tree.verify_proof(0, leaves[0], &[leaves[1], n23]));
+    assert!(tree.verify_proof(2, leaves[2], &[leaves[3], n01]));
+    assert!(!tree.verify_proof(1, leaves[0], &[leaves[1], n23]));
+    assert!(!tree.verify_proof(
+        1,
+        ClaimTree::get_leaf("bob", &wallets[0]),
+        &[leaves[0], n23]
+    ));
+    // Padding leaf
+    assert!(!tree.verify_proof(3, leaves[3], &[leaves[2], n01]));
+
+    assert!(!tree.is_claimed(2));
+    tree.set_claimed(2);
+    assert!(tree.is_claimed(2));
+    assert!(!tree.is_claimed(1));
+
+    let largest = ClaimTree::new(0, 0, [0; 32], ClaimTree::MAX_LEAVES);
+    assert_eq!(
+        largest.try_to_vec().unwrap().len(),
+        MAX_PERMITTED_DATA_INCREASE
+    );
+}
@@ -0,0 +1,113 @@
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use super::Tag;
+use crate::error::Error;
+
+/// Shape of the decay of the release premium
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum PremiumCurve {
+    /// The premium decreases by the same amount every second
+    Linear,
+    /// The premium halves every `half_life` seconds
+    Exponential { half_life: i64 },
+}
+
+/// Declining premium of a name released after its expiry.
+///
+/// Like [`Auction`](super::auction::Auction), the account lives at the legacy name auctioning
+/// state address (seeds: `[name_account]`). The premium is added to the regular price of the name
+/// and the account is closed when the name is registered again.
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+pub struct DutchAuction {
+    pub tag: Tag,
+    pub nonce: u8,
+    /// The released name account
+    pub name_account: Pubkey,
+    /// Unix timestamp at which the name was released
+    pub released_at: i64,
+    /// Premium (USD with 6 decimals) at release time
+    pub start_premium: u64,
+    /// Time after which the premium reaches zero
+    pub window: i64,
+    pub curve: PremiumCurve,
+}
+
+impl DutchAuction {
+    pub fn find_key(name_account: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[&name_account.to_bytes()], program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<DutchAuction, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data.is_empty() || data[0] != Tag::DutchAuction as u8 {
+            return Err(Error::DataTypeMismatch.into());
+        }
+        let result = DutchAuction::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Premium (USD with 6 decimals) to pay on top of the regular price at `now`
+    pub fn get_premium(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.released_at).max(0);
+        if elapsed >= self.window {
+            return 0;
+        }
+        match self.curve {
+            PremiumCurve::Linear => {
+                ((self.start_premium as u128) * ((self.window - elapsed) as u128)
+                    / (self.window as u128)) as u64
+            }
+            PremiumCurve::Exponential { half_life } => {
+                // Offset the curve so that the premium reaches zero at the end of the window
+                let floor = decay(self.start_premium, self.window, half_life);
+                decay(self.start_premium, elapsed, half_life).saturating_sub(floor)
+            }
+        }
+    }
+}
+
+/// `amount * 2^(-elapsed / half_life)`, linearly interpolated between two halvings
+fn decay(amount: u64, elapsed: i64, half_life: i64) -> u64 {
+    if half_life <= 0 {
+        return 0;
+    }
+    let halvings = elapsed / half_life;
+    if halvings >= 64 {
+        return 0;
+    }
+    let value = amount >> halvings;
+    let remainder = (elapsed % half_life) as u128;
+    value - ((value / 2) as u128 * remainder / half_life as u128) as u64
+}
+
+#[test]
+fn test_premium() {
+    let mut auction = DutchAuction {
+        tag: Tag::DutchAuction,
+        nonce: 0,
+        name_account: Pubkey::default(),
+        released_at: 1_000,
+        start_premium: 1_000_000,
+        window: 1_000,
+        curve: PremiumCurve::Linear,
+    };
+    assert_eq!(auction.get_premium(0), 1_000_000);
+    assert_eq!(auction.get_premium(1_000), 1_000_000);
+    assert_eq!(auction.get_premium(1_250), 750_000);
+    assert_eq!(auction.get_premium(2_000), 0);
+    assert_eq!(auction.get_premium(5_000), 0);
+
+    auction.curve = PremiumCurve::Exponential { half_life: 100 };
+    let end = decay(1_000_000, 1_000, 100);
+    assert_eq!(auction.get_premium(1_000), 1_000_000 - end);
+    assert_eq!(auction.get_premium(1_100), 500_000 - end);
+    assert_eq!(auction.get_premium(1_150), 375_000 - end);
+    assert_eq!(auction.get_premium(1_999), decay(1_000_000, 999, 100) - end);
+    assert_eq!(auction.get_premium(2_000), 0);
+}
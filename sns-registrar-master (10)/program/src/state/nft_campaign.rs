@@ -0,0 +1,76 @@
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use super::Tag;
+use crate::error::Error;
+
+/// What happens to the NFT used to claim a domain name
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum NftCampaignMode {
+    /// The NFT is burned
+    Burn,
+    /// The NFT is kept by its holder but can only be used for one claim
+    Lock,
+    /// Holding the NFT is enough, it can be used for several claims
+    Check,
+}
+
+/// Registration campaign gated by the holding of an NFT of a verified collection
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+pub struct NftCampaign {
+    pub tag: Tag,
+    pub nonce: u8,
+    /// The mint of the verified collection
+    pub collection: Pubkey,
+    pub mode: NftCampaignMode,
+    /// Maximum length (in graphemes) of the names that can be claimed
+    pub max_name_length: u32,
+    /// Maximum number of names that can be claimed through the campaign
+    pub max_claims: u64,
+    /// Number of names claimed so far
+    pub claims: u64,
+}
+
+impl NftCampaign {
+    pub const SEED: &'static [u8; 12] = b"nft_campaign";
+
+    pub fn find_key(collection: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[NftCampaign::SEED, &collection.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<NftCampaign, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data.is_empty() || data[0] != Tag::NftCampaign as u8 {
+            return Err(Error::DataTypeMismatch.into());
+        }
+        let result = NftCampaign::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+/// Marks an NFT as used by a campaign in `Lock` mode
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+pub struct NftClaim {
+    pub tag: Tag,
+    /// The name account claimed with the NFT
+    pub name_account: Pubkey,
+}
+
+impl NftClaim {
+    pub const SEED: &'static [u8; 9] = b"nft_claim";
+
+    pub fn find_key(campaign: &Pubkey, nft_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[NftClaim::SEED, &campaign.to_bytes(), &nft_mint.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+}
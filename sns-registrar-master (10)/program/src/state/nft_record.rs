@@ -0,0 +1,59 @@
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use super::Tag;
+use crate::error::Error;
+
+/// Custody record of a tokenized domain name.
+///
+/// While the record is active, the name account is owned by the record and the holder of the
+/// NFT controls the domain.
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+pub struct NftRecord {
+    /// `ActiveNftRecord` while the domain is tokenized, `InactiveNftRecord` once redeemed
+    pub tag: Tag,
+    pub nonce: u8,
+    /// The tokenized name account
+    pub name_account: Pubkey,
+    /// The owner of the name account when it was tokenized
+    pub owner: Pubkey,
+    /// The mint of the NFT, a new mint for each tokenization
+    pub nft_mint: Pubkey,
+}
+
+impl NftRecord {
+    pub const SEED: &'static [u8; 10] = b"nft_record";
+
+    pub fn new(nonce: u8, name_account: Pubkey, owner: Pubkey, nft_mint: Pubkey) -> Self {
+        Self {
+            tag: Tag::ActiveNftRecord,
+            nonce,
+            name_account,
+            owner,
+            nft_mint,
+        }
+    }
+
+    pub fn find_key(name_account: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[NftRecord::SEED, &name_account.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo, tag: Tag) -> Result<NftRecord, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data.is_empty() || data[0] != tag as u8 {
+            return Err(Error::DataTypeMismatch.into());
+        }
+        let result = NftRecord::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.tag == Tag::ActiveNftRecord
+    }
+}
@@ -0,0 +1,248 @@
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
+};
+use spl_name_service::state::{get_seeds_and_key, NameRecordHeader};
+
+use super::{registration::RegistrationRecord, ReverseLookup, Tag};
+use crate::{error::Error, utils::get_hashed_name};
+
+/// Name a wallet wants to be displayed with.
+///
+/// The record only holds while the wallet owns the name account, resolvers ignore it once the
+/// name has been transferred away.
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+pub struct PrimaryDomain {
+    pub tag: Tag,
+    pub nonce: u8,
+    pub wallet: Pubkey,
+    /// The name account of the primary domain
+    pub name_account: Pubkey,
+}
+
+impl PrimaryDomain {
+    pub const SEED: &'static [u8; 14] = b"primary_domain";
+
+    pub fn new(nonce: u8, wallet: Pubkey, name_account: Pubkey) -> Self {
+        Self {
+            tag: Tag::PrimaryDomain,
+            nonce,
+            wallet,
+            name_account,
+        }
+    }
+
+    pub fn find_key(wallet: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[PrimaryDomain::SEED, &wallet.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<PrimaryDomain, ProgramError> {
+        Self::from_buffer(&a.data.borrow())
+    }
+
+    pub fn from_buffer(mut data: &[u8]) -> Result<PrimaryDomain, ProgramError> {
+        if data.is_empty() || data[0] != Tag::PrimaryDomain as u8 {
+            return Err(Error::DataTypeMismatch.into());
+        }
+        let result = PrimaryDomain::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Whether the wallet still owns the name account, `name_owner` being read from its header
+    pub fn is_verified(&self, name_owner: &Pubkey) -> bool {
+        self.wallet == *name_owner
+    }
+}
+
+/// Returns the primary domain name of a wallet from its `PrimaryDomain` account and from the keys
+/// and data of the accounts it points to, or `None` when the record is stale. `program_id` is the
+/// registrar owning the `PrimaryDomain` account, whose central state owns the reverse lookups:
+/// - `name_account` is the name account of the record, still owned by the wallet
+/// - `reverse_lookup` is the reverse lookup account of that name
+/// - `registration_record` is the registration record of the domain, the parent domain of a
+///   subdomain, if it exists. The name is not verified once the registration expired.
+pub fn get_verified_primary_domain(
+    program_id: &Pubkey,
+    primary_domain_data: &[u8],
+    name_account: (&Pubkey, &[u8]),
+    reverse_lookup: (&Pubkey, &[u8]),
+    registration_record: Option<(&Pubkey, &[u8])>,
+    now: i64,
+) -> Option<String> {
+    let primary_domain = PrimaryDomain::from_buffer(primary_domain_data).ok()?;
+    let (name_account_key, name_account_data) = name_account;
+    if primary_domain.name_account != *name_account_key {
+        return None;
+    }
+    let header = NameRecordHeader::unpack_from_slice(name_account_data).ok()?;
+    if !primary_domain.is_verified(&header.owner) {
+        return None;
+    }
+
+    let (reverse_lookup_key, reverse_lookup_data) = reverse_lookup;
+    let (central_state, _) = Pubkey::find_program_address(&[&program_id.to_bytes()], program_id);
+    let reverse_key = |parent: Option<&Pubkey>| {
+        get_seeds_and_key(
+            &spl_name_service::ID,
+            get_hashed_name(&name_account_key.to_string()),
+            Some(&central_state),
+            parent,
+        )
+        .0
+    };
+    if *reverse_lookup_key != reverse_key(None)
+        && *reverse_lookup_key != reverse_key(Some(&header.parent_name))
+    {
+        return None;
+    }
+
+    if let Some((registration_record_key, registration_record_data)) = registration_record {
+        let domain = [*name_account_key, header.parent_name]
+            .iter()
+            .find(|domain| {
+                *registration_record_key == RegistrationRecord::find_key(domain, program_id).0
+            })?;
+        let record = RegistrationRecord::from_buffer(registration_record_data).ok()?;
+        if record.name_account != *domain || record.is_expired(now) {
+            return None;
+        }
+    }
+
+    let reverse_lookup =
+        ReverseLookup::deserialize(&mut reverse_lookup_data.get(NameRecordHeader::LEN..)?).ok()?;
+    Some(reverse_lookup.name)
+}
+
+#[test]
+fn test_verified_primary_domain() {
+    let wallet = Pubkey::new_unique();
+    let parent = Pubkey::new_unique();
+    let name_key = crate::utils::get_name_key("bonfida", Some(&parent)).unwrap();
+    let primary_domain = PrimaryDomain::new(255, wallet, name_key)
+        .try_to_vec()
+        .unwrap();
+    let name_account = |owner: Pubkey| {
+        let mut data = vec![0; NameRecordHeader::LEN];
+        NameRecordHeader {
+            parent_name: parent,
+            owner,
+            class: Pubkey::default(),
+        }
+        .pack_into_slice(&mut data);
+        data
+    };
+    let reverse_key = crate::utils::get_reverse_key(&name_key, None).unwrap();
+    let mut reverse_lookup = vec![0; NameRecordHeader::LEN];
+    ReverseLookup {
+        name: "bonfida".to_owned(),
+        metadata_url: None,
+    }
+    .serialize(&mut reverse_lookup)
+    .unwrap();
+    let (registration_key, nonce) = RegistrationRecord::find_key(&name_key, &crate::ID);
+    let registration = RegistrationRecord::new(nonce, name_key, 1_000)
+        .try_to_vec()
+        .unwrap();
+    let owned = name_account(wallet);
+
+    assert_eq!(
+        get_verified_primary_domain(
+            &crate::ID,
+            &primary_domain,
+            (&name_key, &owned),
+            (&reverse_key, &reverse_lookup),
+            Some((&registration_key, &registration)),
+            1_000
+        ),
+        Some("bonfida".to_owned())
+    );
+    // Names without registration record never expire
+    assert_eq!(
+        get_verified_primary_domain(
+            &crate::ID,
+            &primary_domain,
+            (&name_key, &owned),
+            (&reverse_key, &reverse_lookup),
+            None,
+            1_000
+        ),
+        Some("bonfida".to_owned())
+    );
+    // Transferred away
+    assert_eq!(
+        get_verified_primary_domain(
+            &crate::ID,
+            &primary_domain,
+            (&name_key, &name_account(Pubkey::new_unique())),
+            (&reverse_key, &reverse_lookup),
+            None,
+            1_000
+        ),
+        None
+    );
+    // Another name account owned by the wallet
+    assert_eq!(
+        get_verified_primary_domain(
+            &crate::ID,
+            &primary_domain,
+            (&Pubkey::new_unique(), &owned),
+            (&reverse_key, &reverse_lookup),
+            None,
+            1_000
+        ),
+        None
+    );
+    // Reverse lookup of another name
+    assert_eq!(
+        get_verified_primary_domain(
+            &crate::ID,
+            &primary_domain,
+            (&name_key, &owned),
+            (&Pubkey::new_unique(), &reverse_lookup),
+            None,
+            1_000
+        ),
+        None
+    );
+    // Expired, or the registration record of another name
+    assert_eq!(
+        get_verified_primary_domain(
+            &crate::ID,
+            &primary_domain,
+            (&name_key, &owned),
+            (&reverse_key, &reverse_lookup),
+            Some((&registration_key, &registration)),
+            1_001
+        ),
+        None
+    );
+    assert_eq!(
+        get_verified_primary_domain(
+            &crate::ID,
+            &primary_domain,
+            (&name_key, &owned),
+            (&reverse_key, &reverse_lookup),
+            Some((&Pubkey::new_unique(), &registration)),
+            1_000
+        ),
+        None
+    );
+    // Reverse lookup and registration record of another deployment
+    assert_eq!(
+        get_verified_primary_domain(
+            &Pubkey::new_unique(),
+            &primary_domain,
+            (&name_key, &owned),
+            (&reverse_key, &reverse_lookup),
+            None,
+            1_000
+        ),
+        None
+    );
+}
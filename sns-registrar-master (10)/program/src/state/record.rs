@@ -0,0 +1,213 @@
+use std::convert::TryInto;
+
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{msg, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{address, content, dns, error::Error};
+
+/// Type of a record stored under a domain name.
+///
+/// Records are name accounts whose parent is the domain, owned by the central state and named
+/// after `Record::name`. Their data is a `RecordData` padded with zeros to `RecordData::space`.
+/// Resolvers must ignore stale records, see `RecordData::is_stale`. DNS records hold a whole
+/// RRset, see the `dns` module.
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Record {
+    Sol,
+    Eth,
+    Btc,
+    Bsc,
+    Inj,
+    A,
+    Aaaa,
+    Cname,
+    Txt,
+    Mx,
+    Ipfs,
+    Arwv,
+}
+
+impl Record {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Record::Sol => "SOL",
+            Record::Eth => "ETH",
+            Record::Btc => "BTC",
+            Record::Bsc => "BSC",
+            Record::Inj => "INJ",
+            Record::A => "A",
+            Record::Aaaa => "AAAA",
+            Record::Cname => "CNAME",
+            Record::Txt => "TXT",
+            Record::Mx => "MX",
+            Record::Ipfs => "IPFS",
+            Record::Arwv => "ARWV",
+        }
+    }
+
+    /// Name of the record account, hashed to derive its key
+    pub fn name(&self) -> String {
+        format!("\x02{}", self.as_str())
+    }
+
+    /// Maximum length of the content of the record
+    pub fn max_content_len(&self) -> usize {
+        match self {
+            Record::Sol => 32,
+            Record::Eth | Record::Bsc => 20,
+            Record::Btc | Record::Inj => address::MAX_BECH32_LEN,
+            Record::A | Record::Aaaa | Record::Cname | Record::Txt | Record::Mx => {
+                dns::max_content_len(*self)
+            }
+            Record::Ipfs => content::MAX_CID_LEN,
+            Record::Arwv => content::ARWEAVE_TX_ID_LEN,
+        }
+    }
+
+    /// Validates a value entered by a user and returns the content to store
+    pub fn parse_value(&self, value: &str) -> Result<Vec<u8>, ProgramError> {
+        let content = match self {
+            Record::Sol => address::parse_sol(value).map(|k| k.to_bytes().to_vec()),
+            Record::Eth | Record::Bsc => address::parse_evm(value).map(|a| a.to_vec()),
+            Record::Btc => address::parse_btc(value).map(String::into_bytes),
+            Record::Inj => address::parse_inj(value).map(String::into_bytes),
+            Record::A | Record::Aaaa | Record::Cname | Record::Txt | Record::Mx => {
+                dns::encode_rrset(*self, value)
+            }
+            Record::Ipfs => content::parse_ipfs(value),
+            Record::Arwv => content::parse_arweave(value).map(|id| id.to_vec()),
+        };
+        content.ok_or_else(|| {
+            msg!("Invalid {} record value: {}", self.as_str(), value);
+            Error::InvalidRecord.into()
+        })
+    }
+
+    /// Renders stored content in the format accepted by `parse_value`
+    pub fn display(&self, content: &[u8]) -> Option<String> {
+        match self {
+            Record::Sol => Some(Pubkey::new_from_array(content.try_into().ok()?).to_string()),
+            Record::Eth | Record::Bsc => {
+                Some(address::to_checksum_address(content.try_into().ok()?))
+            }
+            Record::Btc | Record::Inj => String::from_utf8(content.to_vec()).ok(),
+            Record::A | Record::Aaaa | Record::Cname | Record::Txt | Record::Mx => {
+                dns::display_rrset(*self, content)
+            }
+            Record::Ipfs => content::display_ipfs(content),
+            Record::Arwv => content::display_arweave(content),
+        }
+    }
+}
+
+/// Data of a record account, after the `NameRecordHeader`
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct RecordData {
+    pub record: Record,
+    /// Owner of the domain when the record was written, the record is stale once the domain
+    /// changes hands
+    pub staleness_owner: Pubkey,
+    /// Whether the address of the record proved that it consents to the association
+    pub right_of_association: bool,
+    pub content: Vec<u8>,
+}
+
+impl RecordData {
+    /// Validates `value` and creates a record bound to the current owner of the domain
+    pub fn new(record: Record, value: &str, domain_owner: Pubkey) -> Result<Self, ProgramError> {
+        Ok(Self {
+            record,
+            staleness_owner: domain_owner,
+            right_of_association: false,
+            content: record.parse_value(value)?,
+        })
+    }
+
+    /// Space allocated to the record accounts of type `record`
+    pub fn space(record: Record) -> usize {
+        1 + 32 + 1 + 4 + record.max_content_len()
+    }
+
+    /// Serializes the record, padded to the space of the account
+    pub fn to_padded_vec(&self) -> Vec<u8> {
+        let mut data = self.try_to_vec().unwrap();
+        data.resize(Self::space(self.record), 0);
+        data
+    }
+
+    /// Deserializes the data of a record account, ignoring the padding
+    pub fn from_buffer(mut data: &[u8]) -> Result<RecordData, ProgramError> {
+        let result = RecordData::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Whether the record was written by a previous owner of the domain and must be ignored
+    pub fn is_stale(&self, domain_owner: &Pubkey) -> bool {
+        self.staleness_owner != *domain_owner
+    }
+
+    /// Message an ETH address signs with `personal_sign` to prove its right of association. It
+    /// names the owner who wrote the record, so that a signature cannot be replayed once the
+    /// domain changed hands.
+    pub fn eth_verification_message(&self, domain: &Pubkey) -> Option<String> {
+        if self.record != Record::Eth {
+            return None;
+        }
+        Some(format!(
+            "Link {} to the domain name account {} owned by {}",
+            self.record.display(&self.content)?,
+            domain,
+            self.staleness_owner
+        ))
+    }
+
+    /// Records the right of association of a SOL record, proven by the signature of its address
+    pub fn set_sol_right_of_association(&mut self, signer: &Pubkey) -> Result<(), ProgramError> {
+        if self.record != Record::Sol || self.content != signer.as_ref() {
+            msg!("The right of association must be signed by the address of a SOL record");
+            return Err(Error::InvalidRecord.into());
+        }
+        self.right_of_association = true;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_record_data() {
+    let owner = Pubkey::new_unique();
+    let record = RecordData::new(
+        Record::Btc,
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+        owner,
+    )
+    .unwrap();
+    let data = record.to_padded_vec();
+    assert_eq!(data.len(), RecordData::space(Record::Btc));
+    assert_eq!(RecordData::from_buffer(&data).unwrap(), record);
+    assert_eq!(
+        Record::Btc.display(&record.content).as_deref(),
+        Some("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
+    );
+    assert!(!record.is_stale(&owner));
+    assert!(record.is_stale(&Pubkey::new_unique()));
+    assert!(Record::Eth
+        .parse_value("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
+        .is_err());
+
+    let address = Pubkey::new_unique();
+    let mut record = RecordData::new(Record::Sol, &address.to_string(), owner).unwrap();
+    assert!(record.set_sol_right_of_association(&owner).is_err());
+    record.set_sol_right_of_association(&address).unwrap();
+    assert!(record.right_of_association);
+
+    let record = RecordData::new(Record::Mx, "10 mail.example.com", owner).unwrap();
+    let data = record.to_padded_vec();
+    assert_eq!(data.len(), RecordData::space(Record::Mx));
+    assert_eq!(
+        Record::Mx
+            .display(&RecordData::from_buffer(&data).unwrap().content)
+            .as_deref(),
+        Some("10 mail.example.com.")
+    );
+}
@@ -0,0 +1,66 @@
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use super::Tag;
+use crate::error::Error;
+
+/// Registration term of a domain name. Names without a registration record, such as the names
+/// created with an NFT of a campaign, never expire.
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+pub struct RegistrationRecord {
+    pub tag: Tag,
+    pub nonce: u8,
+    /// The registered name account
+    pub name_account: Pubkey,
+    /// Unix timestamp at which the registration lapses
+    pub expiry: i64,
+}
+
+impl RegistrationRecord {
+    pub const SEED: &'static [u8; 12] = b"registration";
+    /// Time during which an expired name can still be renewed by its owner with `Renew`
+    pub const GRACE_PERIOD: i64 = 30 * 86_400;
+    /// Duration of a registration year
+    pub const YEAR: i64 = 365 * 86_400;
+
+    pub fn new(nonce: u8, name_account: Pubkey, expiry: i64) -> Self {
+        Self {
+            tag: Tag::RegistrationRecord,
+            nonce,
+            name_account,
+            expiry,
+        }
+    }
+
+    pub fn find_key(name_account: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[RegistrationRecord::SEED, &name_account.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<RegistrationRecord, ProgramError> {
+        Self::from_buffer(&a.data.borrow())
+    }
+
+    pub fn from_buffer(mut data: &[u8]) -> Result<RegistrationRecord, ProgramError> {
+        if data.is_empty() || data[0] != Tag::RegistrationRecord as u8 {
+            return Err(Error::DataTypeMismatch.into());
+        }
+        let result = RegistrationRecord::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    /// Whether the registration lapsed, the name only resolves again once renewed
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expiry < now
+    }
+
+    /// Whether the name can be taken back from its owner
+    pub fn is_reclaimable(&self, now: i64) -> bool {
+        self.expiry.saturating_add(Self::GRACE_PERIOD) < now
+    }
+}
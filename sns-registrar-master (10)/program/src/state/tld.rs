@@ -0,0 +1,47 @@
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use super::Tag;
+use crate::error::Error;
+
+/// Top level domain registered in addition to the default root domain
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+pub struct Tld {
+    pub tag: Tag,
+    pub nonce: u8,
+    /// The root name account of the TLD
+    pub root_domain: Pubkey,
+    /// Owner of the token accounts receiving the registration payments
+    pub vault_owner: Pubkey,
+    /// Whether names can be registered under the TLD
+    pub enabled: bool,
+    /// USD price (6 decimals) of names of 1 to 10 graphemes, the last entry applies to longer names
+    pub usd_prices: [u64; 10],
+}
+
+impl Tld {
+    pub const SEED: &'static [u8; 3] = b"tld";
+
+    pub fn find_key(root_domain: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[Tld::SEED, &root_domain.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<Tld, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data.is_empty() || data[0] != Tag::Tld as u8 {
+            return Err(Error::DataTypeMismatch.into());
+        }
+        let result = Tld::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    pub fn get_usd_price(&self, len: usize) -> u64 {
+        self.usd_prices[len.clamp(1, self.usd_prices.len()) - 1]
+    }
+}
@@ -0,0 +1,88 @@
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use super::Tag;
+use crate::utils::get_grapheme_len;
+
+/// The names a voucher can be redeemed for
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Eq, Debug, Clone)]
+pub enum VoucherScope {
+    /// A single domain name
+    Name(String),
+    /// Any domain name whose length (in graphemes) is within the bounds (inclusive)
+    Length { min: u32, max: u32 },
+}
+
+/// Registration voucher signed by the voucher authority
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Eq, Debug, Clone)]
+pub struct Voucher {
+    /// Unique identifier of the voucher, a voucher can only be redeemed once
+    pub nonce: u64,
+    pub scope: VoucherScope,
+    /// Discount applied to the domain price, 100 makes the registration free
+    pub discount_pct: u8,
+    /// The only account allowed to own the domain registered with the voucher
+    pub beneficiary: Pubkey,
+    /// Unix timestamp after which the voucher can no longer be redeemed
+    pub expiry: i64,
+}
+
+impl Voucher {
+    /// The message signed by the voucher authority, bound to the registrar program
+    pub fn message(&self, program_id: &Pubkey) -> Vec<u8> {
+        let mut message = program_id.to_bytes().to_vec();
+        self.serialize(&mut message).unwrap();
+        message
+    }
+
+    pub fn applies_to(&self, name: &str) -> bool {
+        match &self.scope {
+            VoucherScope::Name(n) => n == name,
+            VoucherScope::Length { min, max } => {
+                let len = get_grapheme_len(name) as u32;
+                *min <= len && len <= *max
+            }
+        }
+    }
+}
+
+/// Marks a voucher as redeemed
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, Debug)]
+pub struct VoucherNonce {
+    pub tag: Tag,
+    /// The name account registered with the voucher
+    pub name_account: Pubkey,
+}
+
+impl VoucherNonce {
+    pub const SEED: &'static [u8; 7] = b"voucher";
+
+    pub fn find_key(voucher_nonce: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds: &[&[u8]] = &[VoucherNonce::SEED, &voucher_nonce.to_le_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+}
+
+#[test]
+fn test_voucher_scope() {
+    let mut voucher = Voucher {
+        nonce: 0,
+        scope: VoucherScope::Name("bonfida".to_owned()),
+        discount_pct: 100,
+        beneficiary: Pubkey::default(),
+        expiry: 0,
+    };
+    assert!(voucher.applies_to("bonfida"));
+    assert!(!voucher.applies_to("bonfid"));
+
+    voucher.scope = VoucherScope::Length { min: 3, max: 4 };
+    assert!(!voucher.applies_to("ab"));
+    assert!(voucher.applies_to("abc"));
+    assert!(voucher.applies_to("🍍🍍🍍🍍"));
+    assert!(!voucher.applies_to("abcde"));
+}
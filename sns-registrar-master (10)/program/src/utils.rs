@@ -1,22 +1,40 @@
 use crate::{
     central_state,
     constants::{
-        REFERRER_DISCOUNT_AND_FEE, ROOT_DOMAIN_ACCOUNT, VAULT_OWNER, VAULT_OWNER_DEPRECATED,
+        FIDA_MINT, MAX_REGISTRATION_YEARS, MULTI_YEAR_DISCOUNTS_PCT, REFERRER_DISCOUNT_AND_FEE,
+        REFERRER_FEES_PCT, REFERRER_WHITELIST, ROOT_DOMAIN_ACCOUNT, VAULT_OWNER,
+        VAULT_OWNER_DEPRECATED,
     },
+    cpi::Cpi,
     processor::create_split_v2,
+    state::{
+        dutch_auction::DutchAuction, record::Record, registration::RegistrationRecord, tld::Tld,
+    },
 };
 use bonfida_utils::{
     checks::{check_account_key, check_account_owner},
     fp_math::fp32_div,
     tokens::SupportedToken,
+    BorshSize,
 };
+use borsh::{BorshDeserialize, BorshSerialize};
 
 use solana_program::{
-    account_info::AccountInfo, clock::Clock, hash::hashv, msg, program_error::ProgramError,
-    program_pack::Pack, pubkey::Pubkey, sysvar::Sysvar,
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    ed25519_program,
+    entrypoint::ProgramResult,
+    hash::hashv,
+    instruction::Instruction,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar,
+    sysvar::Sysvar,
 };
 
-use spl_name_service::state::{get_seeds_and_key, HASH_PREFIX};
+use spl_name_service::state::{get_seeds_and_key, NameRecordHeader, HASH_PREFIX};
 use spl_token::state::Account;
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -35,6 +53,24 @@ pub fn get_usd_price(len: usize) -> u64 {
     return multiplier * 1_000;
 }
 
+/// USD price of a registration of `years` years, after the multi-year discount
+pub fn get_multi_year_usd_price(usd_price: u64, years: u8) -> Result<u64, ProgramError> {
+    if years == 0 || years > MAX_REGISTRATION_YEARS {
+        msg!(
+            "Registrations last from 1 to {} years",
+            MAX_REGISTRATION_YEARS
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+    let discount_idx = (years as usize).min(MULTI_YEAR_DISCOUNTS_PCT.len()) - 1;
+    let price = usd_price
+        .checked_mul(years as u64)
+        .and_then(|p| p.checked_mul(100 - MULTI_YEAR_DISCOUNTS_PCT[discount_idx]))
+        .ok_or(crate::Error::Overflow)?
+        / 100;
+    Ok(price)
+}
+
 pub fn get_grapheme_len(name: &str) -> usize {
     name.graphemes(true).count()
 }
@@ -70,6 +106,43 @@ pub fn get_reverse_key(
     Ok(reverse_lookup_account_key)
 }
 
+/// Key of the `record` account of a domain name
+pub fn get_record_key(domain_key: &Pubkey, record: Record) -> Pubkey {
+    let (record_key, _) = get_seeds_and_key(
+        &spl_name_service::ID,
+        get_hashed_name(&record.name()),
+        Some(&central_state::KEY),
+        Some(domain_key),
+    );
+    record_key
+}
+
+/// Key of the state auction account of a domain name
+pub fn get_state_key(name_account: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (state_key, _) = Pubkey::find_program_address(&[&name_account.to_bytes()], program_id);
+    state_key
+}
+
+/// Checks that a domain name is lower case and has no space or dot
+pub fn check_domain_name(name: &str) -> ProgramResult {
+    if name != name.trim().to_lowercase() || name.contains('.') {
+        msg!("Domain names must be lower case and have no space or dot");
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Checks that `owner` is the current owner of the `domain` name account
+pub fn check_domain_owner(domain: &AccountInfo, owner: &AccountInfo) -> ProgramResult {
+    check_account_owner(domain, &spl_name_service::ID)?;
+    let header = NameRecordHeader::unpack_from_slice(&domain.data.borrow())?;
+    if header.owner != *owner.key {
+        msg!("The signer does not own the domain name");
+        return Err(crate::Error::PermissionDenied.into());
+    }
+    Ok(())
+}
+
 pub fn get_special_discount_and_fee(referrer_key: &Pubkey) -> (Option<u8>, Option<u8>) {
     #[cfg(feature = "no-special-discount-fee")]
     {
@@ -107,6 +180,32 @@ pub fn get_special_discount_and_fee(referrer_key: &Pubkey) -> (Option<u8>, Optio
     }
 }
 
+/// Percentage of a payment transferred to a whitelisted referrer
+pub fn get_referrer_fee_pct(referrer_key: &Pubkey) -> u64 {
+    let (_, special_fee) = get_special_discount_and_fee(referrer_key);
+    special_fee.map_or(REFERRER_FEES_PCT, |fee| fee as u64)
+}
+
+/// Checks that a referrer token account is owned by the whitelisted referrer at `referrer_idx_opt`
+/// and returns the referrer key
+pub fn check_referrer_token_account(
+    referrer_account: &AccountInfo,
+    referrer_idx_opt: Option<u16>,
+) -> Result<Pubkey, ProgramError> {
+    check_account_owner(referrer_account, &spl_token::ID)?;
+    let referrer_token_acc = Account::unpack(&referrer_account.data.borrow())?;
+
+    #[cfg(not(feature = "no-referrer-check"))]
+    if REFERRER_WHITELIST.get(referrer_idx_opt.ok_or(ProgramError::InvalidArgument)? as usize)
+        != Some(&referrer_token_acc.owner)
+    {
+        msg!("Referrer token account owner is not whitelisted.");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Ok(referrer_token_acc.owner)
+}
+
 pub struct PythAccounts<'a, 'b> {
     pub pyth_mapping_acc_or_feed: &'a AccountInfo<'b>,
     pub buyer_token_mint: Pubkey,
@@ -125,24 +224,295 @@ impl<'a, 'b: 'a> From<&'_ create_split_v2::Accounts<'a, AccountInfo<'b>>> for Py
     }
 }
 
-pub fn get_domain_price_checked<'a, 'b: 'a>(
+pub fn get_domain_price_checked(
     domain_name: &str,
-    accounts: &create_split_v2::Accounts<'a, AccountInfo<'b>>,
+    state: &AccountInfo,
+    pyth_accounts: &PythAccounts,
+    tld: Option<&Tld>,
+    years: u8,
 ) -> Result<u64, ProgramError> {
-    let usd_price = get_usd_price(get_grapheme_len(domain_name));
+    let usd_price = get_domain_usd_price(domain_name, state, tld, years)?;
     msg!("Registering domain for {}", usd_price);
-    let buyer_token_mint =
-        spl_token::state::Account::unpack_from_slice(&accounts.buyer_token_source.data.borrow())
-            .unwrap()
-            .mint;
 
-    let token_price =
-        get_token_usd_price_checked_v2(accounts.pyth_feed_account, &buyer_token_mint)?;
+    let token_price = get_token_usd_price_checked_v2(
+        pyth_accounts.pyth_mapping_acc_or_feed,
+        &pyth_accounts.buyer_token_mint,
+    )?;
     let domain_price = fp32_div(usd_price, token_price).unwrap();
 
     Ok(domain_price)
 }
 
+/// USD price (6 decimals) of a registration of `years` years, including the release premium
+pub fn get_domain_usd_price(
+    domain_name: &str,
+    state: &AccountInfo,
+    tld: Option<&Tld>,
+    years: u8,
+) -> Result<u64, ProgramError> {
+    let len = get_grapheme_len(domain_name);
+    let yearly_usd_price = tld.map_or_else(|| get_usd_price(len), |tld| tld.get_usd_price(len));
+    let usd_price = get_multi_year_usd_price(yearly_usd_price, years)?
+        .checked_add(get_release_premium(state)?)
+        .ok_or(crate::Error::Overflow)?;
+    Ok(usd_price)
+}
+
+/// Token amounts of a registration, set as return data by the `quote_price` instruction
+#[derive(BorshDeserialize, BorshSerialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct PriceQuote {
+    /// Price of the domain name, including the release premium and the multi-year discount
+    pub base: u64,
+    /// Total of the mint, voucher and referrer discounts
+    pub discount: u64,
+    /// Amount transferred to the referrer
+    pub referrer_fee: u64,
+    /// Amount transferred to the vault
+    pub vault_amount: u64,
+}
+
+impl PriceQuote {
+    /// Amount charged to the buyer
+    pub fn total(&self) -> Result<u64, ProgramError> {
+        self.referrer_fee
+            .checked_add(self.vault_amount)
+            .ok_or_else(|| crate::Error::Overflow.into())
+    }
+
+    /// Adds up the quotes of a batch of registrations
+    pub fn sum(quotes: &[PriceQuote]) -> Result<PriceQuote, ProgramError> {
+        quotes
+            .iter()
+            .try_fold(PriceQuote::default(), |acc, quote| {
+                Some(PriceQuote {
+                    base: acc.base.checked_add(quote.base)?,
+                    discount: acc.discount.checked_add(quote.discount)?,
+                    referrer_fee: acc.referrer_fee.checked_add(quote.referrer_fee)?,
+                    vault_amount: acc.vault_amount.checked_add(quote.vault_amount)?,
+                })
+            })
+            .ok_or_else(|| crate::Error::Overflow.into())
+    }
+}
+
+/// Applies the discounts of the payment mint, of `discount_pct` and of the referrer to the domain
+/// price and splits the payment between the referrer and the vault
+pub fn get_price_quote(
+    domain_price: u64,
+    mint: &Pubkey,
+    discount_pct: u8,
+    referrer: Option<&Pubkey>,
+) -> Result<PriceQuote, ProgramError> {
+    let apply_discount = |price: u64, discount: u64| {
+        100u64
+            .checked_sub(discount)
+            .and_then(|pct| pct.checked_mul(price))
+            .map(|p| p / 100)
+            .ok_or(crate::Error::Overflow)
+    };
+
+    let mut price = apply_discount(domain_price, discount_pct as u64)?;
+
+    // 5% discount if paid in FIDA
+    if *mint == FIDA_MINT {
+        price = apply_discount(price, 5)?;
+    }
+
+    let referrer_fee = match referrer {
+        Some(referrer) => {
+            let (discount, _) = get_special_discount_and_fee(referrer);
+            if let Some(discount) = discount {
+                price = apply_discount(price, discount as u64)?;
+            }
+            price
+                .checked_mul(get_referrer_fee_pct(referrer))
+                .ok_or(crate::Error::Overflow)?
+                / 100
+        }
+        None => 0,
+    };
+
+    Ok(PriceQuote {
+        base: domain_price,
+        discount: domain_price
+            .checked_sub(price)
+            .ok_or(crate::Error::Overflow)?,
+        referrer_fee,
+        vault_amount: price
+            .checked_sub(referrer_fee)
+            .ok_or(crate::Error::Overflow)?,
+    })
+}
+
+/// Takes the TLD account of `root_domain`, passed right after the fixed accounts of an instruction
+/// whenever `root_domain` is not the default root domain.
+/// The account key is checked against the TLD of `root_domain` in `check_root_domain`.
+pub fn next_tld_account<'a, 'b>(
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    root_domain: &AccountInfo,
+) -> Result<Option<&'a AccountInfo<'b>>, ProgramError> {
+    if root_domain.key == &ROOT_DOMAIN_ACCOUNT {
+        return Ok(None);
+    }
+    next_account_info(accounts_iter).map(Some)
+}
+
+/// Checks that `root_domain` is either the default root domain or the root of an enabled TLD,
+/// in which case the TLD is returned
+pub fn check_root_domain(
+    root_domain: &AccountInfo,
+    tld_opt: Option<&AccountInfo>,
+) -> Result<Option<Tld>, ProgramError> {
+    let tld_account = match tld_opt {
+        None if root_domain.key == &ROOT_DOMAIN_ACCOUNT => return Ok(None),
+        None => {
+            msg!("The TLD account of the root domain is missing");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        Some(tld_account) => tld_account,
+    };
+    check_account_key(tld_account, &Tld::find_key(root_domain.key, &crate::ID).0)?;
+    check_account_owner(tld_account, &crate::ID)?;
+    let tld = Tld::from_account_info(tld_account)?;
+    if !tld.enabled {
+        return Err(crate::Error::TldDisabled.into());
+    }
+    Ok(Some(tld))
+}
+
+/// Checks that the vault is owned by the vault owner of the TLD, or by the registrar vault owner
+/// under the default root domain
+pub fn check_vault(vault: &AccountInfo, tld: Option<&Tld>) -> ProgramResult {
+    match tld {
+        Some(tld) => {
+            check_account_owner(vault, &spl_token::ID)?;
+            let vault = Account::unpack(&vault.data.borrow())?;
+            if vault.owner != tld.vault_owner {
+                return Err(ProgramError::IllegalOwner);
+            }
+        }
+        None => {
+            check_vault_token_account_owner(vault)?;
+        }
+    }
+    Ok(())
+}
+
+/// Premium (USD with 6 decimals) of a name recently released from expiry
+pub fn get_release_premium(state: &AccountInfo) -> Result<u64, ProgramError> {
+    if state.data_is_empty() {
+        return Ok(0);
+    }
+    check_account_owner(state, &crate::ID)?;
+    let dutch_auction = DutchAuction::from_account_info(state)?;
+    Ok(dutch_auction.get_premium(Clock::get()?.unix_timestamp))
+}
+
+/// Checks that a registration which does not charge the release premium does not skip it: the
+/// state account has to be empty or hold a release whose premium has fully decayed
+pub fn check_no_release_premium(state: &AccountInfo) -> ProgramResult {
+    let premium = get_release_premium(state).map_err(|_| {
+        msg!("The name auctioning state account is not empty.");
+        ProgramError::InvalidArgument
+    })?;
+    if premium != 0 {
+        msg!("The name is released with a premium of {}", premium);
+        return Err(crate::Error::ReleasePremiumActive.into());
+    }
+    Ok(())
+}
+
+/// Closes the release state of a name once it is registered again, the rent goes to `destination`
+pub fn close_release_state(state: &AccountInfo, destination: &AccountInfo) {
+    if state.data_is_empty() {
+        return;
+    }
+    let mut source = state.lamports.borrow_mut();
+    let mut target = destination.lamports.borrow_mut();
+    **target += **source;
+    **source = 0;
+    state.data.borrow_mut().fill(0);
+}
+
+/// Checks the state auction account of a name being registered: only names released with a
+/// declining premium can be bought while the state is in use
+pub fn check_release_state(
+    state: &AccountInfo,
+    name_account: &Pubkey,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    check_account_key(state, &get_state_key(name_account, program_id))?;
+    if !state.data_is_empty() {
+        DutchAuction::from_account_info(state).map_err(|_| {
+            msg!("The name auctioning state account is not empty.");
+            ProgramError::InvalidArgument
+        })?;
+    }
+    Ok(())
+}
+
+/// Checks the name of a new registration and the keys of its name, reverse lookup, state auction
+/// and *optional* registration record accounts, and returns the name account key
+pub fn check_registration_accounts(
+    program_id: &Pubkey,
+    root_domain: &Pubkey,
+    name: &str,
+    name_account: &AccountInfo,
+    reverse_lookup: &AccountInfo,
+    state: &AccountInfo,
+    registration_record_opt: Option<&AccountInfo>,
+) -> Result<Pubkey, ProgramError> {
+    check_domain_name(name)?;
+    let name_account_key = get_name_key(name, Some(root_domain))?;
+    check_account_key(name_account, &name_account_key)?;
+    check_account_key(reverse_lookup, &get_reverse_key(&name_account_key, None)?)?;
+    if let Some(registration_record) = registration_record_opt {
+        check_account_key(
+            registration_record,
+            &RegistrationRecord::find_key(&name_account_key, program_id).0,
+        )?;
+    }
+    check_release_state(state, &name_account_key, program_id)?;
+    Ok(name_account_key)
+}
+
+/// Sets the expiry of `name_account` to `years` years from now, creating its registration record
+/// if needed, and returns the expiry
+pub fn write_registration_record<'a>(
+    program_id: &Pubkey,
+    system_program: &AccountInfo<'a>,
+    fee_payer: &AccountInfo<'a>,
+    registration_record: &AccountInfo<'a>,
+    name_account: &Pubkey,
+    years: u8,
+) -> Result<i64, ProgramError> {
+    let (registration_record_key, nonce) = RegistrationRecord::find_key(name_account, program_id);
+    check_account_key(registration_record, &registration_record_key)?;
+
+    let expiry = Clock::get()?
+        .unix_timestamp
+        .checked_add(RegistrationRecord::YEAR * years as i64)
+        .ok_or(crate::Error::Overflow)?;
+    let record = RegistrationRecord::new(nonce, *name_account, expiry);
+    if registration_record.data_is_empty() {
+        Cpi::create_account(
+            program_id,
+            system_program,
+            fee_payer,
+            registration_record,
+            &[RegistrationRecord::SEED, &name_account.to_bytes(), &[nonce]],
+            record.borsh_len(),
+        )?;
+    } else {
+        // Left behind by a name which expired and was reclaimed
+        check_account_owner(registration_record, program_id)?;
+        RegistrationRecord::from_account_info(registration_record)?;
+    }
+    record.save(&mut registration_record.data.borrow_mut());
+
+    Ok(expiry)
+}
+
 pub fn get_token_usd_price_checked_v2(
     pyth_feed: &AccountInfo<'_>,
     mint: &Pubkey,
@@ -160,6 +530,71 @@ pub fn get_token_usd_price_checked_v2(
     Ok(token_price)
 }
 
+/// Returns the signer and the message of an Ed25519 precompile instruction verifying a single
+/// signature whose data is embedded in the instruction itself
+pub fn get_ed25519_signed_message(ix: &Instruction) -> Result<(Pubkey, &[u8]), ProgramError> {
+    // num_signatures (u8), padding (u8) and one `Ed25519SignatureOffsets` (7 x u16)
+    const OFFSETS_END: usize = 16;
+    if ix.program_id != ed25519_program::ID || ix.data.len() < OFFSETS_END || ix.data[0] != 1 {
+        return Err(crate::Error::InvalidVoucher.into());
+    }
+    let read_u16 = |i: usize| u16::from_le_bytes([ix.data[i], ix.data[i + 1]]);
+    let signature_offset = read_u16(2) as usize;
+    let signature_ix_idx = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_ix_idx = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix_idx = read_u16(14);
+
+    // The signed data must live in the precompile instruction, not elsewhere in the transaction
+    if signature_ix_idx != u16::MAX || public_key_ix_idx != u16::MAX || message_ix_idx != u16::MAX {
+        return Err(crate::Error::InvalidVoucher.into());
+    }
+    if ix
+        .data
+        .get(signature_offset..signature_offset + 64)
+        .is_none()
+    {
+        return Err(crate::Error::InvalidVoucher.into());
+    }
+    let public_key = ix
+        .data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(crate::Error::InvalidVoucher)?;
+    let message = ix
+        .data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(crate::Error::InvalidVoucher)?;
+
+    Ok((Pubkey::try_from(public_key).unwrap(), message))
+}
+
+/// Checks that the instruction preceding the current one is an Ed25519 precompile instruction
+/// verifying the signature of `message` by `signer`
+pub fn check_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<(), ProgramError> {
+    check_account_key(instructions_sysvar, &sysvar::instructions::ID)?;
+    let current_idx = sysvar::instructions::load_current_index_checked(instructions_sysvar)?;
+    if current_idx == 0 {
+        msg!("The signature verification instruction is missing");
+        return Err(crate::Error::InvalidVoucher.into());
+    }
+    let ix = sysvar::instructions::load_instruction_at_checked(
+        current_idx as usize - 1,
+        instructions_sysvar,
+    )?;
+    let (signed_by, signed_message) = get_ed25519_signed_message(&ix)?;
+    if &signed_by != signer || signed_message != message {
+        msg!("The signature does not match the expected signer and message");
+        return Err(crate::Error::InvalidVoucher.into());
+    }
+    Ok(())
+}
+
 pub fn check_vault_token_account_owner(account: &AccountInfo) -> Result<Account, ProgramError> {
     check_account_owner(account, &spl_token::ID)?;
     let token_account = Account::unpack_from_slice(&account.data.borrow())?;
@@ -187,3 +622,90 @@ pub fn test_length() {
     assert_eq!(get_grapheme_len(&string_5), 2);
     assert_eq!(get_grapheme_len(&string_6), 1);
 }
+
+#[test]
+pub fn test_multi_year_price() {
+    assert_eq!(get_multi_year_usd_price(20_000_000, 1).unwrap(), 20_000_000);
+    assert_eq!(get_multi_year_usd_price(20_000_000, 2).unwrap(), 38_000_000);
+    assert_eq!(get_multi_year_usd_price(20_000_000, 3).unwrap(), 54_000_000);
+    assert_eq!(
+        get_multi_year_usd_price(20_000_000, 10).unwrap(),
+        160_000_000
+    );
+    assert!(get_multi_year_usd_price(20_000_000, 0).is_err());
+    assert!(get_multi_year_usd_price(20_000_000, MAX_REGISTRATION_YEARS + 1).is_err());
+}
+
+#[test]
+pub fn test_ed25519_signed_message() {
+    let signer = Pubkey::new_unique();
+    let message = b"voucher".to_vec();
+
+    let mut data = vec![1, 0];
+    let public_key_offset = 16u16;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+    for v in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&v.to_le_bytes());
+    }
+    data.extend_from_slice(&signer.to_bytes());
+    data.extend_from_slice(&[0; 64]);
+    data.extend_from_slice(&message);
+
+    let mut ix = Instruction::new_with_bytes(ed25519_program::ID, &data, vec![]);
+    assert_eq!(
+        get_ed25519_signed_message(&ix).unwrap(),
+        (signer, message.as_slice())
+    );
+
+    // Message pointing to another instruction of the transaction
+    ix.data[14] = 0;
+    assert!(get_ed25519_signed_message(&ix).is_err());
+
+    ix.data = data;
+    ix.program_id = Pubkey::new_unique();
+    assert!(get_ed25519_signed_message(&ix).is_err());
+}
+
+#[test]
+pub fn test_batch_price_quote() {
+    let prices = [20_000_000, 160_000_000, 1_000_001];
+    let quotes = prices
+        .iter()
+        .map(|price| get_price_quote(*price, &FIDA_MINT, 10, None).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        quotes[0],
+        PriceQuote {
+            base: 20_000_000,
+            discount: 2_900_000,
+            referrer_fee: 0,
+            vault_amount: 17_100_000,
+        }
+    );
+
+    let total = PriceQuote::sum(&quotes).unwrap();
+    assert_eq!(total.base, prices.iter().sum::<u64>());
+    assert_eq!(
+        total.vault_amount,
+        quotes.iter().map(|q| q.vault_amount).sum::<u64>()
+    );
+    assert_eq!(total.base, total.discount + total.vault_amount);
+    assert_eq!(total.total().unwrap(), total.vault_amount);
+    assert_eq!(PriceQuote::sum(&[]).unwrap(), PriceQuote::default());
+
+    let overflowing = PriceQuote {
+        vault_amount: u64::MAX,
+        ..quotes[0]
+    };
+    assert!(PriceQuote::sum(&[overflowing, quotes[1]]).is_err());
+    assert!(overflowing.total().is_ok());
+}